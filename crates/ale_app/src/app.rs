@@ -1,20 +1,35 @@
+use std::any::type_name;
 use std::collections::HashMap;
 use std::fmt::Display;
 
+use log::{info, warn};
+
+use ale_console::ale_console_execute;
+use ale_data::alevec::Key;
+use ale_input::Input;
 use ale_math::color::Color;
+use ale_math::random::Random;
 use ale_math::Vector2;
 use ale_opengl::renderer::task::RenderTask;
 use ale_opengl::{
-  ale_opengl_blend_enable, ale_opengl_clear_render, ale_opengl_clear_render_color, ale_opengl_depth_test_enable,
+  ale_opengl_blend_enable, ale_opengl_clear_render, ale_opengl_clear_render_color, ale_opengl_depth_reverse_z_set,
+  ale_opengl_depth_test_enable, ale_opengl_viewport_set,
 };
 use ale_render::target::RenderTargetType;
+use ale_resources::path::ResourcePath;
+use ale_window::backend::WindowBackend;
 use ale_window::display::DisplaySetting;
 use ale_window::window::Window;
+use ale_world::visitor::VisitorMut;
 use ale_world::world::World;
 
 use crate::engine::Engine;
-use crate::visitor::{CameraVisitor, FixedTickVisitor, InputVisitor, RenderableVisitor, TickVisitor};
-use crate::{init_term, AppError, FixedStep, WorldTick};
+use crate::plugin::Plugin;
+use crate::profiler::Profiler;
+use crate::state::StateStack;
+use crate::visitor::{CameraVisitor, FixedTickVisitor, InputVisitor, RenderableVisitor, ResizeVisitor, TickVisitor};
+use crate::config::{AppConfig, APP_CONFIG_USAGE};
+use crate::{init_term_with_level, AppError, FixedStep, WorldTick};
 
 pub trait Genesis {
   fn register_components(&self, world: &mut World);
@@ -26,51 +41,155 @@ pub trait Genesis {
 
 pub struct App {
   genesis: Box<dyn Genesis>,
+  plugins: Vec<Box<dyn Plugin>>,
+  states: StateStack,
+  config: Option<AppConfig>,
+  window_backend: Option<Box<dyn WindowBackend>>,
 }
 
 impl App {
   pub fn new<T: Genesis + 'static>(init: T) -> App {
     App {
       genesis: Box::new(init),
+      plugins: vec![],
+      states: StateStack::new(),
+      config: None,
+      window_backend: None,
     }
   }
 
+  // Plugins are run in the order they are added
+  pub fn add_plugin<T: Plugin + 'static>(mut self, plugin: T) -> App {
+    self.plugins.push(Box::new(plugin));
+    self
+  }
+
+  // Skips reading the config file and command line
+  pub fn with_config(mut self, config: AppConfig) -> App {
+    self.config = Some(config);
+    self
+  }
+
+  // GlfwBackend when not set. Without a GL context (HeadlessBackend) the app runs without rendering,
+  // the world is still visited for render tasks and cameras but plugins' render hooks aren't called.
+  pub fn with_window_backend<T: WindowBackend + 'static>(mut self, window_backend: T) -> App {
+    self.window_backend = Some(Box::new(window_backend));
+    self
+  }
+
+  // A bad config file or command line prints the error with the usage and exits with 2
   pub fn run(mut self) {
-    self.run_app_loop().unwrap();
+    let config = match self.config.take() {
+      Some(config) => config,
+      None => match AppConfig::from_env() {
+        Ok(config) => config,
+        Err(err) => {
+          eprintln!("{}\n{}", err, APP_CONFIG_USAGE);
+          std::process::exit(2);
+        }
+      },
+    };
+    self.run_app_loop(config).unwrap();
   }
 
-  fn run_app_loop(&mut self) -> anyhow::Result<()> {
-    init_term();
+  fn run_app_loop(&mut self, config: AppConfig) -> anyhow::Result<()> {
+    init_term_with_level(config.log_level());
+    if let Some(resource_root) = &config.resource_root {
+      ResourcePath::set_root(resource_root.clone());
+    }
 
-    let mut tick = WorldTick::FixedStep(FixedStep::new(0.01f32));
+    let mut tick = WorldTick::FixedStep(FixedStep::new(config.fixed_step()));
     let mut world = World::new();
 
     let mut display_setting = self.genesis.window();
-    let mut engine = Engine::new(display_setting)?;
+    config.apply_display_setting(&mut display_setting);
+    let window_backend = match self.window_backend.take() {
+      Some(window_backend) => window_backend,
+      None => intern_default_window_backend()?,
+    };
+    let mut engine = Engine::new(display_setting, window_backend)?;
+    let seed = config.seed();
+    info!("random seed {}, replay with --seed {}", seed, seed);
+    engine.random = Random::new(seed);
 
+    for plugin in &mut self.plugins {
+      plugin.register_components(&mut world);
+    }
     self.genesis.register_components(&mut world);
+
+    for plugin in &mut self.plugins {
+      plugin.init(&mut engine, &mut world)?;
+    }
     self.genesis.init(&mut engine, &mut world)?;
 
-    ale_opengl_depth_test_enable();
-    ale_opengl_blend_enable();
+    // genesis may push the initial state
+    self.states.handle_events(&mut engine);
+
+    // console variables are registered by now
+    for line in config.console_lines() {
+      ale_console_execute(&mut engine.console, &line);
+    }
+
+    if engine.windows.has_gl_context() {
+      ale_opengl_depth_test_enable();
+      ale_opengl_blend_enable();
+    }
+
+    // nothing reports the initial size, HiDPI windows start scaled
+    if let Some(window_key) = engine.windows.primary() {
+      self.apply_window_size(&mut engine, &mut world, window_key);
+    }
 
     while engine.windows.len() >= 1 {
+      engine.profiler.begin_frame();
+
+      engine.profiler.begin("poll");
       engine.windows.poll_inputs();
+      engine.profiler.end();
 
-      {
-        // handle input only for the first window
-        match engine.windows.iter_mut().next() {
-          None => {}
-          Some(w) => {
-            let mut input_vis = InputVisitor { input: w.input() };
-            world.visit_mut(&mut input_vis);
-          }
-        };
+      engine.profiler.begin("hot_reload");
+      for resource_reloaded in engine.hot_reload() {
+        for plugin in &mut self.plugins {
+          plugin.resource_reloaded(&mut engine, &resource_reloaded);
+        }
       }
+      engine.profiler.end();
 
       tick.prepare_tick();
       let delta_time = tick.delta_time();
 
+      {
+        engine.profiler.begin("input");
+        // handle input only for the first window
+        let mut inputs = match engine.windows.primary() {
+          None => vec![],
+          Some(window_key) => engine.windows.input(window_key),
+        };
+        inputs.extend(engine.windows.poll_gamepads());
+        if let Some(window_key) = engine.windows.primary() {
+          self.resize(&mut engine, &mut world, window_key, &inputs);
+        }
+
+        engine.input_state.begin_frame(delta_time);
+        for input in &inputs {
+          engine.input_state.handle_input(input);
+        }
+
+        for plugin in &mut self.plugins {
+          plugin.input(&mut engine, &inputs);
+        }
+        let mut input_vis = InputVisitor {
+          input: inputs,
+          input_state: &engine.input_state,
+        };
+        visit_profiled(&mut engine.profiler, &mut world, &mut input_vis);
+        if let Some(state_world) = self.states.active_world_mut() {
+          visit_profiled(&mut engine.profiler, state_world, &mut input_vis);
+        }
+        engine.profiler.end();
+      }
+
+      engine.profiler.begin("fixed_tick");
       while tick.should_tick() {
         tick.tick();
 
@@ -78,64 +197,169 @@ impl App {
         let mut fixed_tick_vis = FixedTickVisitor {
           delta_time: tick.delta_time(),
         };
-        world.visit_mut(&mut fixed_tick_vis);
+        visit_profiled(&mut engine.profiler, &mut world, &mut fixed_tick_vis);
+        if let Some(state_world) = self.states.active_world_mut() {
+          visit_profiled(&mut engine.profiler, state_world, &mut fixed_tick_vis);
+        }
+
+        for plugin in &mut self.plugins {
+          plugin.fixed_tick(&mut engine, &mut world, tick.delta_time());
+        }
       }
+      engine.profiler.end();
 
+      engine.profiler.begin("resolve_world_commands");
       world.resolve_world_commands();
+      for state_world in self.states.worlds_mut() {
+        state_world.resolve_world_commands();
+      }
+      engine.profiler.end();
 
       //tick
+      engine.profiler.begin("tick");
       let mut tick_vis = TickVisitor { delta_time };
-      world.visit_mut(&mut tick_vis);
+      visit_profiled(&mut engine.profiler, &mut world, &mut tick_vis);
+      if let Some(state_world) = self.states.active_world_mut() {
+        visit_profiled(&mut engine.profiler, state_world, &mut tick_vis);
+      }
+
+      for plugin in &mut self.plugins {
+        plugin.tick(&mut engine, &mut world, delta_time);
+      }
+      engine.profiler.end();
 
       // render
       self.render(&mut engine, &mut world);
 
+      // state transitions are applied at the end of the frame
+      self.states.handle_events(&mut engine);
+
       // cleanup
       engine.windows.cleanup();
+
+      engine.profiler.end_frame();
     }
 
     Ok(())
   }
 
+  // The inputs already carry the resize for plugins and Inputable components,
+  // everything the engine owns that depends on the framebuffer size is updated here
+  fn resize(&mut self, engine: &mut Engine, world: &mut World, window_key: Key<Window>, inputs: &Vec<Input>) {
+    let is_resized = inputs
+      .iter()
+      .any(|input| matches!(input, Input::FramebufferResize { .. } | Input::ContentScale { .. }));
+    if is_resized {
+      self.apply_window_size(engine, world, window_key);
+    }
+  }
+
+  // The viewport and cameras get the framebuffer size, the root panel the logical size
+  fn apply_window_size(&mut self, engine: &mut Engine, world: &mut World, window_key: Key<Window>) {
+    let (framebuffer_size, screen_size, content_scale, panel_key) = match engine.windows.get(window_key) {
+      None => return,
+      Some(window) => (
+        window.get_framebuffer_size(),
+        window.get_screen_size(),
+        window.get_content_scale().x,
+        window.panel_key,
+      ),
+    };
+
+    if engine.windows.has_gl_context() {
+      ale_opengl_viewport_set(framebuffer_size);
+    }
+    if let Some(text_renderer) = &mut engine.text_renderer {
+      text_renderer.set_content_scale(content_scale);
+    }
+
+    if let Some(panel) = panel_key.and_then(|panel_key| engine.panels.get_mut(panel_key)) {
+      panel.resize(screen_size);
+    }
+
+    let mut resize_vis = ResizeVisitor {
+      size: framebuffer_size,
+      content_scale,
+    };
+    visit_profiled(&mut engine.profiler, world, &mut resize_vis);
+    for state_world in self.states.worlds_mut() {
+      visit_profiled(&mut engine.profiler, state_world, &mut resize_vis);
+    }
+  }
+
   // This function takes first window and first camera found.
   // Too hard to do multiple windows and multiple camera for now
   // Will implement when the use case arise.
   fn render(&mut self, engine: &mut Engine, world: &mut World) {
+    engine.profiler.begin("render");
     let mut renderable_vis = RenderableVisitor {
       render_tasks: Vec::new(),
     };
-    world.visit_mut(&mut renderable_vis);
+    visit_profiled(&mut engine.profiler, world, &mut renderable_vis);
 
     let mut camera_vis = CameraVisitor {
       camera_render_info: HashMap::new(),
     };
-    world.visit_mut(&mut camera_vis);
+    visit_profiled(&mut engine.profiler, world, &mut camera_vis);
+
+    for state_world in self.states.visible_worlds_mut() {
+      visit_profiled(&mut engine.profiler, state_world, &mut renderable_vis);
+      visit_profiled(&mut engine.profiler, state_world, &mut camera_vis);
+    }
 
     // Just take first window we found
-    let mut window = match engine.windows.iter_mut().next() {
-      None => {
+    let window_key = match engine.windows.primary() {
+      Some(window_key) if engine.windows.has_gl_context() => window_key,
+      _ => {
+        engine.profiler.end();
         return;
       }
-      Some(w) => w,
     };
-    window.make_current();
-    ale_opengl_clear_render();
+    engine.windows.make_current(window_key);
 
     // Just take whatever first camera we found
-    if let Some((_, camera)) = camera_vis.camera_render_info.iter().next() {
+    let camera = camera_vis.camera_render_info.values().next();
+
+    // post_render needs a camera, pre_render is skipped along with it so plugins always get both or neither
+    if camera.is_some() {
+      for plugin in &mut self.plugins {
+        plugin.pre_render(engine);
+      }
+    }
+    ale_opengl_depth_reverse_z_set(camera.map_or(false, |camera| camera.is_reverse_z));
+    ale_opengl_clear_render();
+
+    if let Some(camera) = camera {
       for renderable in renderable_vis.render_tasks {
         match renderable {
-          RenderTask::StaticMesh(_) => {}
+          RenderTask::StaticMesh(static_mesh) => {
+            if let Some(static_mesh_renderer) = &mut engine.static_mesh_renderer {
+              if let Err(err) = static_mesh_renderer.render(&mut engine.resources, &static_mesh, camera) {
+                warn!("{}", err);
+              }
+            }
+          }
           RenderTask::Sprite(sprite) => {
-            engine
-              .sprite_renderer
-              .render_flat_box(sprite.position, sprite.size, sprite.color, camera.orthographic)
+            if let Some(sprite_renderer) = &engine.sprite_renderer {
+              sprite_renderer.render_flat_box(sprite.position, sprite.size, sprite.color, camera.orthographic);
+            }
           }
         }
       }
+
+      for plugin in &mut self.plugins {
+        plugin.render(engine, camera);
+      }
+      for plugin in &mut self.plugins {
+        plugin.post_render(engine, camera);
+      }
     }
 
-    window.swap_buffers();
+    engine.profiler.end();
+
+    engine.profiler.begin("swap");
+    engine.windows.swap_buffers(window_key);
+    engine.profiler.end();
 
     // TODO: this needs to be cooked more before implement
     // // TODO: Group by render target in here instead
@@ -200,3 +424,105 @@ impl App {
   // }
   // }
 }
+
+#[cfg(feature = "glfw")]
+fn intern_default_window_backend() -> Result<Box<dyn WindowBackend>, AppError> {
+  Ok(Box::new(ale_window::glfw_backend::GlfwBackend::new()))
+}
+
+#[cfg(not(feature = "glfw"))]
+fn intern_default_window_backend() -> Result<Box<dyn WindowBackend>, AppError> {
+  Err(anyhow::anyhow!("no window backend, enable the glfw feature or use App::with_window_backend"))
+}
+
+// Times a single world visit, scope is named after the visited component trait
+// with a nested scope for each component type implementing it
+fn visit_profiled<T: ?Sized + 'static>(profiler: &mut Profiler, world: &mut World, visitor: &mut dyn VisitorMut<T>) {
+  profiler.begin(type_name::<T>());
+  world.visit_mut_scoped(visitor, profiler);
+  profiler.end();
+}
+
+#[test]
+fn app_runs_headless() {
+  use std::cell::RefCell;
+  use std::rc::Rc;
+
+  use ale_input::{Action, Key, Modifier};
+  use ale_math::rect::Rect;
+  use ale_window::headless::HeadlessBackend;
+
+  struct HeadlessGenesis;
+
+  impl Genesis for HeadlessGenesis {
+    fn register_components(&self, _world: &mut World) {}
+
+    fn window(&self) -> DisplaySetting {
+      DisplaySetting::new(Rect {
+        position: Vector2::new(0, 0),
+        size: Vector2::new(800, 600),
+      })
+    }
+
+    fn init(&self, _engine: &mut Engine, _world: &mut World) -> Result<(), AppError> {
+      Ok(())
+    }
+  }
+
+  #[derive(Default)]
+  struct Frames {
+    ticks: usize,
+    input_count: usize,
+    is_space_down: bool,
+    framebuffer_size: Option<Vector2<u32>>,
+  }
+
+  // Records what every frame sees and closes the window on the third tick
+  struct FramesPlugin(Rc<RefCell<Frames>>);
+
+  impl Plugin for FramesPlugin {
+    fn input(&mut self, _engine: &mut Engine, inputs: &Vec<Input>) {
+      self.0.borrow_mut().input_count += inputs.len();
+    }
+
+    fn tick(&mut self, engine: &mut Engine, _world: &mut World, _delta_time: f32) {
+      let mut frames = self.0.borrow_mut();
+      frames.ticks += 1;
+      frames.is_space_down |= engine.input_state.is_down(Key::Space);
+
+      let window_key = engine.windows.primary().unwrap();
+      frames.framebuffer_size = engine.windows.get(window_key).map(|window| window.get_framebuffer_size());
+      if frames.ticks == 3 {
+        engine.windows.close(window_key);
+      }
+    }
+  }
+
+  let backend = HeadlessBackend::new(Vector2::new(800, 600));
+  let input_sender = backend.input_sender();
+  input_sender
+    .send(Input::Key(Key::Space, 0, Action::Press, Modifier::empty()))
+    .unwrap();
+  input_sender
+    .send(Input::FramebufferResize {
+      width: 1024,
+      height: 768,
+    })
+    .unwrap();
+
+  let frames = Rc::new(RefCell::new(Frames::default()));
+  let mut app = App::new(HeadlessGenesis)
+    .add_plugin(FramesPlugin(frames.clone()))
+    .with_window_backend(backend);
+  let config = AppConfig {
+    seed: Some(1),
+    ..AppConfig::default()
+  };
+  app.run_app_loop(config).unwrap();
+
+  let frames = frames.borrow();
+  assert_eq!(frames.ticks, 3);
+  assert_eq!(frames.input_count, 2);
+  assert!(frames.is_space_down);
+  assert_eq!(frames.framebuffer_size, Some(Vector2::new(1024, 768)));
+}
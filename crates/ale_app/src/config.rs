@@ -0,0 +1,351 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::{env, fs, io};
+
+use log::LevelFilter;
+use serde::Deserialize;
+use thiserror::Error;
+
+use ale_math::random::Random;
+use ale_window::display::{DisplaySetting, TargetMonitor, WindowMode};
+
+// Looked up next to the executable
+pub const APP_CONFIG_FILE: &str = "alers.toml";
+
+// Printed along with AppConfigError when the command line can't be parsed
+pub const APP_CONFIG_USAGE: &str = "usage: [--width <u32>] [--height <u32>] [--title <string>] [--vsync <bool>]
+       [--window-mode <windowed|borderless|fullscreen>] [--monitor <index>] [--msaa-samples <u32>]
+       [--log <off|error|warn|info|debug|trace>] [--tick-rate <f32>] [--resource-root <path>]
+       [--seed <u64>] [--set <console_variable>=<value>]...";
+
+// Everything is optional, anything not set falls back to what Genesis provides.
+//
+//   log = "info"
+//   tick_rate = 100.0
+//   resource_root = "../resources"
+//   seed = 42
+//
+//   [window]
+//   width = 1280
+//   height = 720
+//   title = "Tetris"
+//   vsync = true
+//   mode = "borderless"
+//   monitor = 1
+//   msaa_samples = 4
+//
+//   [console]
+//   fxaa_is_enabled = false
+//
+// Command line: --width 1280 --height 720 --title Tetris --vsync true --log info
+//               --window-mode fullscreen --monitor 1 --msaa-samples 4
+//               --tick-rate 60 --resource-root ../resources --seed 42 --set fxaa_is_enabled=false
+#[derive(Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct AppConfig {
+  pub window: WindowConfig,
+  pub log: Option<String>,
+  // fixed ticks per second
+  pub tick_rate: Option<f32>,
+  pub resource_root: Option<PathBuf>,
+  // Engine::random seed, taken from the clock when not set
+  pub seed: Option<u64>,
+  // console variables, applied after every plugin has been initialized
+  pub console: BTreeMap<String, toml::Value>,
+}
+
+#[derive(Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct WindowConfig {
+  pub width: Option<u32>,
+  pub height: Option<u32>,
+  pub title: Option<String>,
+  pub vsync: Option<bool>,
+  pub mode: Option<WindowModeConfig>,
+  // 0 is the primary monitor
+  pub monitor: Option<usize>,
+  pub msaa_samples: Option<u32>,
+}
+
+#[derive(Deserialize, Copy, Clone, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum WindowModeConfig {
+  Windowed,
+  Borderless,
+  // at the monitor's current video mode
+  Fullscreen,
+}
+
+impl FromStr for WindowModeConfig {
+  type Err = ();
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "windowed" => Ok(WindowModeConfig::Windowed),
+      "borderless" => Ok(WindowModeConfig::Borderless),
+      "fullscreen" => Ok(WindowModeConfig::Fullscreen),
+      _ => Err(()),
+    }
+  }
+}
+
+#[derive(Error, Debug)]
+pub enum AppConfigError {
+  #[error("(AppConfigError::IOError)\nPath: {}\nError: {}", .1, .0)]
+  IOError(io::Error, String),
+  #[error("(AppConfigError::ParseError)\nPath: {}\nError: {}", .1, .0)]
+  ParseError(toml::de::Error, String),
+  #[error("(AppConfigError::UnknownFlag) {}", .0)]
+  UnknownFlag(String),
+  #[error("(AppConfigError::MissingValue) {} needs a value", .0)]
+  MissingValue(String),
+  #[error("(AppConfigError::InvalidValue) {} can't take {}", .0, .1)]
+  InvalidValue(String, String),
+}
+
+impl AppConfig {
+  // Config file next to the executable, then command line flags on top of it
+  pub fn from_env() -> Result<AppConfig, AppConfigError> {
+    let mut config = match env::current_exe() {
+      Ok(exe) => AppConfig::load_or_default(&exe.with_file_name(APP_CONFIG_FILE))?,
+      Err(_) => AppConfig::default(),
+    };
+    config.apply_args(env::args().skip(1))?;
+    Ok(config)
+  }
+
+  pub fn load_or_default(path: &Path) -> Result<AppConfig, AppConfigError> {
+    if !path.exists() {
+      return Ok(AppConfig::default());
+    }
+    let path_str = path.to_string_lossy().to_string();
+    let content = fs::read_to_string(path).map_err(|e| AppConfigError::IOError(e, path_str.clone()))?;
+    let config = AppConfig::parse(&content).map_err(|e| AppConfigError::ParseError(e, path_str))?;
+    config.validate()?;
+    Ok(config)
+  }
+
+  // Values toml can't reject by type alone, checked the same way as their command line flag
+  pub fn validate(&self) -> Result<(), AppConfigError> {
+    if let Some(log) = &self.log {
+      intern_parse::<LevelFilter>("log", log)?;
+    }
+    Ok(())
+  }
+
+  pub fn parse(content: &str) -> Result<AppConfig, toml::de::Error> {
+    toml::from_str(content)
+  }
+
+  pub fn apply_args<I: Iterator<Item = String>>(&mut self, mut args: I) -> Result<(), AppConfigError> {
+    while let Some(flag) = args.next() {
+      let value = match args.next() {
+        None => return Err(AppConfigError::MissingValue(flag)),
+        Some(value) => value,
+      };
+
+      match flag.as_str() {
+        "--width" => self.window.width = Some(intern_parse(&flag, &value)?),
+        "--height" => self.window.height = Some(intern_parse(&flag, &value)?),
+        "--title" => self.window.title = Some(value),
+        "--vsync" => self.window.vsync = Some(intern_parse(&flag, &value)?),
+        "--window-mode" => self.window.mode = Some(intern_parse(&flag, &value)?),
+        "--monitor" => self.window.monitor = Some(intern_parse(&flag, &value)?),
+        "--msaa-samples" => self.window.msaa_samples = Some(intern_parse(&flag, &value)?),
+        "--log" => {
+          intern_parse::<LevelFilter>(&flag, &value)?;
+          self.log = Some(value);
+        }
+        "--tick-rate" => self.tick_rate = Some(intern_parse(&flag, &value)?),
+        "--resource-root" => self.resource_root = Some(PathBuf::from(value)),
+        "--seed" => self.seed = Some(intern_parse(&flag, &value)?),
+        "--set" => {
+          let (name, variable) = match value.split_once('=') {
+            None => return Err(AppConfigError::InvalidValue(flag, value)),
+            Some(pair) => pair,
+          };
+          self
+            .console
+            .insert(name.to_owned(), toml::Value::String(variable.to_owned()));
+        }
+        _ => return Err(AppConfigError::UnknownFlag(flag)),
+      }
+    }
+    Ok(())
+  }
+
+  pub fn apply_display_setting(&self, display_setting: &mut DisplaySetting) {
+    if let Some(width) = self.window.width {
+      display_setting.dimension.size.x = width as i32;
+    }
+    if let Some(height) = self.window.height {
+      display_setting.dimension.size.y = height as i32;
+    }
+    if let Some(title) = &self.window.title {
+      display_setting.title = title.clone();
+    }
+    if let Some(vsync) = self.window.vsync {
+      display_setting.vsync = vsync;
+    }
+    if let Some(mode) = self.window.mode {
+      display_setting.window_mode = match mode {
+        WindowModeConfig::Windowed => WindowMode::Windowed,
+        WindowModeConfig::Borderless => WindowMode::Borderless,
+        WindowModeConfig::Fullscreen => WindowMode::Fullscreen(None),
+      };
+    }
+    if let Some(monitor) = self.window.monitor {
+      display_setting.monitor = TargetMonitor::from_index(monitor);
+    }
+    if let Some(msaa_samples) = self.window.msaa_samples {
+      display_setting.msaa_samples = if msaa_samples > 0 { Some(msaa_samples) } else { None };
+    }
+  }
+
+  pub fn log_level(&self) -> LevelFilter {
+    self
+      .log
+      .as_ref()
+      .and_then(|log| LevelFilter::from_str(log).ok())
+      .unwrap_or(LevelFilter::Debug)
+  }
+
+  // Fixed step in seconds
+  pub fn fixed_step(&self) -> f32 {
+    match self.tick_rate {
+      Some(tick_rate) if tick_rate > 0.0 => 1.0 / tick_rate,
+      _ => 0.01,
+    }
+  }
+
+  // A new clock based seed every call when none is set
+  pub fn seed(&self) -> u64 {
+    self.seed.unwrap_or_else(Random::seed_from_time)
+  }
+
+  // Console lines for every console variable, e.g. "fxaa_is_enabled false"
+  pub fn console_lines(&self) -> Vec<String> {
+    self
+      .console
+      .iter()
+      .map(|(name, value)| {
+        let value = match value {
+          toml::Value::String(s) => s.clone(),
+          toml::Value::Array(values) => values.iter().map(|v| v.to_string()).collect::<Vec<String>>().join(" "),
+          value => value.to_string(),
+        };
+        format!("{} {}", name, value)
+      })
+      .collect()
+  }
+}
+
+fn intern_parse<T: FromStr>(flag: &str, value: &str) -> Result<T, AppConfigError> {
+  value
+    .parse::<T>()
+    .map_err(|_| AppConfigError::InvalidValue(flag.to_owned(), value.to_owned()))
+}
+
+#[test]
+fn app_config_parse() {
+  let config = AppConfig::parse(
+    r#"
+    log = "info"
+    tick_rate = 50.0
+
+    [window]
+    width = 1280
+    title = "Tetris"
+
+    [console]
+    fxaa_is_enabled = false
+    fxaa_color = [1.0, 0.5, 0.0]
+    "#,
+  )
+  .unwrap();
+
+  assert_eq!(config.log_level(), LevelFilter::Info);
+  assert_eq!(config.fixed_step(), 0.02);
+  assert_eq!(config.window.width, Some(1280));
+  assert_eq!(config.window.height, None);
+  assert_eq!(config.window.title, Some("Tetris".to_owned()));
+  assert_eq!(
+    config.console_lines(),
+    vec!["fxaa_color 1.0 0.5 0.0".to_owned(), "fxaa_is_enabled false".to_owned()]
+  );
+}
+
+#[test]
+fn app_config_args_override_file() {
+  let mut config = AppConfig::parse("[window]\nwidth = 800\nheight = 600").unwrap();
+  let args = vec!["--width", "1280", "--log", "warn", "--set", "fxaa_is_enabled=false"];
+  config.apply_args(args.into_iter().map(|s| s.to_owned())).unwrap();
+
+  assert_eq!(config.window.width, Some(1280));
+  assert_eq!(config.window.height, Some(600));
+  assert_eq!(config.log_level(), LevelFilter::Warn);
+  assert_eq!(config.console_lines(), vec!["fxaa_is_enabled false".to_owned()]);
+
+  config.apply_args(vec!["--seed".to_owned(), "42".to_owned()].into_iter()).unwrap();
+  assert_eq!(config.seed(), 42);
+}
+
+#[test]
+fn app_config_window_mode() {
+  use ale_math::rect::Rect;
+  use ale_math::Vector2;
+
+  let mut config = AppConfig::parse("[window]\nmode = \"borderless\"\nmonitor = 1").unwrap();
+  let args = vec!["--msaa-samples", "4"];
+  config.apply_args(args.into_iter().map(|s| s.to_owned())).unwrap();
+
+  let mut display_setting = DisplaySetting::new(Rect {
+    position: Vector2::new(0, 0),
+    size: Vector2::new(800, 600),
+  });
+  config.apply_display_setting(&mut display_setting);
+  assert_eq!(display_setting.window_mode, WindowMode::Borderless);
+  assert_eq!(display_setting.monitor, TargetMonitor::SECOND);
+  assert_eq!(display_setting.msaa_samples, Some(4));
+
+  assert!(matches!(
+    config.apply_args(vec!["--window-mode".to_owned(), "maximized".to_owned()].into_iter()),
+    Err(AppConfigError::InvalidValue(_, _))
+  ));
+}
+
+#[test]
+fn app_config_args_errors() {
+  let mut config = AppConfig::default();
+  let to_args = |args: Vec<&str>| args.into_iter().map(|s| s.to_owned()).collect::<Vec<String>>().into_iter();
+
+  assert!(matches!(
+    config.apply_args(to_args(vec!["--fullscreen", "true"])),
+    Err(AppConfigError::UnknownFlag(_))
+  ));
+  assert!(matches!(
+    config.apply_args(to_args(vec!["--width"])),
+    Err(AppConfigError::MissingValue(_))
+  ));
+  assert!(matches!(
+    config.apply_args(to_args(vec!["--width", "wide"])),
+    Err(AppConfigError::InvalidValue(_, _))
+  ));
+  assert!(matches!(
+    config.apply_args(to_args(vec!["--set", "fxaa_is_enabled"])),
+    Err(AppConfigError::InvalidValue(_, _))
+  ));
+}
+
+#[test]
+fn app_config_file_invalid_log() {
+  let path = env::temp_dir().join("ale_app_config_file_invalid_log.toml");
+  fs::write(&path, "log = \"verbose\"").unwrap();
+  let result = AppConfig::load_or_default(&path);
+  fs::remove_file(&path).unwrap();
+
+  assert!(matches!(result, Err(AppConfigError::InvalidValue(_, _))));
+  assert!(AppConfig::parse("log = \"loud\"").unwrap().validate().is_err());
+  assert!(AppConfig::parse("log = \"trace\"").unwrap().validate().is_ok());
+}
@@ -1,44 +1,75 @@
 use ale_data::alevec;
 use log::info;
 use std::collections::HashMap;
+use std::time::Duration;
 
+use ale_console::{ale_console_new, ale_console_print_output, Console};
 use ale_data::alevec::AleVec;
 use ale_data::channel::Channel;
 use ale_data::indexmap::Id;
+use ale_input::state::InputState;
+use ale_math::random::Random;
 use ale_math::rect::Rect;
 use ale_math::{Vector2, Zero};
 use ale_opengl::renderer::sprite::SpriteRenderer;
+use ale_opengl::renderer::static_mesh::StaticMeshRenderer;
 use ale_opengl::renderer::text::TextRenderer;
 use ale_render::target::RenderTargets;
-use ale_resources::resources::Resources;
+use ale_resources::path::ResourcePath;
+use ale_resources::resources::{ResourceReloaded, Resources};
+use ale_resources::watcher::ResourceWatcher;
 use ale_ui::panels::Panels;
-use ale_window::backend::Windows;
-use ale_window::display::{DisplaySetting, TargetMonitor};
+use ale_window::backend::{WindowBackend, Windows};
+use ale_window::display::DisplaySetting;
 
 use crate::event::EngineEvent;
+use crate::profiler::Profiler;
+use crate::state::StateTransition;
 use crate::AppError;
 
+const PROFILER_MAX_FRAMES: usize = 300;
+const RESOURCE_WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
 pub struct Engine {
   pub windows: Windows,
   pub panels: Panels,
   pub resources: Resources,
   pub render_targets: RenderTargets,
 
-  pub text_renderer: TextRenderer,
-  pub sprite_renderer: SpriteRenderer,
+  // None when the window backend has no GL context, e.g. HeadlessBackend
+  pub text_renderer: Option<TextRenderer>,
+  pub sprite_renderer: Option<SpriteRenderer>,
+  pub static_mesh_renderer: Option<StaticMeshRenderer>,
+
+  pub input_state: InputState,
+  pub console: Console,
+  pub profiler: Profiler,
+  pub resource_watcher: ResourceWatcher,
+  // seeded from AppConfig, fork it per system to keep them reproducible
+  pub random: Random,
 
   pub event_queue: Channel<EngineEvent>,
+  // applied by the App's StateStack at the end of every frame
+  pub state_queue: Channel<StateTransition>,
 }
 
 impl Engine {
-  pub fn new(display_setting: DisplaySetting) -> Result<Engine, AppError> {
+  // The renderers are created right after the window when window_backend has a GL context
+  pub fn new(display_setting: DisplaySetting, window_backend: Box<dyn WindowBackend>) -> Result<Engine, AppError> {
     let mut resources = Resources::new();
 
-    let mut windows = Windows::new();
+    let mut windows = Windows::with_backend(window_backend);
     windows.add(display_setting);
 
-    let text_renderer = TextRenderer::new_with_resources(&mut resources)?;
-    let sprite_renderer = SpriteRenderer::new_with_resource(&mut resources)?;
+    let (text_renderer, sprite_renderer, static_mesh_renderer) = if windows.has_gl_context() {
+      (
+        Some(TextRenderer::new_with_resources(&mut resources)?),
+        Some(SpriteRenderer::new_with_resource(&mut resources)?),
+        Some(StaticMeshRenderer::new_with_resource(&mut resources)?),
+      )
+    } else {
+      (None, None, None)
+    };
 
     Ok(Engine {
       windows,
@@ -47,7 +78,61 @@ impl Engine {
       render_targets: RenderTargets::new(),
       text_renderer,
       sprite_renderer,
+      static_mesh_renderer,
+      input_state: InputState::new(),
+      console: ale_console_new(100),
+      profiler: Profiler::new(PROFILER_MAX_FRAMES),
+      resource_watcher: ResourceWatcher::new(&ResourcePath::root(), RESOURCE_WATCH_INTERVAL),
+      random: Random::new(Random::seed_from_time()),
       event_queue: Channel::new(),
+      state_queue: Channel::new(),
     })
   }
+
+  // Reloads resources changed on disk and recompiles the engine renderers' shaders.
+  // Failures keep the old resource and are printed to the console.
+  pub fn hot_reload(&mut self) -> Vec<ResourceReloaded> {
+    let mut reloaded = vec![];
+    for changed_path in self.resource_watcher.poll() {
+      let changed_path = changed_path.to_string_lossy();
+      let (r, errors) = self.resources.reload(&changed_path);
+      reloaded.extend(r);
+      for err in errors {
+        ale_console_print_output(&mut self.console, &format!("fail to reload {}\n{}", changed_path, err));
+      }
+    }
+
+    for r in &reloaded {
+      match r {
+        ResourceReloaded::Shader(key) => {
+          let shader = match self.resources.shaders.stash.get(*key) {
+            None => continue,
+            Some(shader) => shader,
+          };
+          let mut errors = vec![];
+          if let Some(Err(err)) = self.text_renderer.as_mut().map(|r| r.reload_shader(*key, shader)) {
+            errors.push(format!("{}", err));
+          }
+          if let Some(Err(err)) = self.sprite_renderer.as_mut().map(|r| r.reload_shader(*key, shader)) {
+            errors.push(format!("{}", err));
+          }
+          if let Some(Err(err)) = self.static_mesh_renderer.as_mut().map(|r| r.reload_shader(*key, shader)) {
+            errors.push(format!("{}", err));
+          }
+          for err in errors {
+            ale_console_print_output(&mut self.console, &err);
+          }
+        }
+        ResourceReloaded::Mesh(key) => {
+          if let Some(static_mesh_renderer) = &mut self.static_mesh_renderer {
+            static_mesh_renderer.reload_mesh(*key);
+          }
+        }
+        // the engine renderers don't upload resource textures, plugins re-upload theirs in resource_reloaded
+        ResourceReloaded::Texture(_) => {}
+      }
+    }
+
+    reloaded
+  }
 }
@@ -5,6 +5,8 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use thiserror::Error;
 
 use ale_input::Input;
+use ale_math::Vector2;
+use ale_opengl::ale_opengl_viewport_set;
 use ale_opengl::old::opengl::{RenderResources, SimpleRenderTasks};
 
 use ale_window::display::DisplaySetting;
@@ -22,9 +24,13 @@ use ale_world::world;
 use ale_world::world::World;
 
 pub mod app;
+pub mod config;
 pub mod engine;
 pub mod visitor;
 pub mod event;
+pub mod plugin;
+pub mod profiler;
+pub mod state;
 
 // TODO: Break this to 1 function per trait
 pub trait App<S> {
@@ -39,6 +45,7 @@ pub trait App<S> {
   fn render(&mut self, s: &mut S);
 }
 
+#[cfg(feature = "glfw")]
 pub fn ale_app_run<S, T: App<S>>(mut app: T, display_info: DisplaySetting) {
   let err = ale_app_run_internal(app, display_info);
   match err {
@@ -49,6 +56,7 @@ pub fn ale_app_run<S, T: App<S>>(mut app: T, display_info: DisplaySetting) {
   }
 }
 
+#[cfg(feature = "glfw")]
 pub fn ale_app_run_internal<S, T: App<S>>(mut app: T, display_info: DisplaySetting) -> anyhow::Result<()> {
   // Initialize File Logging
   init_term();
@@ -56,18 +64,43 @@ pub fn ale_app_run_internal<S, T: App<S>>(mut app: T, display_info: DisplaySetti
   // Initialize the engine
   let mut windows = Windows::new();
   let mut window_key = windows.add(display_info);
+  windows.make_current(window_key);
 
   let window = windows.get(window_key).unwrap();
+  let framebuffer_size = window.get_framebuffer_size();
+  let content_scale = window.get_content_scale();
   let mut state = app.load(window)?;
 
+  // The framebuffer can differ from the requested size on HiDPI displays, seed the viewport
+  // and let the app size its camera and text the same way it handles a resize
+  ale_opengl_viewport_set(framebuffer_size);
+  app.input(
+    &mut state,
+    vec![
+      Input::FramebufferResize {
+        width: framebuffer_size.x,
+        height: framebuffer_size.y,
+      },
+      Input::ContentScale {
+        x: content_scale.x,
+        y: content_scale.y,
+      },
+    ],
+  );
+
   let mut tick = WorldTick::FixedStep(FixedStep::new(0.01f32));
 
   // Main Game Loop
   while windows.len() != 0 {
     windows.poll_inputs();
 
-    let window = windows.get_mut(window_key).unwrap();
-    app.input(&mut state, window.input());
+    let inputs = windows.input(window_key);
+    for input in &inputs {
+      if let Input::FramebufferResize { width, height } = input {
+        ale_opengl_viewport_set(Vector2::new(*width, *height));
+      }
+    }
+    app.input(&mut state, inputs);
 
     tick.prepare_tick();
     while tick.should_tick() {
@@ -78,7 +111,7 @@ pub fn ale_app_run_internal<S, T: App<S>>(mut app: T, display_info: DisplaySetti
     app.tick(&mut state);
     app.render(&mut state);
 
-    window.swap_buffers();
+    windows.swap_buffers(window_key);
     windows.cleanup();
   }
 
@@ -113,7 +146,11 @@ pub fn init() {
 }
 
 pub fn init_term() {
-  TermLogger::init(LevelFilter::Debug,
+  init_term_with_level(LevelFilter::Debug);
+}
+
+pub fn init_term_with_level(level: LevelFilter) {
+  TermLogger::init(level,
                    Config::default(),
                    TerminalMode::Mixed,
                    ColorChoice::Auto).unwrap();
@@ -0,0 +1,63 @@
+use ale_camera::CameraRenderInfo;
+use ale_console::ale_console_input;
+use ale_data::alevec::Key;
+use ale_input::Input;
+use ale_math::Vector2;
+use ale_opengl::console::ale_opengl_console_render;
+use ale_resources::font::Font;
+use ale_world::world::World;
+
+use crate::engine::Engine;
+use crate::plugin::Plugin;
+use crate::AppError;
+
+const CONSOLE_FONT: &str = "font/Inconsolata-Regular.ttf";
+
+// Feeds input to the engine console and draws it on top of everything else
+pub struct ConsolePlugin {
+  font: Option<Key<Font>>,
+}
+
+impl ConsolePlugin {
+  pub fn new() -> ConsolePlugin {
+    ConsolePlugin { font: None }
+  }
+}
+
+impl Plugin for ConsolePlugin {
+  fn init(&mut self, engine: &mut Engine, _world: &mut World) -> Result<(), AppError> {
+    self.font = engine.resources.fonts.load(CONSOLE_FONT)?.into_iter().next();
+    Ok(())
+  }
+
+  fn input(&mut self, engine: &mut Engine, inputs: &Vec<Input>) {
+    for input in inputs {
+      ale_console_input(&mut engine.console, input);
+    }
+  }
+
+  fn post_render(&mut self, engine: &mut Engine, camera_render_info: &CameraRenderInfo) {
+    let (font_key, text_renderer) = match (self.font, &mut engine.text_renderer) {
+      (Some(font_key), Some(text_renderer)) => (font_key, text_renderer),
+      _ => return,
+    };
+
+    let screen_size = match engine.windows.iter().next() {
+      None => return,
+      Some(window) => window.get_screen_size(),
+    };
+
+    let font = match engine.resources.fonts.get_mut(font_key) {
+      None => return,
+      Some(font) => font,
+    };
+
+    ale_opengl_console_render(
+      text_renderer,
+      camera_render_info,
+      &engine.console,
+      Vector2::new(screen_size.x as i32, screen_size.y as i32),
+      font,
+    );
+  }
+}
@@ -0,0 +1,66 @@
+use ale_camera::CameraRenderInfo;
+use ale_data::channel::{Channel, Sender};
+use ale_math::Vector3;
+use ale_opengl::debug::line::{
+  ale_opengl_debug_context_new, ale_opengl_debug_line_clear, ale_opengl_debug_line_queue,
+  ale_opengl_debug_point_clear, ale_opengl_debug_point_queue, ale_opengl_debug_render, OpenGLDebugContext,
+};
+use ale_world::world::World;
+
+use crate::engine::Engine;
+use crate::plugin::Plugin;
+use crate::AppError;
+
+pub enum DebugDraw {
+  // start, end, color
+  Line(Vector3<f32>, Vector3<f32>, Vector3<f32>),
+  // position, color
+  Point(Vector3<f32>, Vector3<f32>),
+}
+
+// Renders lines and points sent through its channel, everything sent is cleared after every frame
+pub struct DebugLinePlugin {
+  opengl_debug_context: Option<OpenGLDebugContext>,
+  channel: Channel<DebugDraw>,
+}
+
+impl DebugLinePlugin {
+  pub fn new() -> DebugLinePlugin {
+    DebugLinePlugin {
+      opengl_debug_context: None,
+      channel: Channel::new(),
+    }
+  }
+
+  pub fn get_debug_draw_sender(&self) -> Sender<DebugDraw> {
+    self.channel.sender.clone()
+  }
+}
+
+impl Plugin for DebugLinePlugin {
+  fn init(&mut self, engine: &mut Engine, _world: &mut World) -> Result<(), AppError> {
+    if engine.windows.has_gl_context() {
+      self.opengl_debug_context = Some(ale_opengl_debug_context_new());
+    }
+    Ok(())
+  }
+
+  fn render(&mut self, _engine: &mut Engine, camera_render_info: &CameraRenderInfo) {
+    let opengl_debug_context = match &mut self.opengl_debug_context {
+      None => return,
+      Some(ctx) => ctx,
+    };
+
+    for debug_draw in self.channel.receiver.try_iter() {
+      match debug_draw {
+        DebugDraw::Line(start, end, color) => ale_opengl_debug_line_queue(opengl_debug_context, start, end, color),
+        DebugDraw::Point(position, color) => ale_opengl_debug_point_queue(opengl_debug_context, position, color),
+      }
+    }
+
+    ale_opengl_debug_render(opengl_debug_context, camera_render_info);
+
+    ale_opengl_debug_line_clear(opengl_debug_context);
+    ale_opengl_debug_point_clear(opengl_debug_context);
+  }
+}
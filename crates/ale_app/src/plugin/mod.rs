@@ -0,0 +1,45 @@
+use ale_camera::CameraRenderInfo;
+use ale_input::Input;
+use ale_resources::resources::ResourceReloaded;
+use ale_world::world::World;
+
+use crate::engine::Engine;
+use crate::AppError;
+
+pub mod console;
+pub mod debug;
+pub mod pbr;
+pub mod profiler;
+
+// A plugin bundles an engine feature (physics, fxaa, console, etc) so games don't have to wire it manually.
+// Every hook has an empty default, implement only what the feature needs.
+pub trait Plugin {
+  // Wire the components this plugin provides into the world
+  fn register_components(&mut self, _world: &mut World) {}
+
+  // Called once after the engine is created, load resources and register console variables here.
+  // GL objects can be created here unless engine.windows.has_gl_context() is false.
+  fn init(&mut self, _engine: &mut Engine, _world: &mut World) -> Result<(), AppError> {
+    Ok(())
+  }
+
+  fn input(&mut self, _engine: &mut Engine, _inputs: &Vec<Input>) {}
+
+  // A resource has been reloaded in place from disk, recreate anything derived from it (gpu objects, etc)
+  fn resource_reloaded(&mut self, _engine: &mut Engine, _resource_reloaded: &ResourceReloaded) {}
+
+  // Systems, runs after the world has been ticked
+  fn fixed_tick(&mut self, _engine: &mut Engine, _world: &mut World, _delta_time: f32) {}
+
+  fn tick(&mut self, _engine: &mut Engine, _world: &mut World, _delta_time: f32) {}
+
+  // Render passes, in order:
+  // pre_render -> world render tasks -> render -> post_render -> swap
+  // None of them run on frames without a camera or without a GL context,
+  // pre_render is always followed by post_render.
+  fn pre_render(&mut self, _engine: &mut Engine) {}
+
+  fn render(&mut self, _engine: &mut Engine, _camera_render_info: &CameraRenderInfo) {}
+
+  fn post_render(&mut self, _engine: &mut Engine, _camera_render_info: &CameraRenderInfo) {}
+}
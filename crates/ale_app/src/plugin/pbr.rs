@@ -0,0 +1,67 @@
+use anyhow::anyhow;
+
+use ale_math::rect::Rect;
+use ale_math::{Vector2, Vector3, Zero};
+use ale_opengl::pbr::ale_opengl_pbr_context_new;
+use ale_opengl::renderer::static_mesh::PbrLight;
+use ale_world::world::World;
+
+use crate::engine::Engine;
+use crate::plugin::Plugin;
+use crate::AppError;
+
+// Installs the pbr context RenderTask::StaticMesh with Material::Pbr is drawn with,
+// lit by an equirectangular hdr environment map, e.g. "hdr/GravelPlaza_Env.hdr", and up to
+// PBR_MAX_LIGHTS point lights. Lights can be changed later with StaticMeshRenderer::set_pbr_lights.
+pub struct PbrPlugin {
+  environment_map_path: String,
+  pbr_lights: Vec<PbrLight>,
+}
+
+impl PbrPlugin {
+  pub fn new(environment_map_path: &str) -> PbrPlugin {
+    PbrPlugin {
+      environment_map_path: environment_map_path.to_owned(),
+      pbr_lights: vec![],
+    }
+  }
+
+  pub fn with_light(mut self, position: Vector3<f32>, color: Vector3<f32>) -> PbrPlugin {
+    self.pbr_lights.push(PbrLight { position, color });
+    self
+  }
+}
+
+impl Plugin for PbrPlugin {
+  fn init(&mut self, engine: &mut Engine, _world: &mut World) -> Result<(), AppError> {
+    // the static mesh renderer only exists with a GL context
+    if engine.static_mesh_renderer.is_none() {
+      return Ok(());
+    }
+    let environment_map_key = engine
+      .resources
+      .textures
+      .load(&self.environment_map_path)
+      .map_err(|err| anyhow!("fail to load {}: {:?}", self.environment_map_path, err))?
+      .remove(0);
+
+    // the cubemap passes change the viewport, it's put back to the window's
+    let framebuffer_size = engine
+      .windows
+      .primary()
+      .and_then(|window_key| engine.windows.framebuffer_size(window_key))
+      .unwrap_or(Vector2::zero());
+    let viewport = Rect {
+      position: Vector2::zero(),
+      size: Vector2::new(framebuffer_size.x as i32, framebuffer_size.y as i32),
+    };
+
+    let environment_map = engine.resources.textures.get(environment_map_key).unwrap();
+    let opengl_pbr_context = ale_opengl_pbr_context_new(environment_map, &viewport, vec![]);
+    if let Some(static_mesh_renderer) = &mut engine.static_mesh_renderer {
+      static_mesh_renderer.set_pbr_context(opengl_pbr_context);
+      static_mesh_renderer.set_pbr_lights(self.pbr_lights.clone());
+    }
+    Ok(())
+  }
+}
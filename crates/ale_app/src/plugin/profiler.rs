@@ -0,0 +1,115 @@
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ale_camera::CameraRenderInfo;
+use ale_console::{
+  ale_console_command_event_handle, ale_console_command_register, ale_console_print_output,
+  ale_console_variable_event_handle, ale_console_variable_register, Console,
+};
+use ale_data::alevec::Key;
+use ale_math::Vector2;
+use ale_resources::font::Font;
+use ale_variable::{to_variable, ToVariable};
+use ale_world::world::World;
+
+use crate::engine::Engine;
+use crate::plugin::Plugin;
+use crate::AppError;
+
+const OVERLAY_FONT: &str = "font/Inconsolata-Regular.ttf";
+const OVERLAY_FONT_SIZE: u32 = 16;
+
+const PROFILER_DUMP: &str = "profiler_dump";
+
+// Console:
+//   profiler_overlay true/false : show frame timings on screen
+//   profiler_dump [frames]      : write the last [frames] frames (whole history by default)
+//                                 to a chrome trace json in the working directory
+pub struct ProfilerPlugin {
+  font: Option<Key<Font>>,
+
+  profiler_overlay: bool,
+}
+
+impl ProfilerPlugin {
+  pub fn new() -> ProfilerPlugin {
+    ProfilerPlugin {
+      font: None,
+      profiler_overlay: false,
+    }
+  }
+
+  fn console_variable_refresh(&mut self, console: &mut Console) {
+    self.profiler_overlay = ale_console_variable_event_handle(console, to_variable!(self.profiler_overlay));
+  }
+}
+
+impl Plugin for ProfilerPlugin {
+  fn init(&mut self, engine: &mut Engine, _world: &mut World) -> Result<(), AppError> {
+    self.font = engine.resources.fonts.load(OVERLAY_FONT)?.into_iter().next();
+
+    ale_console_variable_register(&mut engine.console, to_variable!(self.profiler_overlay));
+    ale_console_command_register(&mut engine.console, PROFILER_DUMP);
+    Ok(())
+  }
+
+  fn tick(&mut self, engine: &mut Engine, _world: &mut World, _delta_time: f32) {
+    self.console_variable_refresh(&mut engine.console);
+
+    let arguments = match ale_console_command_event_handle(&mut engine.console, PROFILER_DUMP) {
+      None => return,
+      Some(arguments) => arguments,
+    };
+    let num_frames = match arguments.first().map(|frames| frames.parse::<usize>()) {
+      None => engine.profiler.frames().len(),
+      Some(Ok(num_frames)) => num_frames,
+      Some(Err(_)) => {
+        ale_console_print_output(&mut engine.console, &format!("usage: {} [frames]", PROFILER_DUMP));
+        return;
+      }
+    };
+
+    let now_ms = SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .expect("Time went backwards")
+      .as_millis();
+    let path = format!("profiler-{}.json", now_ms);
+    match fs::write(&path, engine.profiler.chrome_trace(num_frames)) {
+      Ok(_) => ale_console_print_output(&mut engine.console, &format!("profiler trace written to {}", path)),
+      Err(err) => ale_console_print_output(&mut engine.console, &format!("fail to write {}: {}", path, err)),
+    }
+  }
+
+  fn post_render(&mut self, engine: &mut Engine, camera_render_info: &CameraRenderInfo) {
+    if !self.profiler_overlay {
+      return;
+    }
+    let font_key = match self.font {
+      None => return,
+      Some(font_key) => font_key,
+    };
+    let screen_size = match engine.windows.iter().next() {
+      None => return,
+      Some(window) => window.get_screen_size(),
+    };
+
+    let mut lines = vec![];
+    if let Some(frame) = engine.profiler.last_frame() {
+      lines.push(format!("frame {:.2}ms", frame.duration as f32 / 1000.0));
+    }
+    for (name, average) in engine.profiler.averages() {
+      lines.push(format!("{} {:.2}ms", name, average as f32 / 1000.0));
+    }
+
+    let (font, text_renderer) = match (engine.resources.fonts.get_mut(font_key), &mut engine.text_renderer) {
+      (Some(font), Some(text_renderer)) => (font, text_renderer),
+      _ => return,
+    };
+    let bounds = Vector2::new(screen_size.x as i32, screen_size.y as i32);
+    let mut curr_pos = Vector2::new(screen_size.x as f32 * 0.7, screen_size.y as f32);
+    for line in lines {
+      curr_pos.y -= OVERLAY_FONT_SIZE as f32;
+      text_renderer.render(camera_render_info, font, OVERLAY_FONT_SIZE, curr_pos, &line, Some(bounds));
+    }
+  }
+}
@@ -0,0 +1,228 @@
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::time::Instant;
+
+use ale_world::visitor::VisitScope;
+
+// A single timed region inside a frame, times are in microseconds since the profiler was created
+#[derive(Clone, Debug)]
+pub struct ProfilerScope {
+  pub name: String,
+  pub start: u64,
+  pub duration: u64,
+  pub depth: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct ProfilerFrame {
+  pub start: u64,
+  pub duration: u64,
+  pub scopes: Vec<ProfilerScope>,
+}
+
+// Scoped timing markers with a rolling history of the last max_frames frames.
+// Scopes must be closed in the reverse order they're opened.
+pub struct Profiler {
+  epoch: Instant,
+  max_frames: usize,
+  frames: VecDeque<ProfilerFrame>,
+
+  current: Option<ProfilerFrame>,
+  // index to current.scopes
+  open_scopes: Vec<usize>,
+}
+
+impl Profiler {
+  pub fn new(max_frames: usize) -> Profiler {
+    Profiler {
+      epoch: Instant::now(),
+      max_frames,
+      frames: VecDeque::new(),
+      current: None,
+      open_scopes: vec![],
+    }
+  }
+
+  pub fn begin_frame(&mut self) {
+    self.current = Some(ProfilerFrame {
+      start: self.now(),
+      duration: 0,
+      scopes: vec![],
+    });
+    self.open_scopes.clear();
+  }
+
+  pub fn end_frame(&mut self) {
+    let now = self.now();
+    let mut frame = match self.current.take() {
+      None => return,
+      Some(frame) => frame,
+    };
+
+    // Close whatever is left open
+    while let Some(index) = self.open_scopes.pop() {
+      let scope = &mut frame.scopes[index];
+      scope.duration = now - scope.start;
+    }
+    frame.duration = now - frame.start;
+
+    if self.frames.len() >= self.max_frames {
+      self.frames.pop_front();
+    }
+    self.frames.push_back(frame);
+  }
+
+  pub fn begin(&mut self, name: &str) {
+    let start = self.now();
+    let depth = self.open_scopes.len();
+    let frame = match &mut self.current {
+      None => return,
+      Some(frame) => frame,
+    };
+
+    self.open_scopes.push(frame.scopes.len());
+    frame.scopes.push(ProfilerScope {
+      name: name.to_owned(),
+      start,
+      duration: 0,
+      depth,
+    });
+  }
+
+  pub fn end(&mut self) {
+    let now = self.now();
+    let frame = match &mut self.current {
+      None => return,
+      Some(frame) => frame,
+    };
+
+    if let Some(index) = self.open_scopes.pop() {
+      let scope = &mut frame.scopes[index];
+      scope.duration = now - scope.start;
+    }
+  }
+
+  pub fn frames(&self) -> &VecDeque<ProfilerFrame> {
+    &self.frames
+  }
+
+  pub fn last_frame(&self) -> Option<&ProfilerFrame> {
+    self.frames.back()
+  }
+
+  // Average duration (microseconds) of every top level scope over the history, in the order they first appear
+  pub fn averages(&self) -> Vec<(String, u64)> {
+    let mut averages: Vec<(String, u64, u64)> = vec![];
+    for frame in &self.frames {
+      for scope in frame.scopes.iter().filter(|s| s.depth == 0) {
+        match averages.iter_mut().find(|(name, _, _)| name == &scope.name) {
+          None => averages.push((scope.name.clone(), scope.duration, 1)),
+          Some((_, total, count)) => {
+            *total += scope.duration;
+            *count += 1;
+          }
+        }
+      }
+    }
+    averages
+      .into_iter()
+      .map(|(name, total, count)| (name, total / count))
+      .collect()
+  }
+
+  // Chrome trace_event format (chrome://tracing or https://ui.perfetto.dev) of the last num_frames frames
+  pub fn chrome_trace(&self, num_frames: usize) -> String {
+    let skip = self.frames.len().saturating_sub(num_frames);
+
+    let mut events = vec![];
+    for frame in self.frames.iter().skip(skip) {
+      events.push(intern_trace_event("frame", frame.start, frame.duration));
+      for scope in &frame.scopes {
+        events.push(intern_trace_event(&scope.name, scope.start, scope.duration));
+      }
+    }
+
+    format!("{{\"traceEvents\":[{}]}}", events.join(","))
+  }
+
+  fn now(&self) -> u64 {
+    self.epoch.elapsed().as_micros() as u64
+  }
+}
+
+// A scope per component type during World::visit_mut_scoped
+impl VisitScope for Profiler {
+  fn begin(&mut self, type_name: &str) {
+    Profiler::begin(self, type_name);
+  }
+
+  fn end(&mut self) {
+    Profiler::end(self);
+  }
+}
+
+fn intern_trace_event(name: &str, start: u64, duration: u64) -> String {
+  let mut escaped = String::new();
+  for c in name.chars() {
+    match c {
+      '"' => escaped.push_str("\\\""),
+      '\\' => escaped.push_str("\\\\"),
+      c if c.is_control() => {
+        let _ = write!(escaped, "\\u{:04x}", c as u32);
+      }
+      c => escaped.push(c),
+    }
+  }
+
+  format!(
+    "{{\"name\":\"{}\",\"cat\":\"ale\",\"ph\":\"X\",\"ts\":{},\"dur\":{},\"pid\":0,\"tid\":0}}",
+    escaped, start, duration
+  )
+}
+
+#[test]
+fn profiler_history_is_bounded() {
+  let mut profiler = Profiler::new(2);
+  for _ in 0..3 {
+    profiler.begin_frame();
+    profiler.begin("tick");
+    profiler.end();
+    profiler.end_frame();
+  }
+
+  assert_eq!(profiler.frames().len(), 2);
+  assert_eq!(profiler.last_frame().unwrap().scopes.len(), 1);
+}
+
+#[test]
+fn profiler_nested_scopes() {
+  let mut profiler = Profiler::new(10);
+  profiler.begin_frame();
+  profiler.begin("render");
+  profiler.begin("visit");
+  profiler.end();
+  profiler.end();
+  profiler.begin("swap");
+  profiler.end_frame();
+
+  let scopes = &profiler.last_frame().unwrap().scopes;
+  assert_eq!(scopes[0].depth, 0);
+  assert_eq!(scopes[1].depth, 1);
+  assert_eq!(scopes[2].name, "swap");
+  assert!(scopes[1].start >= scopes[0].start);
+  assert!(scopes[1].duration <= scopes[0].duration);
+}
+
+#[test]
+fn profiler_chrome_trace_escapes_names() {
+  let mut profiler = Profiler::new(10);
+  profiler.begin_frame();
+  profiler.begin("visit \"dyn Tickable\"");
+  profiler.end();
+  profiler.end_frame();
+
+  let trace = profiler.chrome_trace(1);
+  assert!(trace.starts_with("{\"traceEvents\":["));
+  assert!(trace.contains("\"name\":\"visit \\\"dyn Tickable\\\"\""));
+  assert!(trace.contains("\"ph\":\"X\""));
+}
@@ -0,0 +1,242 @@
+use ale_world::world::World;
+use log::error;
+
+use crate::engine::Engine;
+use crate::AppError;
+
+// A screen of the game (title, play, pause, game over), each state owns its own world.
+// Transitions are requested by sending a StateTransition through engine.state_queue
+pub trait GameState<E = Engine> {
+  fn register_components(&self, world: &mut World);
+
+  // Called when the state is put on the stack, spawn its entities here.
+  // On Err the state is dropped and the stack is left as it was.
+  fn on_enter(&mut self, engine: &mut E, world: &mut World) -> Result<(), AppError>;
+
+  // Called before the state is removed from the stack, its world is dropped afterwards
+  fn on_exit(&mut self, _engine: &mut E, _world: &mut World) {}
+
+  // Another state has been pushed on top of this one
+  fn on_pause(&mut self, _engine: &mut E, _world: &mut World) {}
+
+  // The state on top of this one has been popped
+  fn on_resume(&mut self, _engine: &mut E, _world: &mut World) {}
+
+  // Overlays (e.g. pause menu) let the states beneath them keep rendering, they're still not ticked
+  fn is_overlay(&self) -> bool {
+    false
+  }
+}
+
+pub enum StateTransition {
+  Push(Box<dyn GameState>),
+  Pop,
+  // Replaces the top state
+  Switch(Box<dyn GameState>),
+}
+
+struct StateEntry<E> {
+  state: Box<dyn GameState<E>>,
+  world: World,
+}
+
+// The new state always enters before the current top is paused or exited,
+// so a state failing on_enter never leaves the stack half transitioned
+pub struct StateStack<E = Engine> {
+  states: Vec<StateEntry<E>>,
+}
+
+impl<E> StateStack<E> {
+  pub fn new() -> StateStack<E> {
+    StateStack { states: vec![] }
+  }
+
+  pub fn len(&self) -> usize {
+    self.states.len()
+  }
+
+  pub fn push(&mut self, engine: &mut E, state: Box<dyn GameState<E>>) -> Result<(), AppError> {
+    let entry = StateStack::enter(engine, state)?;
+    if let Some(top) = self.states.last_mut() {
+      top.state.on_pause(engine, &mut top.world);
+    }
+
+    self.states.push(entry);
+    Ok(())
+  }
+
+  pub fn pop(&mut self, engine: &mut E) {
+    let mut entry = match self.states.pop() {
+      None => return,
+      Some(entry) => entry,
+    };
+    entry.state.on_exit(engine, &mut entry.world);
+
+    if let Some(top) = self.states.last_mut() {
+      top.state.on_resume(engine, &mut top.world);
+    }
+  }
+
+  // Replaces the top state, the state beneath it is not resumed
+  pub fn switch(&mut self, engine: &mut E, state: Box<dyn GameState<E>>) -> Result<(), AppError> {
+    let entry = StateStack::enter(engine, state)?;
+    if let Some(mut top) = self.states.pop() {
+      top.state.on_exit(engine, &mut top.world);
+    }
+
+    self.states.push(entry);
+    Ok(())
+  }
+
+  fn enter(engine: &mut E, mut state: Box<dyn GameState<E>>) -> Result<StateEntry<E>, AppError> {
+    let mut world = World::new();
+    state.register_components(&mut world);
+    state.on_enter(engine, &mut world)?;
+    Ok(StateEntry { state, world })
+  }
+
+  // Only the top state receives input and ticks
+  pub fn active_world_mut(&mut self) -> Option<&mut World> {
+    self.states.last_mut().map(|entry| &mut entry.world)
+  }
+
+  // Top state and every overlay beneath it, down to the first non overlay state. Bottom first.
+  pub fn visible_worlds_mut(&mut self) -> impl Iterator<Item = &mut World> {
+    let first_visible = self
+      .states
+      .iter()
+      .rposition(|entry| !entry.state.is_overlay())
+      .unwrap_or(0);
+    self.states[first_visible..].iter_mut().map(|entry| &mut entry.world)
+  }
+
+  pub fn worlds_mut(&mut self) -> impl Iterator<Item = &mut World> {
+    self.states.iter_mut().map(|entry| &mut entry.world)
+  }
+}
+
+impl StateStack<Engine> {
+  // Applies every transition queued in engine.state_queue, engine.event_queue is left alone
+  pub fn handle_events(&mut self, engine: &mut Engine) {
+    let transitions: Vec<StateTransition> = engine.state_queue.receiver.try_iter().collect();
+    for transition in transitions {
+      let result = match transition {
+        StateTransition::Push(state) => self.push(engine, state),
+        StateTransition::Pop => {
+          self.pop(engine);
+          Ok(())
+        }
+        StateTransition::Switch(state) => self.switch(engine, state),
+      };
+      if let Err(err) = result {
+        error!("failed to enter state: {}", err);
+      }
+    }
+  }
+}
+
+#[test]
+fn state_stack_transitions() {
+  // Records every callback into the log passed as the engine
+  struct LogState {
+    name: &'static str,
+    is_failing: bool,
+  }
+
+  impl GameState<Vec<String>> for LogState {
+    fn register_components(&self, _world: &mut World) {}
+
+    fn on_enter(&mut self, log: &mut Vec<String>, _world: &mut World) -> Result<(), AppError> {
+      log.push(format!("{} enter", self.name));
+      if self.is_failing {
+        return Err(anyhow::anyhow!("{} failed", self.name));
+      }
+      Ok(())
+    }
+
+    fn on_exit(&mut self, log: &mut Vec<String>, _world: &mut World) {
+      log.push(format!("{} exit", self.name));
+    }
+
+    fn on_pause(&mut self, log: &mut Vec<String>, _world: &mut World) {
+      log.push(format!("{} pause", self.name));
+    }
+
+    fn on_resume(&mut self, log: &mut Vec<String>, _world: &mut World) {
+      log.push(format!("{} resume", self.name));
+    }
+  }
+
+  let state = |name: &'static str| Box::new(LogState { name, is_failing: false });
+  let failing = |name: &'static str| Box::new(LogState { name, is_failing: true });
+  let mut log: Vec<String> = vec![];
+  let mut stack = StateStack::new();
+
+  stack.push(&mut log, state("title")).unwrap();
+  stack.push(&mut log, state("options")).unwrap();
+  stack.pop(&mut log);
+  stack.switch(&mut log, state("play")).unwrap();
+  stack.pop(&mut log);
+  stack.pop(&mut log);
+  assert_eq!(stack.len(), 0);
+  assert_eq!(
+    log,
+    vec![
+      "title enter",
+      "options enter",
+      "title pause",
+      "options exit",
+      "title resume",
+      "play enter",
+      "title exit",
+      "play exit",
+    ]
+  );
+
+  // A failing state never reaches the stack and the top carries on as if nothing happened
+  log.clear();
+  stack.push(&mut log, state("play")).unwrap();
+  assert!(stack.push(&mut log, failing("pause")).is_err());
+  assert!(stack.switch(&mut log, failing("game over")).is_err());
+  assert_eq!(stack.len(), 1);
+  stack.pop(&mut log);
+  assert_eq!(log, vec!["play enter", "pause enter", "game over enter", "play exit"]);
+}
+
+#[test]
+fn state_stack_handles_engine_state_queue() {
+  use ale_math::rect::Rect;
+  use ale_math::Vector2;
+  use ale_window::display::DisplaySetting;
+  use ale_window::headless::HeadlessBackend;
+
+  struct EmptyState;
+
+  impl GameState for EmptyState {
+    fn register_components(&self, _world: &mut World) {}
+
+    fn on_enter(&mut self, _engine: &mut Engine, _world: &mut World) -> Result<(), AppError> {
+      Ok(())
+    }
+  }
+
+  let display_setting = DisplaySetting::new(Rect {
+    position: Vector2::new(0, 0),
+    size: Vector2::new(800, 600),
+  });
+  let backend = HeadlessBackend::new(Vector2::new(800, 600));
+  let mut engine = Engine::new(display_setting, Box::new(backend)).unwrap();
+  let mut stack = StateStack::new();
+
+  let sender = engine.state_queue.sender.clone();
+  sender.send(StateTransition::Push(Box::new(EmptyState))).unwrap();
+  sender.send(StateTransition::Push(Box::new(EmptyState))).unwrap();
+  stack.handle_events(&mut engine);
+  assert_eq!(stack.len(), 2);
+
+  sender.send(StateTransition::Pop).unwrap();
+  sender.send(StateTransition::Switch(Box::new(EmptyState))).unwrap();
+  stack.handle_events(&mut engine);
+  assert_eq!(stack.len(), 1);
+  assert!(engine.state_queue.receiver.is_empty());
+}
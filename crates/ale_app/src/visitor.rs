@@ -8,6 +8,8 @@ use ale_world::components::{Inputable, Tickable};
 use ale_world::visitor::VisitorMut;
 use std::collections::HashMap;
 use ale_input::Input;
+use ale_input::state::InputState;
+use ale_math::Vector2;
 
 pub struct CameraVisitor {
   pub camera_render_info: HashMap<Id<Entity>, CameraRenderInfo>,
@@ -20,6 +22,19 @@ impl VisitorMut<dyn Camera> for CameraVisitor {
   }
 }
 
+pub struct ResizeVisitor {
+  // in pixels
+  pub size: Vector2<u32>,
+  pub content_scale: f32,
+}
+
+impl VisitorMut<dyn Camera> for ResizeVisitor {
+  fn visit(&mut self, camera: &mut (dyn Camera + 'static)) {
+    camera.set_viewport(Vector2::new(0, 0), self.size);
+    camera.set_content_scale(self.content_scale);
+  }
+}
+
 pub struct RenderableVisitor {
   pub render_tasks: Vec<RenderTask>,
 }
@@ -50,12 +65,13 @@ impl VisitorMut<dyn Tickable> for FixedTickVisitor {
   }
 }
 
-pub struct InputVisitor {
+pub struct InputVisitor<'a> {
   pub input : Vec<Input>,
+  pub input_state: &'a InputState,
 }
 
-impl VisitorMut<dyn Inputable> for InputVisitor {
+impl<'a> VisitorMut<dyn Inputable> for InputVisitor<'a> {
   fn visit(&mut self, component: &mut (dyn Inputable + 'static)) {
-    component.input(&self.input)
+    component.input(&self.input, self.input_state)
   }
 }
\ No newline at end of file
@@ -1,7 +1,14 @@
 use ale_data::entity::{Component, Entity};
 use ale_data::indexmap::Id;
+use ale_math::Vector2;
 use crate::CameraRenderInfo;
 
 pub trait Camera: Component {
   fn get_camera_info(&mut self) -> (Id<Entity>, CameraRenderInfo);
+
+  // Called when the window's framebuffer is resized, forward it to Camera::set_viewport
+  fn set_viewport(&mut self, _position: Vector2<i32>, _size: Vector2<u32>) {}
+
+  // Called when the window's content scale changes, forward it to Camera::set_content_scale
+  fn set_content_scale(&mut self, _content_scale: f32) {}
 }
\ No newline at end of file
@@ -0,0 +1,17 @@
+use ale_input::state::InputState;
+use ale_input::Input;
+
+use crate::Camera;
+
+// Drives a Camera from input and time. The world components wire input and tick to these,
+// see ale_world::camera.
+pub trait CameraController {
+  fn camera(&self) -> &Camera;
+
+  fn camera_mut(&mut self) -> &mut Camera;
+
+  // inputs are this frame's events, input_state has already been updated with them
+  fn input(&mut self, inputs: &Vec<Input>, input_state: &InputState);
+
+  fn tick(&mut self, delta_time: f32);
+}
@@ -1,4 +1,6 @@
+use crate::controller::CameraController;
 use crate::{Camera, CameraRenderInfo};
+use ale_input::state::InputState;
 use ale_input::{Action, Input, Key};
 use ale_math::prelude::*;
 use ale_math::{Deg, Quaternion, Vector2, Vector3, Zero};
@@ -17,6 +19,8 @@ pub struct FlyCamera {
   camera_rotate_speed: f32,
 
   disable_input: bool,
+  // mouse-look stops while the cursor is outside or the window isn't focused
+  is_mouse_look_enabled: bool,
 }
 
 impl FlyCamera {
@@ -29,6 +33,7 @@ impl FlyCamera {
       camera_speed: 10.0,
       camera_rotate_speed: 100.0,
       disable_input: false,
+      is_mouse_look_enabled: true,
     }
   }
 
@@ -70,6 +75,13 @@ impl FlyCamera {
   }
 
   fn camera_input(&mut self, input: &Input) {
+    match input {
+      Input::FramebufferResize { width, height } => {
+        self.camera.set_viewport(Vector2::new(0, 0), Vector2::new(*width, *height))
+      }
+      Input::ContentScale { x, .. } => self.camera.set_content_scale(*x),
+      _ => {}
+    }
     if self.disable_input {
       return;
     }
@@ -86,6 +98,19 @@ impl FlyCamera {
       Input::Key(Key::S, _, Action::Release, _) => self.move_input.z += 1.0f32,
       Input::Key(Key::Z, _, Action::Press, _) => self.disable_input = !self.disable_input,
 
+      // scroll up to fly faster
+      Input::Scroll { y, .. } => {
+        self.camera_speed = (self.camera_speed * 1.1f32.powf(*y)).max(SMALL_NUMBERF32);
+      }
+      Input::CursorEnter | Input::FocusGained => self.is_mouse_look_enabled = true,
+      Input::CursorLeave => self.is_mouse_look_enabled = false,
+      // key releases won't arrive anymore
+      Input::FocusLost => {
+        self.is_mouse_look_enabled = false;
+        self.move_input = Vector3::zero();
+      }
+
+      Input::MouseMotion { .. } if !self.is_mouse_look_enabled => {}
       Input::MouseMotion{rel_x, rel_y, abs_x, abs_y} => {
         self.rotate_input.x += *rel_x;
         self.rotate_input.y += *rel_y;
@@ -96,3 +121,21 @@ impl FlyCamera {
     }
   }
 }
+
+impl CameraController for FlyCamera {
+  fn camera(&self) -> &Camera {
+    &self.camera
+  }
+
+  fn camera_mut(&mut self) -> &mut Camera {
+    &mut self.camera
+  }
+
+  fn input(&mut self, inputs: &Vec<Input>, _input_state: &InputState) {
+    FlyCamera::input(self, inputs);
+  }
+
+  fn tick(&mut self, delta_time: f32) {
+    FlyCamera::tick(self, delta_time);
+  }
+}
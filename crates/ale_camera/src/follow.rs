@@ -0,0 +1,172 @@
+use ale_data::entity::Entity;
+use ale_data::indexmap::Id;
+use ale_input::state::InputState;
+use ale_input::Input;
+use ale_math::geometry::Ray;
+use ale_math::prelude::*;
+use ale_math::transform::AleTransform;
+use ale_math::{Quaternion, Vector2, Vector3};
+
+use crate::controller::CameraController;
+use crate::{component, Camera, CameraRenderInfo};
+
+// Casts a ray up to the given distance and returns the distance of the first hit, e.g. against physics colliders
+pub type CollisionProbe = Box<dyn Fn(&Ray, f32) -> Option<f32>>;
+
+// Third person camera trailing behind a target, feed it the target every frame with set_target
+pub struct FollowCamera {
+  id: Id<Entity>,
+  camera: Camera,
+
+  target_position: Vector3<f32>,
+  target_rotation: Quaternion<f32>,
+  // snaps on the first tick instead of flying in from wherever the camera started
+  is_snapped: bool,
+
+  // in target space, behind is +z
+  pub offset: Vector3<f32>,
+  // in target space, the point the camera looks at
+  pub look_offset: Vector3<f32>,
+  // higher catches up faster, 0 doesn't move at all
+  pub damping: f32,
+  // distance kept from whatever the probe hits
+  pub collision_offset: f32,
+  collision_probe: Option<CollisionProbe>,
+}
+
+impl FollowCamera {
+  pub fn new(camera: Camera) -> FollowCamera {
+    FollowCamera {
+      id: Id::new(),
+      camera,
+      target_position: Vector3::zero(),
+      target_rotation: Quaternion::one(),
+      is_snapped: false,
+      offset: Vector3::new(0.0, 2.0, 5.0),
+      look_offset: Vector3::new(0.0, 1.0, 0.0),
+      damping: 8.0,
+      collision_offset: 0.2,
+      collision_probe: None,
+    }
+  }
+
+  pub fn id(&self) -> Id<Entity> {
+    self.id
+  }
+
+  pub fn set_target(&mut self, position: Vector3<f32>, rotation: Quaternion<f32>) {
+    self.target_position = position;
+    self.target_rotation = rotation;
+  }
+
+  pub fn set_collision_probe(&mut self, collision_probe: Option<CollisionProbe>) {
+    self.collision_probe = collision_probe;
+  }
+
+  // Jump to the resting position on the next tick, e.g. after a teleport
+  pub fn snap(&mut self) {
+    self.is_snapped = false;
+  }
+
+  fn look_point(&self) -> Vector3<f32> {
+    self.target_position + self.target_rotation.rotate_vector(self.look_offset)
+  }
+
+  fn desired_position(&self) -> Vector3<f32> {
+    self.target_position + self.target_rotation.rotate_vector(self.offset)
+  }
+
+  // Pulls position towards the look point when something is in between
+  fn resolve_collision(&self, position: Vector3<f32>) -> Vector3<f32> {
+    let probe = match &self.collision_probe {
+      None => return position,
+      Some(probe) => probe,
+    };
+    let look_point = self.look_point();
+    let distance = (position - look_point).magnitude();
+    if distance <= f32::EPSILON {
+      return position;
+    }
+
+    let ray = Ray::new(look_point, position - look_point);
+    match probe(&ray, distance + self.collision_offset) {
+      Some(hit) if hit < distance + self.collision_offset => {
+        let distance = (hit - self.collision_offset).max(0.0).min(distance);
+        look_point + ray.direction * distance
+      }
+      _ => position,
+    }
+  }
+}
+
+impl CameraController for FollowCamera {
+  fn camera(&self) -> &Camera {
+    &self.camera
+  }
+
+  fn camera_mut(&mut self) -> &mut Camera {
+    &mut self.camera
+  }
+
+  fn input(&mut self, _inputs: &Vec<Input>, _input_state: &InputState) {}
+
+  fn tick(&mut self, delta_time: f32) {
+    let desired = self.desired_position();
+    let position = if self.is_snapped {
+      // frame rate independent, covers the same fraction of the gap every second
+      let t = 1.0 - (-self.damping * delta_time).exp();
+      self.camera.position().lerp(desired, t)
+    } else {
+      self.is_snapped = true;
+      desired
+    };
+    let position = self.resolve_collision(position);
+    self.camera.set_position(position);
+
+    // look_at keeps the current rotation when the look point is on the camera or straight above/below
+    let mut transform = AleTransform::from_position_rotation(position, self.camera.rotation());
+    transform.look_at(self.look_point(), Vector3::unit_y());
+    self.camera.set_rotation(transform.lcl_rotation);
+  }
+}
+
+impl component::Camera for FollowCamera {
+  fn get_camera_info(&mut self) -> (Id<Entity>, CameraRenderInfo) {
+    (self.id, self.camera.camera_render_info())
+  }
+
+  fn set_viewport(&mut self, position: Vector2<i32>, size: Vector2<u32>) {
+    self.camera.set_viewport(position, size);
+  }
+
+  fn set_content_scale(&mut self, content_scale: f32) {
+    self.camera.set_content_scale(content_scale);
+  }
+}
+
+#[test]
+fn follow_camera_damping_and_collision() {
+  use ale_math::rect::RectI;
+  use ale_math::Deg;
+
+  let rect = RectI::new(Vector2::new(0, 0), Vector2::new(800, 600));
+  let mut follow = FollowCamera::new(Camera::new(Vector3::zero(), rect, 60.0));
+  follow.offset = Vector3::new(0.0, 0.0, 5.0);
+  follow.look_offset = Vector3::zero();
+  follow.set_target(Vector3::zero(), Quaternion::from_angle_y(Deg(90.0)));
+  follow.tick(0.016);
+  assert!((follow.camera().position() - Vector3::new(5.0, 0.0, 0.0)).magnitude() < 0.0001);
+  assert!((follow.camera().forward_dir() - Vector3::new(-1.0, 0.0, 0.0)).magnitude() < 0.0001);
+
+  // Moves part of the way, never overshoots
+  follow.set_target(Vector3::new(0.0, 0.0, 10.0), Quaternion::from_angle_y(Deg(90.0)));
+  follow.tick(0.016);
+  let z = follow.camera().position().z;
+  assert!(z > 0.0 && z < 10.0);
+
+  // A wall 2 units away from the target
+  follow.set_collision_probe(Some(Box::new(|_ray: &Ray, _max: f32| Some(2.0))));
+  follow.snap();
+  follow.tick(0.016);
+  assert!((follow.camera().position() - Vector3::new(1.8, 0.0, 10.0)).magnitude() < 0.0001);
+}
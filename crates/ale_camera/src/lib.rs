@@ -1,18 +1,32 @@
 use ale_math::prelude::*;
-use ale_math::rect::Rect;
+use ale_math::geometry::Ray;
+use ale_math::rect::{RectF, RectI};
 use ale_math::transform::AleTransform;
-use ale_math::{ale_quaternion_look_at, ortho, perspective, Deg, Matrix4, Point3, Quaternion, Vector2, Vector3};
+use ale_math::{ale_quaternion_look_at, ortho, Matrix4, Point3, Quaternion, Vector2, Vector3, Vector4};
 use std::sync::Arc;
 
+use crate::projection::Projection;
+
 pub mod flycamera;
 pub mod component;
+pub mod projection;
+pub mod controller;
+pub mod orbit;
+pub mod follow;
+pub mod pan_zoom;
+pub mod shake;
 
 #[derive(Debug)]
 pub struct Camera {
   transform: AleTransform,
-  pub fov: f32,
+  projection: Projection,
+  // near at depth 1 and far at depth 0, the renderer flips its depth test to match
+  is_reverse_z: bool,
   aspect_ratio: f32,
-  display_rect: Rect,
+  // in pixels
+  display_rect: RectI,
+  // pixels per logical unit, the orthographic matrix works in logical units
+  content_scale: f32,
 
   projection_mat: Option<Matrix4<f32>>,
   orthographic_mat: Option<Matrix4<f32>>,
@@ -20,14 +34,21 @@ pub struct Camera {
 }
 
 impl Camera {
-  pub fn new(position: Vector3<f32>, display_rect: Rect, fov: f32) -> Camera {
+  // Perspective from 0.1 to 100, see with_projection for anything else
+  pub fn new(position: Vector3<f32>, display_rect: RectI, fov: f32) -> Camera {
+    Camera::with_projection(position, display_rect, Projection::perspective(fov, 0.1, 100.0))
+  }
+
+  pub fn with_projection(position: Vector3<f32>, display_rect: RectI, projection: Projection) -> Camera {
     let aspect_ratio = display_rect.size.x as f32 / display_rect.size.y as f32;
 
     Camera {
       transform: AleTransform::from_position_rotation(position, Quaternion::one()),
-      fov,
+      projection,
+      is_reverse_z: false,
       aspect_ratio,
       display_rect,
+      content_scale: 1.0,
       projection_mat: None,
       orthographic_mat: None,
       view_mat: None,
@@ -38,6 +59,12 @@ impl Camera {
     self.transform.position
   }
 
+  // Absolute move, e.g. to follow a point sampled from an ale_math::curve
+  pub fn set_position(&mut self, position: Vector3<f32>) {
+    self.transform.set_position(position);
+    self.view_mat = None;
+  }
+
   pub fn translate(&mut self, translation: Vector3<f32>) {
     if translation.is_zero() {
       return;
@@ -51,6 +78,10 @@ impl Camera {
     self.view_mat = None;
   }
 
+  pub fn rotation(&self) -> Quaternion<f32> {
+    self.transform.lcl_rotation
+  }
+
   pub fn set_rotation(&mut self, rotation: Quaternion<f32>) {
     if rotation.is_zero() {
       return;
@@ -87,46 +118,148 @@ impl Camera {
   }
 
   pub fn set_viewport(&mut self, position: Vector2<i32>, size: Vector2<u32>) {
-    self.display_rect = Rect { position, size };
+    self.display_rect = RectI::new(position, Vector2::new(size.x as i32, size.y as i32));
     self.aspect_ratio = self.display_rect.size.x as f32 / self.display_rect.size.y as f32;
     self.recalculate_matrices();
   }
 
+  pub fn projection(&self) -> &Projection {
+    &self.projection
+  }
+
+  pub fn set_projection(&mut self, projection: Projection) {
+    self.projection = projection;
+    self.projection_mat = None;
+  }
+
+  pub fn is_reverse_z(&self) -> bool {
+    self.is_reverse_z
+  }
+
+  pub fn set_reverse_z(&mut self, is_reverse_z: bool) {
+    self.is_reverse_z = is_reverse_z;
+    self.projection_mat = None;
+  }
+
+  pub fn content_scale(&self) -> f32 {
+    self.content_scale
+  }
+
+  pub fn set_content_scale(&mut self, content_scale: f32) {
+    if content_scale <= 0.0 {
+      return;
+    }
+    self.content_scale = content_scale;
+    self.orthographic_mat = None;
+    self.projection_mat = None;
+  }
+
   fn recalculate_matrices(&mut self) {
     self.orthographic_mat = None;
     self.projection_mat = None;
     self.view_mat = None;
   }
 
-  fn view_mat(&mut self) -> Matrix4<f32> {
-    match self.view_mat {
-      None => {
-        self.view_mat = Some(
-          Matrix4::from(self.transform.lcl_rotation.invert()) * Matrix4::from_translation(-self.transform.position),
-        );
-      }
-      Some(_) => (),
+  // The viewport in logical units, the same units as the cursor in Input::MouseMotion
+  pub fn viewport(&self) -> RectF {
+    RectF::new(
+      Vector2::new(
+        self.display_rect.position.x as f32 / self.content_scale,
+        self.display_rect.position.y as f32 / self.content_scale,
+      ),
+      Vector2::new(
+        self.display_rect.size.x as f32 / self.content_scale,
+        self.display_rect.size.y as f32 / self.content_scale,
+      ),
+    )
+  }
+
+  // cursor and viewport in window coordinates, y going down. The ray starts on the near plane.
+  //
+  //   let ray = camera.screen_to_world_ray(Vector2::new(abs_x, abs_y), camera.viewport());
+  //   let hit = ray.intersect_aabb(&bounds);
+  pub fn screen_to_world_ray(&self, cursor: Vector2<f32>, viewport: RectF) -> Ray {
+    // reverse-Z clip depth goes from 1 at near to 0 at far
+    let (near_z, middle_z) = if self.is_reverse_z { (1.0, 0.5) } else { (-1.0, 0.0) };
+    let ndc = intern_screen_to_ndc(cursor, viewport);
+    let inverse = self.intern_inverse_view_projection();
+
+    // middle instead of far, an infinite far plane unprojects to a point at infinity
+    let near = intern_unproject_ndc(&inverse, ndc.extend(near_z));
+    let middle = intern_unproject_ndc(&inverse, ndc.extend(middle_z));
+    Ray::new(near, middle - near)
+  }
+
+  // (x, y) in window coordinates and z the depth buffer value, 0..1.
+  // None when the point is behind the camera.
+  pub fn world_to_screen(&self, point: Vector3<f32>, viewport: RectF) -> Option<Vector3<f32>> {
+    let clip = self.intern_projection_mat() * self.intern_view_mat() * point.extend(1.0);
+    if clip.w <= 0.0 {
+      return None;
     }
+    let ndc = clip.truncate() / clip.w;
+    Some(Vector3::new(
+      viewport.position.x + (ndc.x + 1.0) / 2.0 * viewport.size.x,
+      viewport.position.y + (1.0 - ndc.y) / 2.0 * viewport.size.y,
+      self.intern_ndc_to_depth(ndc.z),
+    ))
+  }
 
-    self.view_mat.unwrap()
+  // World position of a depth buffer value, 0..1, under the cursor
+  pub fn unproject(&self, cursor: Vector2<f32>, depth: f32, viewport: RectF) -> Vector3<f32> {
+    let ndc = intern_screen_to_ndc(cursor, viewport);
+    let ndc_z = if self.is_reverse_z { depth } else { depth * 2.0 - 1.0 };
+    intern_unproject_ndc(&self.intern_inverse_view_projection(), ndc.extend(ndc_z))
   }
 
-  fn projection_mat(&mut self) -> Matrix4<f32> {
-    match self.projection_mat {
-      None => self.projection_mat = Some(perspective(Deg(self.fov), self.aspect_ratio, 0.1f32, 100.0f32)),
-      Some(_) => (),
+  // Reverse-Z clips to 0..1 already, the regular range is -1..1
+  fn intern_ndc_to_depth(&self, ndc_z: f32) -> f32 {
+    if self.is_reverse_z {
+      ndc_z
+    } else {
+      (ndc_z + 1.0) / 2.0
     }
-    self.projection_mat.unwrap()
+  }
+
+  fn intern_inverse_view_projection(&self) -> Matrix4<f32> {
+    (self.intern_projection_mat() * self.intern_view_mat())
+      .invert()
+      .unwrap_or(Matrix4::identity())
+  }
+
+  fn intern_view_mat(&self) -> Matrix4<f32> {
+    self.view_mat.unwrap_or_else(|| {
+      Matrix4::from(self.transform.lcl_rotation.invert()) * Matrix4::from_translation(-self.transform.position)
+    })
+  }
+
+  fn intern_projection_mat(&self) -> Matrix4<f32> {
+    self.projection_mat.unwrap_or_else(|| {
+      let viewport = self.viewport();
+      self.projection.matrix(self.aspect_ratio, viewport.size, self.is_reverse_z)
+    })
+  }
+
+  fn view_mat(&mut self) -> Matrix4<f32> {
+    let view_mat = self.intern_view_mat();
+    self.view_mat = Some(view_mat);
+    view_mat
+  }
+
+  fn projection_mat(&mut self) -> Matrix4<f32> {
+    let projection_mat = self.intern_projection_mat();
+    self.projection_mat = Some(projection_mat);
+    projection_mat
   }
 
   fn orthographic_mat(&mut self) -> Matrix4<f32> {
     match self.orthographic_mat {
       None => {
         self.orthographic_mat = Some(ortho(
-          self.display_rect.position.x as f32,
-          self.display_rect.size.x as f32,
-          self.display_rect.size.y as f32,
-          self.display_rect.position.y as f32,
+          self.display_rect.position.x as f32 / self.content_scale,
+          self.display_rect.size.x as f32 / self.content_scale,
+          self.display_rect.size.y as f32 / self.content_scale,
+          self.display_rect.position.y as f32 / self.content_scale,
           -1.0,
           1.0f32,
         ))
@@ -142,14 +275,73 @@ impl Camera {
       projection: self.projection_mat(),
       orthographic: self.orthographic_mat(),
       position: self.transform.position,
+      is_reverse_z: self.is_reverse_z,
     }
   }
 }
 
+fn intern_screen_to_ndc(cursor: Vector2<f32>, viewport: RectF) -> Vector2<f32> {
+  let normalized = viewport.normalize_point(cursor);
+  Vector2::new(normalized.x * 2.0 - 1.0, 1.0 - normalized.y * 2.0)
+}
+
+fn intern_unproject_ndc(inverse_view_projection: &Matrix4<f32>, ndc: Vector3<f32>) -> Vector3<f32> {
+  let world: Vector4<f32> = inverse_view_projection * ndc.extend(1.0);
+  world.truncate() / world.w
+}
+
 #[derive(Debug, Clone)]
 pub struct CameraRenderInfo {
   pub view: Matrix4<f32>,
   pub projection: Matrix4<f32>,
   pub orthographic: Matrix4<f32>,
   pub position: Vector3<f32>,
+  pub is_reverse_z: bool,
+}
+
+#[test]
+fn camera_screen_world_roundtrip() {
+  let mut camera = Camera::new(Vector3::new(0.0, 0.0, 10.0), RectI::new(Vector2::new(0, 0), Vector2::new(800, 600)), 90.0);
+  let viewport = camera.viewport();
+  let info = camera.camera_render_info();
+
+  // Center of the screen looks straight ahead
+  let ray = camera.screen_to_world_ray(Vector2::new(400.0, 300.0), viewport);
+  assert!((ray.direction - Vector3::new(0.0, 0.0, -1.0)).magnitude() < 0.0001);
+  assert!((ray.origin - Vector3::new(0.0, 0.0, 9.9)).magnitude() < 0.0001);
+
+  // Agrees with the render matrices
+  let point = Vector3::new(2.0, 1.0, 0.0);
+  let clip = info.projection * info.view * point.extend(1.0);
+  let screen = camera.world_to_screen(point, viewport).unwrap();
+  assert!((screen.x - (clip.x / clip.w + 1.0) * 400.0).abs() < 0.001);
+  assert!((screen.y - (1.0 - clip.y / clip.w) * 300.0).abs() < 0.001);
+  assert!((camera.unproject(screen.truncate(), screen.z, viewport) - point).magnitude() < 0.001);
+
+  // The ray through a projected point passes through it
+  let ray = camera.screen_to_world_ray(screen.truncate(), viewport);
+  assert!((ray.closest_point(point) - point).magnitude() < 0.001);
+
+  assert!(camera.world_to_screen(Vector3::new(0.0, 0.0, 20.0), viewport).is_none());
+}
+
+#[test]
+fn camera_picking_with_projections() {
+  use crate::projection::Projection;
+
+  let rect = RectI::new(Vector2::new(0, 0), Vector2::new(800, 600));
+  let mut camera = Camera::with_projection(Vector3::new(0.0, 0.0, 5.0), rect, Projection::orthographic(6.0, 0.1, 100.0));
+  // Offset viewport, the left half of a 1600 wide window
+  let viewport = RectF::new(Vector2::new(100.0, 0.0), Vector2::new(800.0, 600.0));
+  let ray = camera.screen_to_world_ray(Vector2::new(900.0, 0.0), viewport);
+  assert!((ray.origin - Vector3::new(4.0, 3.0, 4.9)).magnitude() < 0.0001);
+  assert!((ray.direction - Vector3::new(0.0, 0.0, -1.0)).magnitude() < 0.0001);
+
+  camera.set_projection(Projection::perspective_infinite(60.0, 0.1));
+  camera.set_reverse_z(true);
+  let point = Vector3::new(-3.0, 2.0, -50.0);
+  let screen = camera.world_to_screen(point, viewport).unwrap();
+  let ray = camera.screen_to_world_ray(screen.truncate(), viewport);
+  assert!((ray.closest_point(point) - point).magnitude() < 0.01);
+  assert!((camera.unproject(screen.truncate(), screen.z, viewport) - point).magnitude() < 0.05);
 }
@@ -0,0 +1,170 @@
+use ale_data::entity::Entity;
+use ale_data::indexmap::Id;
+use ale_input::state::InputState;
+use ale_input::{Input, MouseButton};
+use ale_math::prelude::*;
+use ale_math::{Deg, Quaternion, Vector2, Vector3};
+
+use crate::controller::CameraController;
+use crate::{component, Camera, CameraRenderInfo};
+
+// Circles around a target, drag to rotate and pan, scroll to zoom
+#[derive(Debug)]
+pub struct OrbitCamera {
+  id: Id<Entity>,
+  camera: Camera,
+
+  target: Vector3<f32>,
+  // degrees, 0 yaw looks down -z, positive pitch looks down from above
+  yaw: f32,
+  pitch: f32,
+  distance: f32,
+
+  pub min_distance: f32,
+  pub max_distance: f32,
+  pub min_pitch: f32,
+  pub max_pitch: f32,
+
+  pub rotate_button: MouseButton,
+  pub pan_button: MouseButton,
+  // degrees per window width dragged
+  pub rotate_speed: f32,
+  // distance multiplier per scroll step
+  pub zoom_speed: f32,
+}
+
+impl OrbitCamera {
+  pub fn new(camera: Camera, target: Vector3<f32>, distance: f32) -> OrbitCamera {
+    let mut orbit_camera = OrbitCamera {
+      id: Id::new(),
+      camera,
+      target,
+      yaw: 0.0,
+      pitch: 30.0,
+      distance,
+      min_distance: 1.0,
+      max_distance: 100.0,
+      min_pitch: -89.0,
+      max_pitch: 89.0,
+      rotate_button: MouseButton::ButtonRight,
+      pan_button: MouseButton::ButtonMiddle,
+      rotate_speed: 360.0,
+      zoom_speed: 1.1,
+    };
+    orbit_camera.set_distance(distance);
+    orbit_camera.apply_pose();
+    orbit_camera
+  }
+
+  pub fn id(&self) -> Id<Entity> {
+    self.id
+  }
+
+  pub fn target(&self) -> Vector3<f32> {
+    self.target
+  }
+
+  pub fn set_target(&mut self, target: Vector3<f32>) {
+    self.target = target;
+  }
+
+  pub fn distance(&self) -> f32 {
+    self.distance
+  }
+
+  // Clamped to min_distance and max_distance
+  pub fn set_distance(&mut self, distance: f32) {
+    self.distance = distance.max(self.min_distance).min(self.max_distance);
+  }
+
+  // (yaw, pitch) in degrees
+  pub fn angles(&self) -> (f32, f32) {
+    (self.yaw, self.pitch)
+  }
+
+  // Pitch is clamped to min_pitch and max_pitch
+  pub fn set_angles(&mut self, yaw: f32, pitch: f32) {
+    self.yaw = yaw.rem_euclid(360.0);
+    self.pitch = pitch.max(self.min_pitch).min(self.max_pitch);
+  }
+
+  fn apply_pose(&mut self) {
+    let (yaw, pitch) = (self.yaw.to_radians(), self.pitch.to_radians());
+    let offset = Vector3::new(pitch.cos() * yaw.sin(), pitch.sin(), pitch.cos() * yaw.cos()) * self.distance;
+    self.camera.set_position(self.target + offset);
+    self
+      .camera
+      .set_rotation(Quaternion::from_angle_y(Deg(self.yaw)) * Quaternion::from_angle_x(Deg(-self.pitch)));
+  }
+}
+
+impl CameraController for OrbitCamera {
+  fn camera(&self) -> &Camera {
+    &self.camera
+  }
+
+  fn camera_mut(&mut self) -> &mut Camera {
+    &mut self.camera
+  }
+
+  fn input(&mut self, inputs: &Vec<Input>, input_state: &InputState) {
+    for input in inputs {
+      match input {
+        Input::MouseMotion { rel_x, rel_y, .. } if input_state.is_mouse_button_down(self.rotate_button) => {
+          self.set_angles(self.yaw - rel_x * self.rotate_speed, self.pitch + rel_y * self.rotate_speed);
+        }
+        // the target follows the cursor, a drag across the window moves it by the distance
+        Input::MouseMotion { rel_x, rel_y, .. } if input_state.is_mouse_button_down(self.pan_button) => {
+          let pan = self.camera.right_dir() * -rel_x + self.camera.up_dir() * *rel_y;
+          self.target += pan * self.distance;
+        }
+        Input::Scroll { y, .. } => self.set_distance(self.distance / self.zoom_speed.powf(*y)),
+        _ => {}
+      }
+    }
+  }
+
+  fn tick(&mut self, _delta_time: f32) {
+    self.apply_pose();
+  }
+}
+
+impl component::Camera for OrbitCamera {
+  fn get_camera_info(&mut self) -> (Id<Entity>, CameraRenderInfo) {
+    (self.id, self.camera.camera_render_info())
+  }
+
+  fn set_viewport(&mut self, position: Vector2<i32>, size: Vector2<u32>) {
+    self.camera.set_viewport(position, size);
+  }
+
+  fn set_content_scale(&mut self, content_scale: f32) {
+    self.camera.set_content_scale(content_scale);
+  }
+}
+
+#[test]
+fn orbit_camera_pose_and_limits() {
+  use ale_math::rect::RectI;
+
+  let rect = RectI::new(Vector2::new(0, 0), Vector2::new(800, 600));
+  let mut orbit = OrbitCamera::new(Camera::new(Vector3::zero(), rect, 60.0), Vector3::new(1.0, 0.0, 0.0), 5.0);
+  orbit.set_angles(90.0, 0.0);
+  orbit.tick(0.0);
+  assert!((orbit.camera().position() - Vector3::new(6.0, 0.0, 0.0)).magnitude() < 0.0001);
+  assert!((orbit.camera().forward_dir() - Vector3::new(-1.0, 0.0, 0.0)).magnitude() < 0.0001);
+
+  orbit.set_angles(0.0, 45.0);
+  orbit.tick(0.0);
+  let to_target = (orbit.target() - orbit.camera().position()).normalize();
+  assert!((orbit.camera().forward_dir() - to_target).magnitude() < 0.0001);
+
+  orbit.set_angles(0.0, 120.0);
+  assert_eq!(orbit.angles(), (0.0, 89.0));
+  orbit.set_distance(1000.0);
+  assert_eq!(orbit.distance(), 100.0);
+
+  let input_state = InputState::new();
+  orbit.input(&vec![Input::Scroll { x: 0.0, y: 100.0 }], &input_state);
+  assert_eq!(orbit.distance(), 1.0);
+}
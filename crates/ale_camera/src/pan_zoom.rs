@@ -0,0 +1,191 @@
+use ale_data::entity::Entity;
+use ale_data::indexmap::Id;
+use ale_input::state::InputState;
+use ale_input::{Input, MouseButton};
+use ale_math::rect::{RectF, RectI};
+use ale_math::{Vector2, Vector3};
+
+use crate::controller::CameraController;
+use crate::projection::Projection;
+use crate::{component, Camera, CameraRenderInfo};
+
+// 2D orthographic camera looking down -z, drag to pan, scroll to zoom towards the cursor
+#[derive(Debug)]
+pub struct PanZoomCamera {
+  id: Id<Entity>,
+  camera: Camera,
+  // world units visible from the bottom to the top of the viewport
+  size: f32,
+
+  pub min_size: f32,
+  pub max_size: f32,
+  // the view never leaves these, in world units with y up
+  pub bounds: Option<RectF>,
+
+  pub pan_button: MouseButton,
+  // size multiplier per scroll step
+  pub zoom_speed: f32,
+}
+
+impl PanZoomCamera {
+  pub fn new(center: Vector2<f32>, display_rect: RectI, size: f32) -> PanZoomCamera {
+    let camera = Camera::with_projection(
+      Vector3::new(center.x, center.y, 0.0),
+      display_rect,
+      Projection::orthographic(size, -100.0, 100.0),
+    );
+    PanZoomCamera {
+      id: Id::new(),
+      camera,
+      size,
+      min_size: 1.0,
+      max_size: 1000.0,
+      bounds: None,
+      pan_button: MouseButton::ButtonLeft,
+      zoom_speed: 1.1,
+    }
+  }
+
+  pub fn id(&self) -> Id<Entity> {
+    self.id
+  }
+
+  pub fn center(&self) -> Vector2<f32> {
+    self.camera.position().truncate()
+  }
+
+  pub fn set_center(&mut self, center: Vector2<f32>) {
+    let center = self.clamp_center(center);
+    let z = self.camera.position().z;
+    self.camera.set_position(Vector3::new(center.x, center.y, z));
+  }
+
+  pub fn size(&self) -> f32 {
+    self.size
+  }
+
+  // Clamped to min_size and max_size, zooms around the center
+  pub fn set_size(&mut self, size: f32) {
+    self.size = size.max(self.min_size).min(self.max_size);
+    self.camera.set_projection(Projection::orthographic(self.size, -100.0, 100.0));
+    self.set_center(self.center());
+  }
+
+  // Visible area in world units
+  pub fn visible_size(&self) -> Vector2<f32> {
+    Vector2::new(self.size * self.camera.aspect_ratio(), self.size)
+  }
+
+  // Zooms while keeping the world point under the cursor in place, cursor in logical units
+  pub fn zoom_at(&mut self, cursor: Vector2<f32>, size: f32) {
+    let viewport = self.camera.viewport();
+    if viewport.size.x <= 0.0 || viewport.size.y <= 0.0 {
+      return self.set_size(size);
+    }
+    // -0.5..0.5 from the center of the viewport, y up
+    let offset = Vector2::new(
+      (cursor.x - viewport.position.x) / viewport.size.x - 0.5,
+      0.5 - (cursor.y - viewport.position.y) / viewport.size.y,
+    );
+    let before = self.visible_size();
+    let world_point = self.center() + Vector2::new(offset.x * before.x, offset.y * before.y);
+
+    self.set_size(size);
+    let after = self.visible_size();
+    self.set_center(world_point - Vector2::new(offset.x * after.x, offset.y * after.y));
+  }
+
+  // Keeps the view inside bounds, centered on them when the view is bigger
+  fn clamp_center(&self, center: Vector2<f32>) -> Vector2<f32> {
+    let bounds = match self.bounds {
+      None => return center,
+      Some(bounds) => bounds,
+    };
+    let half = self.visible_size() / 2.0;
+    let (min, max) = bounds.to_min_max();
+    let clamp_axis = |value: f32, half: f32, min: f32, max: f32| {
+      if max - min <= half * 2.0 {
+        (min + max) / 2.0
+      } else {
+        value.max(min + half).min(max - half)
+      }
+    };
+    Vector2::new(
+      clamp_axis(center.x, half.x, min.x, max.x),
+      clamp_axis(center.y, half.y, min.y, max.y),
+    )
+  }
+}
+
+impl CameraController for PanZoomCamera {
+  fn camera(&self) -> &Camera {
+    &self.camera
+  }
+
+  fn camera_mut(&mut self) -> &mut Camera {
+    &mut self.camera
+  }
+
+  fn input(&mut self, inputs: &Vec<Input>, input_state: &InputState) {
+    for input in inputs {
+      match input {
+        // drag the world along with the cursor
+        Input::MouseMotion { rel_x, rel_y, .. } if input_state.is_mouse_button_down(self.pan_button) => {
+          let visible_size = self.visible_size();
+          let center = self.center() + Vector2::new(-rel_x * visible_size.x, rel_y * visible_size.y);
+          self.set_center(center);
+        }
+        Input::Scroll { y, .. } => {
+          let size = self.size / self.zoom_speed.powf(*y);
+          match input_state.mouse_position() {
+            Some((x, y)) => self.zoom_at(Vector2::new(x, y), size),
+            None => self.set_size(size),
+          }
+        }
+        _ => {}
+      }
+    }
+  }
+
+  // the aspect ratio may have changed since the last clamp
+  fn tick(&mut self, _delta_time: f32) {
+    self.set_center(self.center());
+  }
+}
+
+impl component::Camera for PanZoomCamera {
+  fn get_camera_info(&mut self) -> (Id<Entity>, CameraRenderInfo) {
+    (self.id, self.camera.camera_render_info())
+  }
+
+  fn set_viewport(&mut self, position: Vector2<i32>, size: Vector2<u32>) {
+    self.camera.set_viewport(position, size);
+  }
+
+  fn set_content_scale(&mut self, content_scale: f32) {
+    self.camera.set_content_scale(content_scale);
+  }
+}
+
+#[test]
+fn pan_zoom_camera_zoom_to_cursor_and_bounds() {
+  use ale_math::prelude::*;
+
+  let rect = RectI::new(Vector2::new(0, 0), Vector2::new(800, 400));
+  let mut pan_zoom = PanZoomCamera::new(Vector2::zero(), rect, 10.0);
+  assert_eq!(pan_zoom.visible_size(), Vector2::new(20.0, 10.0));
+
+  // The top right corner of the view stays under the cursor
+  pan_zoom.zoom_at(Vector2::new(800.0, 0.0), 5.0);
+  assert_eq!(pan_zoom.size(), 5.0);
+  let corner = pan_zoom.center() + pan_zoom.visible_size() / 2.0;
+  assert!((corner - Vector2::new(10.0, 5.0)).magnitude() < 0.0001);
+
+  pan_zoom.bounds = Some(RectF::new(Vector2::new(-20.0, -20.0), Vector2::new(40.0, 40.0)));
+  pan_zoom.set_center(Vector2::new(100.0, -100.0));
+  assert!((pan_zoom.center() - Vector2::new(15.0, -17.5)).magnitude() < 0.0001);
+
+  // Taller than the bounds, centered vertically
+  pan_zoom.set_size(50.0);
+  assert!((pan_zoom.center().y - 0.0).abs() < 0.0001);
+}
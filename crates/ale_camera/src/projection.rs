@@ -0,0 +1,175 @@
+use ale_math::{ortho, Matrix4, Vector2};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Projection {
+  Perspective {
+    // vertical, in degrees
+    fov: f32,
+    near: f32,
+    // None is an infinite far plane
+    far: Option<f32>,
+  },
+  Orthographic {
+    size: OrthographicSize,
+    near: f32,
+    far: f32,
+  },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrthographicSize {
+  // World units visible from the bottom to the top of the viewport, centered on the camera
+  Vertical(f32),
+  // One world unit per logical pixel, (0, 0) at the top left of the viewport
+  PixelPerfect,
+}
+
+impl Projection {
+  pub fn perspective(fov: f32, near: f32, far: f32) -> Projection {
+    Projection::Perspective {
+      fov,
+      near,
+      far: Some(far),
+    }
+  }
+
+  pub fn perspective_infinite(fov: f32, near: f32) -> Projection {
+    Projection::Perspective { fov, near, far: None }
+  }
+
+  pub fn orthographic(vertical_size: f32, near: f32, far: f32) -> Projection {
+    Projection::Orthographic {
+      size: OrthographicSize::Vertical(vertical_size),
+      near,
+      far,
+    }
+  }
+
+  pub fn pixel_perfect(near: f32, far: f32) -> Projection {
+    Projection::Orthographic {
+      size: OrthographicSize::PixelPerfect,
+      near,
+      far,
+    }
+  }
+
+  // OpenGL clip space, near at -1 and far at 1. Reverse-Z maps near to 1 and far to 0 in a 0..1 clip range,
+  // it needs glClipControl ZERO_TO_ONE, a GEQUAL depth test and clearing depth to 0 (ale_opengl_depth_reverse_z_set).
+  // The window's depth buffer is fixed point, render targets get the full precision from their float depth.
+  // logical_size is the viewport in logical pixels, only used by PixelPerfect.
+  pub fn matrix(&self, aspect_ratio: f32, logical_size: Vector2<f32>, is_reverse_z: bool) -> Matrix4<f32> {
+    match *self {
+      Projection::Perspective { fov, near, far } => {
+        let f = 1.0 / (fov.to_radians() / 2.0).tan();
+        let (z_scale, z_offset) = match (far, is_reverse_z) {
+          (Some(far), false) => ((far + near) / (near - far), (2.0 * far * near) / (near - far)),
+          (None, false) => (-1.0, -2.0 * near),
+          (Some(far), true) => (near / (far - near), far * near / (far - near)),
+          (None, true) => (0.0, near),
+        };
+        #[rustfmt::skip]
+        let matrix = Matrix4::new(
+          f / aspect_ratio, 0.0, 0.0, 0.0,
+          0.0, f, 0.0, 0.0,
+          0.0, 0.0, z_scale, -1.0,
+          0.0, 0.0, z_offset, 0.0,
+        );
+        matrix
+      }
+      Projection::Orthographic {
+        size: OrthographicSize::Vertical(vertical_size),
+        near,
+        far,
+      } => {
+        let half_height = vertical_size / 2.0;
+        let half_width = half_height * aspect_ratio;
+        intern_ortho(-half_width, half_width, -half_height, half_height, near, far, is_reverse_z)
+      }
+      Projection::Orthographic {
+        size: OrthographicSize::PixelPerfect,
+        near,
+        far,
+      } => intern_ortho(0.0, logical_size.x, logical_size.y, 0.0, near, far, is_reverse_z),
+    }
+  }
+
+  pub fn near(&self) -> f32 {
+    match *self {
+      Projection::Perspective { near, .. } => near,
+      Projection::Orthographic { near, .. } => near,
+    }
+  }
+
+  // None when infinite
+  pub fn far(&self) -> Option<f32> {
+    match *self {
+      Projection::Perspective { far, .. } => far,
+      Projection::Orthographic { far, .. } => Some(far),
+    }
+  }
+}
+
+fn intern_ortho(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32, is_reverse_z: bool) -> Matrix4<f32> {
+  let mut matrix = ortho(left, right, bottom, top, near, far);
+  if is_reverse_z {
+    matrix[2][2] = 1.0 / (far - near);
+    matrix[3][2] = far / (far - near);
+  }
+  matrix
+}
+
+#[test]
+fn projection_perspective_depth() {
+  use ale_math::{perspective, Deg, InnerSpace, Vector4};
+
+  let size = Vector2::new(800.0, 600.0);
+  let projection = Projection::perspective(60.0, 0.1, 100.0);
+  let matrix = projection.matrix(4.0 / 3.0, size, false);
+  let expected = perspective(Deg(60.0f32), 4.0 / 3.0, 0.1, 100.0);
+  for i in 0..4 {
+    assert!((matrix[i] - expected[i]).magnitude() < 0.0001);
+  }
+
+  let depth = |matrix: Matrix4<f32>, z: f32| {
+    let clip = matrix * Vector4::new(0.0, 0.0, z, 1.0);
+    clip.z / clip.w
+  };
+  assert!((depth(matrix, -0.1) + 1.0).abs() < 0.0001);
+  assert!((depth(matrix, -100.0) - 1.0).abs() < 0.001);
+
+  // 0..1 clip range, near at 1 and far at 0
+  let reversed = projection.matrix(4.0 / 3.0, size, true);
+  assert!((depth(reversed, -0.1) - 1.0).abs() < 0.0001);
+  assert!(depth(reversed, -100.0).abs() < 0.0001);
+  assert!(depth(reversed, -50.0) > 0.0 && depth(reversed, -50.0) < depth(reversed, -10.0));
+
+  let reversed_infinite = Projection::perspective_infinite(60.0, 0.1).matrix(4.0 / 3.0, size, true);
+  assert!((depth(reversed_infinite, -0.1) - 1.0).abs() < 0.0001);
+  assert!(depth(reversed_infinite, -100000.0) > 0.0);
+
+  // Nothing gets clipped behind the far plane
+  let infinite = Projection::perspective_infinite(60.0, 0.1).matrix(4.0 / 3.0, size, false);
+  assert!((depth(infinite, -0.1) + 1.0).abs() < 0.0001);
+  assert!(depth(infinite, -100000.0) < 1.0);
+  assert_eq!(Projection::perspective_infinite(60.0, 0.1).far(), None);
+}
+
+#[test]
+fn projection_orthographic() {
+  use ale_math::{InnerSpace, Vector4};
+
+  let size = Vector2::new(800.0, 600.0);
+  let vertical = Projection::orthographic(10.0, -1.0, 1.0).matrix(2.0, size, false);
+  let top_right = vertical * Vector4::new(10.0, 5.0, 0.0, 1.0);
+  assert!((top_right - Vector4::new(1.0, 1.0, 0.0, 1.0)).magnitude() < 0.0001);
+
+  let pixel = Projection::pixel_perfect(-1.0, 1.0).matrix(4.0 / 3.0, size, false);
+  let top_left = pixel * Vector4::new(0.0, 0.0, 0.0, 1.0);
+  let bottom_right = pixel * Vector4::new(800.0, 600.0, 0.0, 1.0);
+  assert!((top_left - Vector4::new(-1.0, 1.0, 0.0, 1.0)).magnitude() < 0.0001);
+  assert!((bottom_right - Vector4::new(1.0, -1.0, 0.0, 1.0)).magnitude() < 0.0001);
+
+  let reversed = Projection::orthographic(10.0, 0.0, 10.0).matrix(2.0, size, true);
+  assert!(((reversed * Vector4::new(0.0, 0.0, 0.0, 1.0)).z - 1.0).abs() < 0.0001);
+  assert!((reversed * Vector4::new(0.0, 0.0, -10.0, 1.0)).z.abs() < 0.0001);
+}
@@ -0,0 +1,168 @@
+use ale_data::entity::Entity;
+use ale_data::indexmap::Id;
+use ale_input::state::InputState;
+use ale_input::Input;
+use ale_math::noise::Noise;
+use ale_math::prelude::*;
+use ale_math::{Deg, Quaternion, Vector2, Vector3};
+
+use crate::controller::CameraController;
+use crate::{component, Camera, CameraRenderInfo};
+
+// Trauma based screen shake on top of any controller. Trauma goes 0..1 and decays over time,
+// the shake grows with trauma squared so small hits stay subtle.
+//
+//   let mut camera = CameraShake::new(OrbitCamera::new(camera, target, 10.0), seed);
+//   camera.add_trauma(0.5);
+#[derive(Debug)]
+pub struct CameraShake<C: CameraController> {
+  inner: C,
+
+  trauma: f32,
+  // trauma lost per second
+  pub decay: f32,
+  // world units at full trauma
+  pub max_offset: f32,
+  // degrees at full trauma
+  pub max_roll: f32,
+  // noise samples per second, higher shakes faster
+  pub frequency: f32,
+
+  noise: Noise,
+  time: f32,
+  // what the last tick added, taken off again before the inner controller runs
+  applied_offset: Vector3<f32>,
+  applied_roll: Quaternion<f32>,
+}
+
+impl<C: CameraController> CameraShake<C> {
+  pub fn new(inner: C, seed: u64) -> CameraShake<C> {
+    CameraShake {
+      inner,
+      trauma: 0.0,
+      decay: 1.0,
+      max_offset: 0.5,
+      max_roll: 5.0,
+      frequency: 15.0,
+      noise: Noise::new(seed),
+      time: 0.0,
+      applied_offset: Vector3::zero(),
+      applied_roll: Quaternion::one(),
+    }
+  }
+
+  pub fn inner(&self) -> &C {
+    &self.inner
+  }
+
+  pub fn inner_mut(&mut self) -> &mut C {
+    &mut self.inner
+  }
+
+  pub fn trauma(&self) -> f32 {
+    self.trauma
+  }
+
+  // Hits stack up to 1
+  pub fn add_trauma(&mut self, trauma: f32) {
+    self.trauma = (self.trauma + trauma).max(0.0).min(1.0);
+  }
+
+  fn restore(&mut self) {
+    let camera = self.inner.camera_mut();
+    camera.set_position(camera.position() - self.applied_offset);
+    camera.set_rotation(camera.rotation() * self.applied_roll.invert());
+  }
+
+  fn apply(&mut self) {
+    let camera = self.inner.camera_mut();
+    camera.set_position(camera.position() + self.applied_offset);
+    camera.set_rotation(camera.rotation() * self.applied_roll);
+  }
+}
+
+impl<C: CameraController> CameraController for CameraShake<C> {
+  fn camera(&self) -> &Camera {
+    self.inner.camera()
+  }
+
+  fn camera_mut(&mut self) -> &mut Camera {
+    self.inner.camera_mut()
+  }
+
+  // the inner controller only ever sees the camera without shake
+  fn input(&mut self, inputs: &Vec<Input>, input_state: &InputState) {
+    self.restore();
+    self.inner.input(inputs, input_state);
+    self.apply();
+  }
+
+  fn tick(&mut self, delta_time: f32) {
+    self.restore();
+    self.inner.tick(delta_time);
+
+    self.trauma = (self.trauma - self.decay * delta_time).max(0.0);
+    self.time += delta_time;
+    let shake = self.trauma * self.trauma;
+    if shake > 0.0 {
+      // each axis samples its own row of the noise
+      let t = self.time * self.frequency;
+      let noise = &self.noise;
+      let sample = |row: f32| noise.perlin2(Vector2::new(t, row));
+      let camera = self.inner.camera();
+      self.applied_offset =
+        (camera.right_dir() * sample(0.5) + camera.up_dir() * sample(1.5)) * self.max_offset * shake;
+      self.applied_roll = Quaternion::from_angle_z(Deg(self.max_roll * shake * sample(2.5)));
+    } else {
+      self.applied_offset = Vector3::zero();
+      self.applied_roll = Quaternion::one();
+    }
+    self.apply();
+  }
+}
+
+impl<C: CameraController + component::Camera + 'static> component::Camera for CameraShake<C> {
+  fn get_camera_info(&mut self) -> (Id<Entity>, CameraRenderInfo) {
+    self.inner.get_camera_info()
+  }
+
+  fn set_viewport(&mut self, position: Vector2<i32>, size: Vector2<u32>) {
+    self.inner.set_viewport(position, size);
+  }
+
+  fn set_content_scale(&mut self, content_scale: f32) {
+    self.inner.set_content_scale(content_scale);
+  }
+}
+
+#[test]
+fn camera_shake_decays_and_restores() {
+  use crate::orbit::OrbitCamera;
+  use ale_math::rect::RectI;
+
+  let rect = RectI::new(Vector2::new(0, 0), Vector2::new(800, 600));
+  let orbit = OrbitCamera::new(Camera::new(Vector3::zero(), rect, 60.0), Vector3::zero(), 5.0);
+  let mut shake = CameraShake::new(orbit, 3);
+  shake.tick(0.016);
+  let rest_position = shake.camera().position();
+  let rest_rotation = shake.camera().rotation();
+
+  shake.add_trauma(0.7);
+  shake.add_trauma(0.7);
+  assert_eq!(shake.trauma(), 1.0);
+  let mut was_shaken = false;
+  for _ in 0..10 {
+    shake.tick(0.016);
+    was_shaken |= (shake.camera().position() - rest_position).magnitude() > 0.001;
+  }
+  assert!(was_shaken);
+  assert!(shake.trauma() < 1.0);
+
+  // Back exactly where the orbit camera put it once the trauma is gone
+  for _ in 0..100 {
+    shake.tick(0.016);
+  }
+  assert_eq!(shake.trauma(), 0.0);
+  assert!((shake.camera().position() - rest_position).magnitude() < 0.0001);
+  assert!((shake.camera().rotation() - rest_rotation).magnitude() < 0.0001);
+}
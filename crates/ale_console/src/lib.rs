@@ -1,11 +1,11 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::str::FromStr;
 
 use ale_input::Input;
 use ale_math::num_traits::clamp;
 use ale_variable::Variable;
 
-use crate::ConsoleEvent::{Print, Set};
+use crate::ConsoleEvent::{Print, Run, Set};
 use crate::OnEnterResult::{ArgumentsFailToParse, ArgumentsNumberDiffer, CommandEmpty, CommandNotFound, EventQueued};
 
 pub struct Console {
@@ -20,6 +20,8 @@ pub struct Console {
   pub(crate) max_lines: usize,
 
   pub(crate) registered_commands: HashMap<String, Variable>,
+  // Commands that aren't backed by a variable, their arguments are passed through as is
+  pub(crate) registered_functions: HashSet<String>,
   pub(crate) events: HashMap<String, ConsoleEvent>,
 
   pub has_focus: bool,
@@ -28,6 +30,7 @@ pub struct Console {
 pub enum ConsoleEvent {
   Print,
   Set(Variable),
+  Run(Vec<String>),
   None,
 }
 
@@ -50,6 +53,7 @@ pub fn ale_console_new(max_lines: usize) -> Console {
     lookup: 0,
     events: HashMap::new(),
     registered_commands: HashMap::new(),
+    registered_functions: HashSet::new(),
   }
 }
 
@@ -58,18 +62,7 @@ pub fn ale_console_input(console: &mut Console, input: &Input) {
     Input::Key(key, scancode, action, modifier) => {
       if action == &ale_input::Action::Press {
         if key == &ale_input::Key::Enter {
-          match intern_on_enter(console) {
-            CommandNotFound(cmd) => ale_console_print_output(console, &format!("{} is an invalid command", cmd)),
-            ArgumentsNumberDiffer(cmd, actual, expected) => ale_console_print_output(
-              console,
-              &format!("{} has {} argument(s), need {}", cmd, actual, expected),
-            ),
-            ArgumentsFailToParse(cmd, arg) => {
-              ale_console_print_output(console, &format!("{} fail to parse {}, type mismatch", cmd, arg))
-            }
-            CommandEmpty => {}
-            EventQueued => {}
-          };
+          intern_on_enter_print(console);
         } else if key == &ale_input::Key::GraveAccent {
           console.has_focus = !console.has_focus;
         } else if key == &ale_input::Key::Backspace {
@@ -104,6 +97,12 @@ pub fn ale_console_input(console: &mut Console, input: &Input) {
   }
 }
 
+// Runs line as if it was typed in the console, e.g. "fxaa_is_enabled false"
+pub fn ale_console_execute(console: &mut Console, line: &str) {
+  console.line_buffer = line.to_owned();
+  intern_on_enter_print(console);
+}
+
 pub fn ale_console_variable_register(console: &mut Console, variable: Variable) {
   console.registered_commands.insert(variable.name_str(), variable);
 }
@@ -116,12 +115,25 @@ pub fn ale_console_variable_event_handle<T: From<Variable>>(console: &mut Consol
   {
     Print => ale_console_print_output(console, &variable.value_str()),
     Set(x) => return x.into(),
-    ConsoleEvent::None => {}
+    Run(_) | ConsoleEvent::None => {}
   }
 
   return variable.into();
 }
 
+// A command taking any arguments, e.g. "profiler_dump 60"
+pub fn ale_console_command_register(console: &mut Console, name: &str) {
+  console.registered_functions.insert(name.to_owned());
+}
+
+// Arguments of the command if it was entered since the last call
+pub fn ale_console_command_event_handle(console: &mut Console, name: &str) -> Option<Vec<String>> {
+  match console.events.remove(name) {
+    Some(Run(arguments)) => Some(arguments),
+    _ => None,
+  }
+}
+
 pub fn ale_console_variable_has_event(console: &Console) -> bool {
   !console.events.is_empty()
 }
@@ -130,6 +142,21 @@ pub fn ale_console_print_output(console: &mut Console, cmd: &str) {
   console.lines.push_back(format!(">> {}", cmd));
 }
 
+fn intern_on_enter_print(console: &mut Console) {
+  match intern_on_enter(console) {
+    CommandNotFound(cmd) => ale_console_print_output(console, &format!("{} is an invalid command", cmd)),
+    ArgumentsNumberDiffer(cmd, actual, expected) => ale_console_print_output(
+      console,
+      &format!("{} has {} argument(s), need {}", cmd, actual, expected),
+    ),
+    ArgumentsFailToParse(cmd, arg) => {
+      ale_console_print_output(console, &format!("{} fail to parse {}, type mismatch", cmd, arg))
+    }
+    CommandEmpty => {}
+    EventQueued => {}
+  };
+}
+
 fn intern_on_enter(console: &mut Console) -> OnEnterResult {
   // If the console has too many lines already, we pop the earliest one
   if console.lines.len() > console.max_lines {
@@ -150,6 +177,12 @@ fn intern_on_enter(console: &mut Console) -> OnEnterResult {
     return CommandEmpty;
   }
 
+  if console.registered_functions.contains(words[0]) {
+    let arguments = words[1..].iter().map(|word| word.to_string()).collect();
+    console.events.insert(words[0].to_owned(), Run(arguments));
+    return EventQueued;
+  }
+
   let variable = console.registered_commands.get_mut(&words[0].to_owned());
 
   match variable {
@@ -230,3 +263,20 @@ fn intern_add_command_line(console: &mut Console, cmd: &str) {
     console.commands.push_back(cmd.to_owned());
   }
 }
+
+#[test]
+fn console_command_passes_arguments() {
+  let mut console = ale_console_new(10);
+  ale_console_command_register(&mut console, "profiler_dump");
+  assert_eq!(ale_console_command_event_handle(&mut console, "profiler_dump"), None);
+
+  ale_console_execute(&mut console, "profiler_dump 60");
+  assert_eq!(
+    ale_console_command_event_handle(&mut console, "profiler_dump"),
+    Some(vec!["60".to_owned()])
+  );
+  assert_eq!(ale_console_command_event_handle(&mut console, "profiler_dump"), None);
+
+  ale_console_execute(&mut console, "profiler_dump");
+  assert_eq!(ale_console_command_event_handle(&mut console, "profiler_dump"), Some(vec![]));
+}
@@ -0,0 +1,546 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use std::{fs, io};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+use crate::{Action, GamepadAxis, GamepadButton, Input, Key, Modifier, MouseAxis, MouseButton};
+
+// Analog bindings (gamepad axes) count as held for actions past this value
+const ACTION_ANALOG_THRESHOLD: f32 = 0.5;
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Binding {
+  Key(Key),
+  MouseButton(MouseButton),
+  // relative motion since the last frame, normalized to the window size
+  MouseMotion(MouseAxis),
+  GamepadButton(GamepadButton),
+  // held past ACTION_ANALOG_THRESHOLD in the direction, axes only get that side of the stick
+  GamepadAxis {
+    axis: GamepadAxis,
+    direction: AxisDirection,
+  },
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AxisDirection {
+  Positive,
+  Negative,
+  // either way, triggers rest at -1.0 so bind them to Positive instead
+  Both,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ActionBinding {
+  // every modifier here has to be held
+  #[serde(default, with = "intern_modifier", skip_serializing_if = "Modifier::is_empty")]
+  pub modifiers: Modifier,
+  // last, a gamepad axis is a table and toml can't have values after it
+  #[serde(flatten)]
+  pub binding: Binding,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct AxisBinding {
+  // value contributed when a digital binding is held, or multiplier for analog ones (e.g. -1.0 to invert)
+  #[serde(default = "intern_default_scale")]
+  pub scale: f32,
+  // analog values with a smaller magnitude are treated as 0
+  #[serde(default)]
+  pub dead_zone: f32,
+  // last, a gamepad axis is a table and toml can't have values after it
+  #[serde(flatten)]
+  pub binding: Binding,
+}
+
+// Named actions and axes bound to raw input, feed every input through handle_input
+// and call begin_frame before feeding the inputs of a new frame.
+//
+//   let mut actions = ActionMap::new();
+//   actions.bind_action("rotate", ActionBinding::new(Binding::Key(Key::Space)));
+//   actions.bind_axis("move_x", AxisBinding::new(Binding::Key(Key::Left), -1.0));
+//   actions.bind_axis("move_x", AxisBinding::new(Binding::Key(Key::Right), 1.0));
+//   let left_x = Binding::GamepadAxis { axis: GamepadAxis::LeftX, direction: AxisDirection::Both };
+//   actions.bind_axis("move_x", AxisBinding::analog(left_x, 0.2));
+//
+//   if actions.pressed("rotate") { .. }
+//   let move_x = actions.axis("move_x");
+//
+// Gamepad bindings don't know which pad they come from, every connected pad drives them
+// and the last input wins. Use one ActionMap per player fed with only that pad's inputs to split them.
+#[derive(Default, Serialize, Deserialize)]
+pub struct ActionMap {
+  #[serde(default)]
+  actions: BTreeMap<String, Vec<ActionBinding>>,
+  #[serde(default)]
+  axes: BTreeMap<String, Vec<AxisBinding>>,
+
+  #[serde(skip)]
+  down: HashSet<Binding>,
+  #[serde(skip)]
+  just_pressed: HashSet<Binding>,
+  #[serde(skip)]
+  just_released: HashSet<Binding>,
+  #[serde(skip)]
+  values: HashMap<Binding, f32>,
+  #[serde(skip)]
+  gamepad_axes: HashMap<GamepadAxis, f32>,
+  #[serde(skip)]
+  modifiers: Modifier,
+}
+
+#[derive(Error, Debug)]
+pub enum ActionMapError {
+  #[error("(ActionMapError::IOError)\nPath: {}\nError: {}", .1, .0)]
+  IOError(io::Error, String),
+  #[error("(ActionMapError::ParseError)\nPath: {}\nError: {}", .1, .0)]
+  ParseError(toml::de::Error, String),
+  #[error("(ActionMapError::SerializeError) {}", .0)]
+  SerializeError(#[from] toml::ser::Error),
+}
+
+impl ActionBinding {
+  pub fn new(binding: Binding) -> ActionBinding {
+    ActionBinding {
+      binding,
+      modifiers: Modifier::empty(),
+    }
+  }
+
+  pub fn with_modifiers(binding: Binding, modifiers: Modifier) -> ActionBinding {
+    ActionBinding { binding, modifiers }
+  }
+}
+
+impl AxisBinding {
+  pub fn new(binding: Binding, scale: f32) -> AxisBinding {
+    AxisBinding {
+      binding,
+      scale,
+      dead_zone: 0.0,
+    }
+  }
+
+  pub fn analog(binding: Binding, dead_zone: f32) -> AxisBinding {
+    AxisBinding {
+      binding,
+      scale: 1.0,
+      dead_zone,
+    }
+  }
+}
+
+impl Binding {
+  // The binding an input would trigger, used to rebind at runtime by listening to the next input
+  pub fn from_input(input: &Input) -> Option<Binding> {
+    match input {
+      Input::Key(key, _, Action::Press, _) => Some(Binding::Key(*key)),
+      Input::MouseButton(mouse_button, Action::Press, _) => Some(Binding::MouseButton(*mouse_button)),
+      Input::GamepadButton(_, gamepad_button, Action::Press) => Some(Binding::GamepadButton(*gamepad_button)),
+      Input::GamepadAxis(_, gamepad_axis, value) if *value > ACTION_ANALOG_THRESHOLD => {
+        Some(Binding::GamepadAxis {
+          axis: *gamepad_axis,
+          direction: AxisDirection::Positive,
+        })
+      }
+      Input::GamepadAxis(_, gamepad_axis, value) if *value < -ACTION_ANALOG_THRESHOLD => {
+        Some(Binding::GamepadAxis {
+          axis: *gamepad_axis,
+          direction: AxisDirection::Negative,
+        })
+      }
+      _ => None,
+    }
+  }
+}
+
+impl ActionMap {
+  pub fn new() -> ActionMap {
+    ActionMap::default()
+  }
+
+  pub fn load(path: &Path) -> Result<ActionMap, ActionMapError> {
+    let path_str = path.to_string_lossy().to_string();
+    let content = fs::read_to_string(path).map_err(|e| ActionMapError::IOError(e, path_str.clone()))?;
+    toml::from_str(&content).map_err(|e| ActionMapError::ParseError(e, path_str))
+  }
+
+  pub fn save(&self, path: &Path) -> Result<(), ActionMapError> {
+    let content = toml::to_string(self)?;
+    fs::write(path, content).map_err(|e| ActionMapError::IOError(e, path.to_string_lossy().to_string()))
+  }
+
+  pub fn bind_action(&mut self, name: &str, action_binding: ActionBinding) {
+    self.actions.entry(name.to_owned()).or_insert(vec![]).push(action_binding);
+  }
+
+  pub fn bind_axis(&mut self, name: &str, axis_binding: AxisBinding) {
+    self.axes.entry(name.to_owned()).or_insert(vec![]).push(axis_binding);
+  }
+
+  // Replaces the binding at index, or adds it if the action doesn't have that many bindings
+  pub fn rebind_action(&mut self, name: &str, index: usize, action_binding: ActionBinding) {
+    let bindings = self.actions.entry(name.to_owned()).or_insert(vec![]);
+    match bindings.get_mut(index) {
+      None => bindings.push(action_binding),
+      Some(b) => *b = action_binding,
+    }
+  }
+
+  pub fn rebind_axis(&mut self, name: &str, index: usize, axis_binding: AxisBinding) {
+    let bindings = self.axes.entry(name.to_owned()).or_insert(vec![]);
+    match bindings.get_mut(index) {
+      None => bindings.push(axis_binding),
+      Some(b) => *b = axis_binding,
+    }
+  }
+
+  pub fn unbind_action(&mut self, name: &str) {
+    self.actions.remove(name);
+  }
+
+  pub fn unbind_axis(&mut self, name: &str) {
+    self.axes.remove(name);
+  }
+
+  pub fn action_bindings(&self, name: &str) -> &[ActionBinding] {
+    self.actions.get(name).map(|b| b.as_slice()).unwrap_or(&[])
+  }
+
+  pub fn axis_bindings(&self, name: &str) -> &[AxisBinding] {
+    self.axes.get(name).map(|b| b.as_slice()).unwrap_or(&[])
+  }
+
+  // Clears what only lasts a single frame (pressed, released, mouse motion)
+  pub fn begin_frame(&mut self) {
+    self.just_pressed.clear();
+    self.just_released.clear();
+    self.values.remove(&Binding::MouseMotion(MouseAxis::X));
+    self.values.remove(&Binding::MouseMotion(MouseAxis::Y));
+  }
+
+  pub fn handle_input(&mut self, input: &Input) {
+    match input {
+      Input::Key(key, _, action, modifier) => {
+        self.modifiers = *modifier;
+        self.intern_digital(Binding::Key(*key), *action);
+      }
+      Input::MouseButton(mouse_button, action, modifier) => {
+        self.modifiers = *modifier;
+        self.intern_digital(Binding::MouseButton(*mouse_button), *action);
+      }
+      Input::MouseMotion { rel_x, rel_y, .. } => {
+        *self.values.entry(Binding::MouseMotion(MouseAxis::X)).or_insert(0.0) += rel_x;
+        *self.values.entry(Binding::MouseMotion(MouseAxis::Y)).or_insert(0.0) += rel_y;
+      }
+      Input::GamepadButton(_, gamepad_button, action) => {
+        self.intern_digital(Binding::GamepadButton(*gamepad_button), *action);
+      }
+      Input::GamepadAxis(_, gamepad_axis, value) => {
+        for direction in [AxisDirection::Positive, AxisDirection::Negative, AxisDirection::Both] {
+          let binding = Binding::GamepadAxis {
+            axis: *gamepad_axis,
+            direction,
+          };
+          let was_down = self.down.contains(&binding);
+          let is_down = direction.value(*value) > ACTION_ANALOG_THRESHOLD;
+          if is_down && !was_down {
+            self.intern_digital(binding, Action::Press);
+          } else if !is_down && was_down {
+            self.intern_digital(binding, Action::Release);
+          }
+        }
+        self.gamepad_axes.insert(*gamepad_axis, *value);
+      }
+      _ => {}
+    }
+  }
+
+  // Went down this frame
+  pub fn pressed(&self, name: &str) -> bool {
+    self
+      .action_bindings(name)
+      .iter()
+      .any(|b| self.just_pressed.contains(&b.binding) && self.modifiers.contains(b.modifiers))
+  }
+
+  pub fn held(&self, name: &str) -> bool {
+    self
+      .action_bindings(name)
+      .iter()
+      .any(|b| self.down.contains(&b.binding) && self.modifiers.contains(b.modifiers))
+  }
+
+  // Went up this frame
+  pub fn released(&self, name: &str) -> bool {
+    self
+      .action_bindings(name)
+      .iter()
+      .any(|b| self.just_released.contains(&b.binding))
+  }
+
+  // Sum of every binding of the axis, digital bindings contribute their scale while held
+  pub fn axis(&self, name: &str) -> f32 {
+    let mut value = 0.0;
+    for axis_binding in self.axis_bindings(name) {
+      value += match axis_binding.binding {
+        Binding::MouseMotion(_) | Binding::GamepadAxis { .. } => {
+          let raw = match axis_binding.binding {
+            Binding::GamepadAxis { axis, direction } => {
+              let raw = *self.gamepad_axes.get(&axis).unwrap_or(&0.0);
+              match direction {
+                AxisDirection::Positive => raw.max(0.0),
+                AxisDirection::Negative => raw.min(0.0),
+                AxisDirection::Both => raw,
+              }
+            }
+            binding => *self.values.get(&binding).unwrap_or(&0.0),
+          };
+          if raw.abs() <= axis_binding.dead_zone {
+            0.0
+          } else {
+            raw * axis_binding.scale
+          }
+        }
+        binding => {
+          if self.down.contains(&binding) {
+            axis_binding.scale
+          } else {
+            0.0
+          }
+        }
+      };
+    }
+    value
+  }
+
+  fn intern_digital(&mut self, binding: Binding, action: Action) {
+    match action {
+      Action::Press => {
+        if self.down.insert(binding) {
+          self.just_pressed.insert(binding);
+        }
+      }
+      Action::Release => {
+        if self.down.remove(&binding) {
+          self.just_released.insert(binding);
+        }
+      }
+      Action::Repeat => {}
+    }
+  }
+}
+
+impl AxisDirection {
+  // How far value goes in this direction, negative when it goes the other way
+  fn value(&self, value: f32) -> f32 {
+    match self {
+      AxisDirection::Positive => value,
+      AxisDirection::Negative => -value,
+      AxisDirection::Both => value.abs(),
+    }
+  }
+}
+
+fn intern_default_scale() -> f32 {
+  1.0
+}
+
+mod intern_modifier {
+  use super::*;
+
+  const NAMES: [(&str, Modifier); 6] = [
+    ("shift", Modifier::SHIFT),
+    ("control", Modifier::CONTROL),
+    ("alt", Modifier::ALT),
+    ("super", Modifier::SUPER),
+    ("capslock", Modifier::CAPSLOCK),
+    ("numlock", Modifier::NUMLOCK),
+  ];
+
+  pub fn serialize<S: Serializer>(modifier: &Modifier, serializer: S) -> Result<S::Ok, S::Error> {
+    let names: Vec<&str> = NAMES
+      .iter()
+      .filter(|(_, m)| modifier.contains(*m))
+      .map(|(name, _)| *name)
+      .collect();
+    names.serialize(serializer)
+  }
+
+  pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Modifier, D::Error> {
+    let names: Vec<String> = Vec::deserialize(deserializer)?;
+    let mut modifier = Modifier::empty();
+    for name in names {
+      match NAMES.iter().find(|(n, _)| *n == name.to_lowercase()) {
+        None => return Err(serde::de::Error::custom(format!("unknown modifier {}", name))),
+        Some((_, m)) => modifier |= *m,
+      }
+    }
+    Ok(modifier)
+  }
+}
+
+#[test]
+fn action_map_pressed_held_released() {
+  let mut actions = ActionMap::new();
+  actions.bind_action("rotate", ActionBinding::new(Binding::Key(Key::Space)));
+  actions.bind_action("rotate", ActionBinding::new(Binding::GamepadButton(GamepadButton::A)));
+
+  actions.begin_frame();
+  actions.handle_input(&Input::Key(Key::Space, 0, Action::Press, Modifier::empty()));
+  assert!(actions.pressed("rotate"));
+  assert!(actions.held("rotate"));
+
+  actions.begin_frame();
+  actions.handle_input(&Input::Key(Key::Space, 0, Action::Repeat, Modifier::empty()));
+  assert!(!actions.pressed("rotate"));
+  assert!(actions.held("rotate"));
+
+  actions.begin_frame();
+  actions.handle_input(&Input::Key(Key::Space, 0, Action::Release, Modifier::empty()));
+  assert!(actions.released("rotate"));
+  assert!(!actions.held("rotate"));
+
+  actions.begin_frame();
+  actions.handle_input(&Input::GamepadButton(0, GamepadButton::A, Action::Press));
+  assert!(actions.pressed("rotate"));
+  assert!(!actions.pressed("unknown"));
+}
+
+#[test]
+fn action_map_modifiers() {
+  let mut actions = ActionMap::new();
+  actions.bind_action(
+    "save",
+    ActionBinding::with_modifiers(Binding::Key(Key::S), Modifier::CONTROL),
+  );
+
+  actions.handle_input(&Input::Key(Key::S, 0, Action::Press, Modifier::empty()));
+  assert!(!actions.pressed("save"));
+
+  actions.begin_frame();
+  actions.handle_input(&Input::Key(Key::S, 0, Action::Release, Modifier::empty()));
+  actions.handle_input(&Input::Key(Key::S, 0, Action::Press, Modifier::CONTROL | Modifier::SHIFT));
+  assert!(actions.pressed("save"));
+}
+
+#[test]
+fn action_map_axis() {
+  let mut actions = ActionMap::new();
+  actions.bind_axis("move_x", AxisBinding::new(Binding::Key(Key::Left), -1.0));
+  actions.bind_axis("move_x", AxisBinding::new(Binding::Key(Key::Right), 1.0));
+  let left_x = Binding::GamepadAxis {
+    axis: GamepadAxis::LeftX,
+    direction: AxisDirection::Both,
+  };
+  actions.bind_axis("move_x", AxisBinding::analog(left_x, 0.2));
+  actions.bind_axis("look_x", AxisBinding::new(Binding::MouseMotion(MouseAxis::X), 2.0));
+
+  actions.handle_input(&Input::Key(Key::Left, 0, Action::Press, Modifier::empty()));
+  assert_eq!(actions.axis("move_x"), -1.0);
+
+  actions.handle_input(&Input::GamepadAxis(0, GamepadAxis::LeftX, 0.1));
+  assert_eq!(actions.axis("move_x"), -1.0);
+  actions.handle_input(&Input::GamepadAxis(0, GamepadAxis::LeftX, 0.5));
+  assert_eq!(actions.axis("move_x"), -0.5);
+
+  actions.handle_input(&Input::MouseMotion {
+    rel_x: 0.25,
+    rel_y: 0.0,
+    abs_x: 0.0,
+    abs_y: 0.0,
+  });
+  assert_eq!(actions.axis("look_x"), 0.5);
+  actions.begin_frame();
+  assert_eq!(actions.axis("look_x"), 0.0);
+}
+
+#[test]
+fn action_map_save_load_rebind() {
+  let mut actions = ActionMap::new();
+  actions.bind_action("rotate", ActionBinding::new(Binding::Key(Key::Space)));
+  actions.bind_action(
+    "save",
+    ActionBinding::with_modifiers(Binding::Key(Key::S), Modifier::CONTROL),
+  );
+  let left_x = Binding::GamepadAxis {
+    axis: GamepadAxis::LeftX,
+    direction: AxisDirection::Both,
+  };
+  actions.bind_axis("move_x", AxisBinding::analog(left_x, 0.2));
+  actions.bind_action("menu", ActionBinding::with_modifiers(left_x, Modifier::SHIFT));
+
+  let rebind = Binding::from_input(&Input::Key(Key::Up, 0, Action::Press, Modifier::empty())).unwrap();
+  actions.rebind_action("rotate", 0, ActionBinding::new(rebind));
+
+  let path = std::env::temp_dir().join(format!("ale_action_map_test_{}.toml", std::process::id()));
+  actions.save(&path).unwrap();
+  let loaded = ActionMap::load(&path).unwrap();
+  let _ = fs::remove_file(&path);
+
+  assert_eq!(loaded.action_bindings("rotate"), &[ActionBinding::new(Binding::Key(Key::Up))]);
+  assert_eq!(loaded.action_bindings("save"), actions.action_bindings("save"));
+  assert_eq!(loaded.axis_bindings("move_x"), actions.axis_bindings("move_x"));
+  assert_eq!(loaded.action_bindings("menu"), actions.action_bindings("menu"));
+}
+
+#[test]
+fn action_map_gamepad_axis_direction() {
+  let mut actions = ActionMap::new();
+  let left = Binding::GamepadAxis {
+    axis: GamepadAxis::LeftX,
+    direction: AxisDirection::Negative,
+  };
+  actions.bind_action("left", ActionBinding::new(left));
+  let right = Binding::GamepadAxis {
+    axis: GamepadAxis::LeftX,
+    direction: AxisDirection::Positive,
+  };
+  actions.bind_action("right", ActionBinding::new(right));
+  let shoot = Binding::GamepadAxis {
+    axis: GamepadAxis::RightTrigger,
+    direction: AxisDirection::Positive,
+  };
+  actions.bind_action("shoot", ActionBinding::new(shoot));
+  actions.bind_axis(
+    "throttle",
+    AxisBinding::analog(
+      Binding::GamepadAxis {
+        axis: GamepadAxis::LeftY,
+        direction: AxisDirection::Negative,
+      },
+      0.0,
+    ),
+  );
+
+  actions.handle_input(&Input::GamepadAxis(0, GamepadAxis::LeftX, -0.9));
+  assert!(actions.pressed("left"));
+  assert!(!actions.held("right"));
+
+  actions.begin_frame();
+  actions.handle_input(&Input::GamepadAxis(0, GamepadAxis::LeftX, 0.9));
+  assert!(actions.released("left"));
+  assert!(actions.pressed("right"));
+
+  // A trigger at rest doesn't count as held
+  actions.handle_input(&Input::GamepadAxis(0, GamepadAxis::RightTrigger, -1.0));
+  assert!(!actions.held("shoot"));
+
+  actions.handle_input(&Input::GamepadAxis(0, GamepadAxis::LeftY, 0.75));
+  assert_eq!(actions.axis("throttle"), 0.0);
+  actions.handle_input(&Input::GamepadAxis(0, GamepadAxis::LeftY, -0.75));
+  assert_eq!(actions.axis("throttle"), -0.75);
+
+  let rebind = Binding::from_input(&Input::GamepadAxis(0, GamepadAxis::RightX, -0.8));
+  assert_eq!(
+    rebind,
+    Some(Binding::GamepadAxis {
+      axis: GamepadAxis::RightX,
+      direction: AxisDirection::Negative,
+    })
+  );
+}
@@ -0,0 +1,399 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::action::{ActionBinding, Binding};
+use crate::{Action, Input, Modifier, MouseButton};
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct GestureConfig {
+  // seconds between the two clicks of a double-click
+  pub double_click_time: f32,
+  // logical units the cursor may move between the two clicks
+  pub double_click_distance: f32,
+  // seconds a button has to be held
+  pub long_press_time: f32,
+  // logical units the cursor has to move with a button down before it's a drag
+  pub drag_threshold: f32,
+  // seconds allowed between two steps of a sequence
+  pub sequence_step_time: f32,
+}
+
+impl Default for GestureConfig {
+  fn default() -> GestureConfig {
+    GestureConfig {
+      double_click_time: 0.3,
+      double_click_distance: 4.0,
+      long_press_time: 0.5,
+      drag_threshold: 4.0,
+      sequence_step_time: 0.3,
+    }
+  }
+}
+
+// Positions are window coordinates in logical units
+#[derive(Clone, PartialEq, Debug)]
+pub enum Gesture {
+  DoubleClick {
+    button: MouseButton,
+    position: (f32, f32),
+  },
+  LongPress(Binding),
+  // position is where the button went down
+  DragStart {
+    button: MouseButton,
+    position: (f32, f32),
+  },
+  // delta since the previous DragMove
+  DragMove {
+    button: MouseButton,
+    position: (f32, f32),
+    delta: (f32, f32),
+  },
+  // delta since DragStart
+  DragEnd {
+    button: MouseButton,
+    position: (f32, f32),
+    delta: (f32, f32),
+  },
+  Chord(String),
+  Sequence(String),
+}
+
+struct Drag {
+  button: MouseButton,
+  origin: (f32, f32),
+  last: (f32, f32),
+  is_dragging: bool,
+}
+
+// Detects gestures on top of raw inputs, same frame protocol as ActionMap:
+// begin_frame once per frame, then every input of that frame through handle_input.
+//
+//   let mut gestures = GestureDetector::new(GestureConfig::default());
+//   gestures.add_chord("save_all", ActionBinding::with_modifiers(Binding::Key(Key::S), Modifier::CONTROL | Modifier::SHIFT));
+//   gestures.add_sequence("hadouken", vec![Binding::Key(Key::Down), Binding::Key(Key::Right), Binding::Key(Key::J)]);
+//
+//   for gesture in gestures.gestures() { .. }
+//   if gestures.sequence("hadouken") { .. }
+pub struct GestureDetector {
+  pub config: GestureConfig,
+  chords: Vec<(String, ActionBinding)>,
+  sequences: Vec<(String, Vec<Binding>)>,
+
+  // seconds since creation
+  time: f32,
+  gestures: Vec<Gesture>,
+
+  // binding -> time it went down
+  down: HashMap<Binding, f32>,
+  long_pressed: HashSet<Binding>,
+  last_click: Option<(MouseButton, f32, (f32, f32))>,
+  mouse_position: (f32, f32),
+  drag: Option<Drag>,
+  // recent presses for sequences
+  history: Vec<(Binding, f32)>,
+}
+
+impl GestureDetector {
+  pub fn new(config: GestureConfig) -> GestureDetector {
+    GestureDetector {
+      config,
+      chords: vec![],
+      sequences: vec![],
+      time: 0.0,
+      gestures: vec![],
+      down: HashMap::new(),
+      long_pressed: HashSet::new(),
+      last_click: None,
+      mouse_position: (0.0, 0.0),
+      drag: None,
+      history: vec![],
+    }
+  }
+
+  // Modifiers have to match exactly, Ctrl+Shift+S won't trigger a Ctrl+S chord
+  pub fn add_chord(&mut self, name: &str, chord: ActionBinding) {
+    self.chords.push((name.to_owned(), chord));
+  }
+
+  // Bindings pressed in order, each within sequence_step_time of the previous one
+  pub fn add_sequence(&mut self, name: &str, sequence: Vec<Binding>) {
+    self.sequences.push((name.to_owned(), sequence));
+  }
+
+  // Clears last frame's gestures and reports long presses that passed long_press_time
+  pub fn begin_frame(&mut self, delta_time: f32) {
+    self.gestures.clear();
+    self.time += delta_time;
+
+    for (binding, pressed_time) in &self.down {
+      if self.time - pressed_time < self.config.long_press_time || self.long_pressed.contains(binding) {
+        continue;
+      }
+      // a drag isn't a long press
+      let is_dragging = match (&self.drag, binding) {
+        (Some(drag), Binding::MouseButton(button)) => drag.is_dragging && drag.button == *button,
+        _ => false,
+      };
+      if !is_dragging {
+        self.long_pressed.insert(*binding);
+        self.gestures.push(Gesture::LongPress(*binding));
+      }
+    }
+  }
+
+  pub fn handle_input(&mut self, input: &Input) {
+    if let Input::MouseMotion { abs_x, abs_y, .. } = input {
+      self.intern_motion((*abs_x, *abs_y));
+      return;
+    }
+
+    let (binding, action, modifiers) = match input {
+      Input::Key(key, _, action, modifier) => (Binding::Key(*key), *action, *modifier),
+      Input::MouseButton(mouse_button, action, modifier) => (Binding::MouseButton(*mouse_button), *action, *modifier),
+      Input::GamepadButton(_, gamepad_button, action) => {
+        (Binding::GamepadButton(*gamepad_button), *action, Modifier::empty())
+      }
+      _ => return,
+    };
+
+    match action {
+      Action::Press => self.intern_press(binding, modifiers),
+      Action::Release => self.intern_release(binding),
+      Action::Repeat => {}
+    }
+  }
+
+  // Detected this frame
+  pub fn gestures(&self) -> &[Gesture] {
+    &self.gestures
+  }
+
+  pub fn chord(&self, name: &str) -> bool {
+    self.gestures.iter().any(|g| matches!(g, Gesture::Chord(n) if n == name))
+  }
+
+  pub fn sequence(&self, name: &str) -> bool {
+    self.gestures.iter().any(|g| matches!(g, Gesture::Sequence(n) if n == name))
+  }
+
+  pub fn double_clicked(&self, mouse_button: MouseButton) -> bool {
+    self
+      .gestures
+      .iter()
+      .any(|g| matches!(g, Gesture::DoubleClick { button, .. } if *button == mouse_button))
+  }
+
+  fn intern_press(&mut self, binding: Binding, modifiers: Modifier) {
+    self.down.insert(binding, self.time);
+
+    for (name, chord) in &self.chords {
+      if chord.binding == binding && chord.modifiers == modifiers {
+        self.gestures.push(Gesture::Chord(name.clone()));
+      }
+    }
+
+    if let Binding::MouseButton(button) = binding {
+      let position = self.mouse_position;
+      match self.last_click {
+        Some((last_button, last_time, last_position))
+          if last_button == button
+            && self.time - last_time <= self.config.double_click_time
+            && intern_distance(last_position, position) <= self.config.double_click_distance =>
+        {
+          self.gestures.push(Gesture::DoubleClick { button, position });
+          // a third click starts over instead of double-clicking again
+          self.last_click = None;
+        }
+        _ => self.last_click = Some((button, self.time, position)),
+      }
+
+      if self.drag.is_none() {
+        self.drag = Some(Drag {
+          button,
+          origin: position,
+          last: position,
+          is_dragging: false,
+        });
+      }
+    }
+
+    if let Some((_, last_time)) = self.history.last() {
+      if self.time - last_time > self.config.sequence_step_time {
+        self.history.clear();
+      }
+    }
+    self.history.push((binding, self.time));
+    let longest = self.sequences.iter().map(|(_, s)| s.len()).max().unwrap_or(0);
+    if self.history.len() > longest {
+      self.history.drain(..self.history.len() - longest);
+    }
+    for (name, sequence) in &self.sequences {
+      let recent = self.history.iter().rev().map(|(b, _)| b).take(sequence.len());
+      if !sequence.is_empty() && recent.eq(sequence.iter().rev()) {
+        self.gestures.push(Gesture::Sequence(name.clone()));
+        self.history.clear();
+        break;
+      }
+    }
+  }
+
+  fn intern_release(&mut self, binding: Binding) {
+    self.down.remove(&binding);
+    self.long_pressed.remove(&binding);
+
+    if let Binding::MouseButton(button) = binding {
+      match &self.drag {
+        Some(drag) if drag.button == button => {
+          if drag.is_dragging {
+            self.gestures.push(Gesture::DragEnd {
+              button,
+              position: self.mouse_position,
+              delta: (self.mouse_position.0 - drag.origin.0, self.mouse_position.1 - drag.origin.1),
+            });
+          }
+          self.drag = None;
+        }
+        _ => {}
+      }
+    }
+  }
+
+  fn intern_motion(&mut self, position: (f32, f32)) {
+    self.mouse_position = position;
+
+    let drag = match &mut self.drag {
+      None => return,
+      Some(drag) => drag,
+    };
+    if !drag.is_dragging {
+      if intern_distance(drag.origin, position) < self.config.drag_threshold {
+        return;
+      }
+      drag.is_dragging = true;
+      self.gestures.push(Gesture::DragStart {
+        button: drag.button,
+        position: drag.origin,
+      });
+    }
+    self.gestures.push(Gesture::DragMove {
+      button: drag.button,
+      position,
+      delta: (position.0 - drag.last.0, position.1 - drag.last.1),
+    });
+    drag.last = position;
+  }
+}
+
+fn intern_distance(a: (f32, f32), b: (f32, f32)) -> f32 {
+  ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+}
+
+#[test]
+fn gesture_double_click_and_long_press() {
+  let mut gestures = GestureDetector::new(GestureConfig::default());
+  let click = |action| Input::MouseButton(MouseButton::ButtonLeft, action, Modifier::empty());
+
+  gestures.begin_frame(0.016);
+  gestures.handle_input(&click(Action::Press));
+  gestures.handle_input(&click(Action::Release));
+  gestures.begin_frame(0.1);
+  gestures.handle_input(&click(Action::Press));
+  assert!(gestures.double_clicked(MouseButton::ButtonLeft));
+
+  // held long enough, reported once
+  gestures.begin_frame(0.6);
+  assert_eq!(
+    gestures.gestures(),
+    &[Gesture::LongPress(Binding::MouseButton(MouseButton::ButtonLeft))]
+  );
+  gestures.begin_frame(0.6);
+  assert!(gestures.gestures().is_empty());
+  gestures.handle_input(&click(Action::Release));
+
+  // too slow for a double-click
+  gestures.handle_input(&click(Action::Press));
+  gestures.handle_input(&click(Action::Release));
+  gestures.begin_frame(0.5);
+  gestures.handle_input(&click(Action::Press));
+  assert!(!gestures.double_clicked(MouseButton::ButtonLeft));
+}
+
+#[test]
+fn gesture_drag() {
+  let mut gestures = GestureDetector::new(GestureConfig::default());
+  let motion = |x: f32, y: f32| Input::MouseMotion {
+    rel_x: 0.0,
+    rel_y: 0.0,
+    abs_x: x,
+    abs_y: y,
+  };
+  let button = MouseButton::ButtonLeft;
+
+  gestures.handle_input(&motion(10.0, 10.0));
+  gestures.handle_input(&Input::MouseButton(button, Action::Press, Modifier::empty()));
+  // below the threshold
+  gestures.handle_input(&motion(12.0, 10.0));
+  assert!(gestures.gestures().is_empty());
+
+  gestures.handle_input(&motion(20.0, 10.0));
+  gestures.handle_input(&motion(20.0, 15.0));
+  gestures.handle_input(&Input::MouseButton(button, Action::Release, Modifier::empty()));
+  assert_eq!(
+    gestures.gestures(),
+    &[
+      Gesture::DragStart {
+        button,
+        position: (10.0, 10.0)
+      },
+      Gesture::DragMove {
+        button,
+        position: (20.0, 10.0),
+        delta: (10.0, 0.0)
+      },
+      Gesture::DragMove {
+        button,
+        position: (20.0, 15.0),
+        delta: (0.0, 5.0)
+      },
+      Gesture::DragEnd {
+        button,
+        position: (20.0, 15.0),
+        delta: (10.0, 5.0)
+      },
+    ]
+  );
+}
+
+#[test]
+fn gesture_chord_and_sequence() {
+  use crate::Key;
+
+  let mut gestures = GestureDetector::new(GestureConfig::default());
+  gestures.add_chord(
+    "save_all",
+    ActionBinding::with_modifiers(Binding::Key(Key::S), Modifier::CONTROL | Modifier::SHIFT),
+  );
+  gestures.add_sequence(
+    "hadouken",
+    vec![Binding::Key(Key::Down), Binding::Key(Key::Right), Binding::Key(Key::J)],
+  );
+  let press = |key, modifier| Input::Key(key, 0, Action::Press, modifier);
+
+  gestures.handle_input(&press(Key::S, Modifier::CONTROL));
+  assert!(!gestures.chord("save_all"));
+  gestures.handle_input(&press(Key::S, Modifier::CONTROL | Modifier::SHIFT));
+  assert!(gestures.chord("save_all"));
+
+  for key in [Key::Down, Key::Right, Key::J] {
+    gestures.begin_frame(0.1);
+    gestures.handle_input(&press(key, Modifier::empty()));
+  }
+  assert!(gestures.sequence("hadouken"));
+
+  // too slow between steps
+  for key in [Key::Down, Key::Right, Key::J] {
+    gestures.begin_frame(0.4);
+    gestures.handle_input(&press(key, Modifier::empty()));
+  }
+  assert!(!gestures.sequence("hadouken"));
+}
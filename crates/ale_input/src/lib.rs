@@ -1,9 +1,15 @@
 use bitflags::bitflags;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+pub mod action;
+pub mod gesture;
+pub mod state;
+
+#[derive(Debug, PartialEq)]
 pub enum Input {
   Key(Key, Scancode, Action, Modifier),
   Char(char),
+  // abs_x and abs_y are logical units from the top left, rel_x and rel_y a fraction of the window size
   MouseMotion {
     rel_x: f32,
     rel_y: f32,
@@ -11,9 +17,33 @@ pub enum Input {
     abs_y: f32,
   },
   MouseButton(MouseButton, Action, Modifier),
+  // wheel or touchpad offset, y is positive when scrolling up
+  Scroll {
+    x: f32,
+    y: f32,
+  },
+  CursorEnter,
+  CursorLeave,
+  FocusGained,
+  FocusLost,
+  // new framebuffer size in pixels, never 0 (minimizing doesn't resize)
+  FramebufferResize {
+    width: u32,
+    height: u32,
+  },
+  // pixels per logical unit, changes when the window moves to a monitor with another scale
+  ContentScale {
+    x: f32,
+    y: f32,
+  },
+  GamepadButton(GamepadId, GamepadButton, Action),
+  // -1.0 to 1.0, triggers rest at -1.0
+  GamepadAxis(GamepadId, GamepadAxis, f32),
+  GamepadConnected(GamepadId),
+  GamepadDisconnected(GamepadId),
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
 pub enum Key {
   Space,
   Apostrophe,
@@ -148,6 +178,7 @@ pub enum Action {
 }
 
 bitflags! {
+  #[derive(Default)]
   pub struct Modifier : u32 {
     const SHIFT     = 0b00000001;
     const CONTROL   = 0b00000010;
@@ -158,7 +189,7 @@ bitflags! {
   }
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
 pub enum MouseButton {
   ButtonLeft,   // Button1
   ButtonRight,  // Button2
@@ -169,3 +200,97 @@ pub enum MouseButton {
   Button7,
   Button8,
 }
+
+pub type GamepadId = i32;
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub enum GamepadButton {
+  A,
+  B,
+  X,
+  Y,
+  LeftBumper,
+  RightBumper,
+  Back,
+  Start,
+  Guide,
+  LeftThumb,
+  RightThumb,
+  DpadUp,
+  DpadRight,
+  DpadDown,
+  DpadLeft,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub enum GamepadAxis {
+  LeftX,
+  LeftY,
+  RightX,
+  RightY,
+  LeftTrigger,
+  RightTrigger,
+}
+
+pub const GAMEPAD_BUTTONS: [GamepadButton; 15] = [
+  GamepadButton::A,
+  GamepadButton::B,
+  GamepadButton::X,
+  GamepadButton::Y,
+  GamepadButton::LeftBumper,
+  GamepadButton::RightBumper,
+  GamepadButton::Back,
+  GamepadButton::Start,
+  GamepadButton::Guide,
+  GamepadButton::LeftThumb,
+  GamepadButton::RightThumb,
+  GamepadButton::DpadUp,
+  GamepadButton::DpadRight,
+  GamepadButton::DpadDown,
+  GamepadButton::DpadLeft,
+];
+
+pub const GAMEPAD_AXES: [GamepadAxis; 6] = [
+  GamepadAxis::LeftX,
+  GamepadAxis::LeftY,
+  GamepadAxis::RightX,
+  GamepadAxis::RightY,
+  GamepadAxis::LeftTrigger,
+  GamepadAxis::RightTrigger,
+];
+
+// Polled state of a gamepad in the standard layout, indexed by GamepadButton / GamepadAxis
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct GamepadState {
+  pub buttons: [bool; 15],
+  pub axes: [f32; 6],
+}
+
+impl GamepadState {
+  pub fn is_down(&self, button: GamepadButton) -> bool {
+    self.buttons[button as usize]
+  }
+
+  pub fn axis(&self, axis: GamepadAxis) -> f32 {
+    self.axes[axis as usize]
+  }
+}
+
+impl Default for GamepadState {
+  // sticks centered, triggers released
+  fn default() -> GamepadState {
+    let mut axes = [0.0; 6];
+    axes[GamepadAxis::LeftTrigger as usize] = -1.0;
+    axes[GamepadAxis::RightTrigger as usize] = -1.0;
+    GamepadState {
+      buttons: [false; 15],
+      axes,
+    }
+  }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub enum MouseAxis {
+  X,
+  Y,
+}
@@ -0,0 +1,376 @@
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::{Action, GamepadAxis, GamepadButton, GamepadId, GamepadState, Input, Key, Modifier, MouseButton};
+
+// Down, pressed this frame and released this frame, for any kind of button
+struct ButtonStates<T: Hash + Eq + Copy> {
+  // button -> seconds held
+  down: HashMap<T, f32>,
+  just_pressed: HashSet<T>,
+  just_released: HashSet<T>,
+}
+
+impl<T: Hash + Eq + Copy> ButtonStates<T> {
+  fn new() -> ButtonStates<T> {
+    ButtonStates {
+      down: HashMap::new(),
+      just_pressed: HashSet::new(),
+      just_released: HashSet::new(),
+    }
+  }
+
+  fn begin_frame(&mut self, delta_time: f32) {
+    self.just_pressed.clear();
+    self.just_released.clear();
+    for held in self.down.values_mut() {
+      *held += delta_time;
+    }
+  }
+
+  fn handle(&mut self, button: T, action: Action) {
+    match action {
+      Action::Press => {
+        if let Entry::Vacant(entry) = self.down.entry(button) {
+          entry.insert(0.0);
+          self.just_pressed.insert(button);
+        }
+      }
+      Action::Release => {
+        if self.down.remove(&button).is_some() {
+          self.just_released.insert(button);
+        }
+      }
+      Action::Repeat => {}
+    }
+  }
+
+  fn release_all(&mut self) {
+    self.release_where(|_| true);
+  }
+
+  fn release_where<F: Fn(&T) -> bool>(&mut self, predicate: F) {
+    let released: Vec<T> = self.down.keys().filter(|button| predicate(button)).cloned().collect();
+    for button in released {
+      self.down.remove(&button);
+      self.just_released.insert(button);
+    }
+  }
+}
+
+// Snapshot of keyboard, mouse and gamepads built from the input events of every frame.
+// Call begin_frame once per frame before feeding that frame's inputs through handle_input.
+pub struct InputState {
+  keys: ButtonStates<Key>,
+  mouse_buttons: ButtonStates<MouseButton>,
+  gamepad_buttons: ButtonStates<(GamepadId, GamepadButton)>,
+  modifiers: Modifier,
+
+  // connected gamepads
+  gamepads: BTreeMap<GamepadId, GamepadState>,
+
+  // window coordinates, in logical units from the top left
+  mouse_position: Option<(f32, f32)>,
+  mouse_delta: (f32, f32),
+  scroll_delta: (f32, f32),
+  is_cursor_inside: bool,
+  is_focused: bool,
+}
+
+impl Default for InputState {
+  fn default() -> InputState {
+    InputState::new()
+  }
+}
+
+impl InputState {
+  pub fn new() -> InputState {
+    InputState {
+      keys: ButtonStates::new(),
+      mouse_buttons: ButtonStates::new(),
+      gamepad_buttons: ButtonStates::new(),
+      modifiers: Modifier::empty(),
+      gamepads: BTreeMap::new(),
+      mouse_position: None,
+      mouse_delta: (0.0, 0.0),
+      scroll_delta: (0.0, 0.0),
+      is_cursor_inside: false,
+      is_focused: true,
+    }
+  }
+
+  pub fn begin_frame(&mut self, delta_time: f32) {
+    self.keys.begin_frame(delta_time);
+    self.mouse_buttons.begin_frame(delta_time);
+    self.gamepad_buttons.begin_frame(delta_time);
+    self.mouse_delta = (0.0, 0.0);
+    self.scroll_delta = (0.0, 0.0);
+  }
+
+  pub fn handle_input(&mut self, input: &Input) {
+    match input {
+      Input::Key(key, _, action, modifier) => {
+        self.modifiers = *modifier;
+        self.keys.handle(*key, *action);
+      }
+      Input::MouseButton(mouse_button, action, modifier) => {
+        self.modifiers = *modifier;
+        self.mouse_buttons.handle(*mouse_button, *action);
+      }
+      Input::MouseMotion { abs_x, abs_y, .. } => {
+        if let Some((x, y)) = self.mouse_position {
+          self.mouse_delta.0 += abs_x - x;
+          self.mouse_delta.1 += abs_y - y;
+        }
+        self.mouse_position = Some((*abs_x, *abs_y));
+      }
+      Input::Scroll { x, y } => {
+        self.scroll_delta.0 += x;
+        self.scroll_delta.1 += y;
+      }
+      Input::CursorEnter => self.is_cursor_inside = true,
+      Input::CursorLeave => self.is_cursor_inside = false,
+      Input::FocusGained => self.is_focused = true,
+      // release events are lost while the window isn't focused
+      Input::FocusLost => {
+        self.is_focused = false;
+        self.release_all();
+      }
+      Input::GamepadConnected(id) => {
+        self.gamepads.insert(*id, GamepadState::default());
+      }
+      // the pad won't send the release events anymore
+      Input::GamepadDisconnected(id) => {
+        self.gamepads.remove(id);
+        self.gamepad_buttons.release_where(|(button_id, _)| button_id == id);
+      }
+      Input::GamepadButton(id, gamepad_button, action) => {
+        if let Some(gamepad) = self.gamepads.get_mut(id) {
+          gamepad.buttons[*gamepad_button as usize] = *action != Action::Release;
+        }
+        self.gamepad_buttons.handle((*id, *gamepad_button), *action);
+      }
+      Input::GamepadAxis(id, gamepad_axis, value) => {
+        if let Some(gamepad) = self.gamepads.get_mut(id) {
+          gamepad.axes[*gamepad_axis as usize] = *value;
+        }
+      }
+      _ => {}
+    }
+  }
+
+  // Releases every key, mouse and gamepad button, e.g. when the window loses focus and won't receive the release events
+  pub fn release_all(&mut self) {
+    self.keys.release_all();
+    self.mouse_buttons.release_all();
+    self.gamepad_buttons.release_all();
+    self.modifiers = Modifier::empty();
+  }
+
+  pub fn is_down(&self, key: Key) -> bool {
+    self.keys.down.contains_key(&key)
+  }
+
+  pub fn just_pressed(&self, key: Key) -> bool {
+    self.keys.just_pressed.contains(&key)
+  }
+
+  pub fn just_released(&self, key: Key) -> bool {
+    self.keys.just_released.contains(&key)
+  }
+
+  // Seconds the key has been held, 0 if it's not down
+  pub fn held_duration(&self, key: Key) -> f32 {
+    *self.keys.down.get(&key).unwrap_or(&0.0)
+  }
+
+  pub fn is_mouse_button_down(&self, mouse_button: MouseButton) -> bool {
+    self.mouse_buttons.down.contains_key(&mouse_button)
+  }
+
+  pub fn mouse_button_just_pressed(&self, mouse_button: MouseButton) -> bool {
+    self.mouse_buttons.just_pressed.contains(&mouse_button)
+  }
+
+  pub fn mouse_button_just_released(&self, mouse_button: MouseButton) -> bool {
+    self.mouse_buttons.just_released.contains(&mouse_button)
+  }
+
+  pub fn mouse_button_held_duration(&self, mouse_button: MouseButton) -> f32 {
+    *self.mouse_buttons.down.get(&mouse_button).unwrap_or(&0.0)
+  }
+
+  pub fn gamepad(&self, id: GamepadId) -> Option<&GamepadState> {
+    self.gamepads.get(&id)
+  }
+
+  // Connected gamepads, sorted by id
+  pub fn gamepad_ids(&self) -> Vec<GamepadId> {
+    self.gamepads.keys().cloned().collect()
+  }
+
+  pub fn gamepad_button_just_pressed(&self, id: GamepadId, gamepad_button: GamepadButton) -> bool {
+    self.gamepad_buttons.just_pressed.contains(&(id, gamepad_button))
+  }
+
+  pub fn gamepad_button_just_released(&self, id: GamepadId, gamepad_button: GamepadButton) -> bool {
+    self.gamepad_buttons.just_released.contains(&(id, gamepad_button))
+  }
+
+  // 0 for a disconnected gamepad
+  pub fn gamepad_axis(&self, id: GamepadId, gamepad_axis: GamepadAxis) -> f32 {
+    match self.gamepads.get(&id) {
+      None => 0.0,
+      Some(gamepad) => gamepad.axis(gamepad_axis),
+    }
+  }
+
+  pub fn modifiers(&self) -> Modifier {
+    self.modifiers
+  }
+
+  // None until the cursor has moved over the window once
+  pub fn mouse_position(&self) -> Option<(f32, f32)> {
+    self.mouse_position
+  }
+
+  // Movement in logical units this frame
+  pub fn mouse_delta(&self) -> (f32, f32) {
+    self.mouse_delta
+  }
+
+  // Scrolled this frame, y is positive when scrolling up
+  pub fn scroll_delta(&self) -> (f32, f32) {
+    self.scroll_delta
+  }
+
+  pub fn is_cursor_inside(&self) -> bool {
+    self.is_cursor_inside
+  }
+
+  pub fn is_focused(&self) -> bool {
+    self.is_focused
+  }
+}
+
+#[test]
+fn input_state_keys() {
+  let mut input_state = InputState::new();
+
+  input_state.begin_frame(0.016);
+  input_state.handle_input(&Input::Key(Key::Left, 0, Action::Press, Modifier::empty()));
+  assert!(input_state.is_down(Key::Left));
+  assert!(input_state.just_pressed(Key::Left));
+  assert_eq!(input_state.held_duration(Key::Left), 0.0);
+
+  input_state.begin_frame(0.5);
+  input_state.handle_input(&Input::Key(Key::Left, 0, Action::Repeat, Modifier::empty()));
+  assert!(input_state.is_down(Key::Left));
+  assert!(!input_state.just_pressed(Key::Left));
+  assert_eq!(input_state.held_duration(Key::Left), 0.5);
+
+  input_state.begin_frame(0.016);
+  input_state.handle_input(&Input::Key(Key::Left, 0, Action::Release, Modifier::empty()));
+  assert!(!input_state.is_down(Key::Left));
+  assert!(input_state.just_released(Key::Left));
+  assert_eq!(input_state.held_duration(Key::Left), 0.0);
+
+  input_state.begin_frame(0.016);
+  assert!(!input_state.just_released(Key::Left));
+}
+
+#[test]
+fn input_state_release_all_on_focus_loss() {
+  let mut input_state = InputState::new();
+  input_state.handle_input(&Input::Key(Key::A, 0, Action::Press, Modifier::SHIFT));
+  input_state.handle_input(&Input::MouseButton(MouseButton::ButtonLeft, Action::Press, Modifier::SHIFT));
+  input_state.handle_input(&Input::GamepadButton(0, GamepadButton::B, Action::Press));
+
+  input_state.begin_frame(0.016);
+  input_state.release_all();
+  assert!(input_state.gamepad_button_just_released(0, GamepadButton::B));
+  assert!(!input_state.is_down(Key::A));
+  assert!(input_state.just_released(Key::A));
+  assert!(!input_state.is_mouse_button_down(MouseButton::ButtonLeft));
+  assert!(input_state.mouse_button_just_released(MouseButton::ButtonLeft));
+  assert_eq!(input_state.modifiers(), Modifier::empty());
+}
+
+#[test]
+fn input_state_mouse() {
+  let mut input_state = InputState::new();
+  let motion = |x: f32, y: f32| Input::MouseMotion {
+    rel_x: 0.0,
+    rel_y: 0.0,
+    abs_x: x,
+    abs_y: y,
+  };
+
+  input_state.handle_input(&motion(10.0, 10.0));
+  assert_eq!(input_state.mouse_position(), Some((10.0, 10.0)));
+  assert_eq!(input_state.mouse_delta(), (0.0, 0.0));
+
+  input_state.begin_frame(0.016);
+  input_state.handle_input(&motion(15.0, 8.0));
+  input_state.handle_input(&motion(20.0, 6.0));
+  assert_eq!(input_state.mouse_delta(), (10.0, -4.0));
+
+  input_state.begin_frame(0.016);
+  assert_eq!(input_state.mouse_delta(), (0.0, 0.0));
+  assert_eq!(input_state.mouse_position(), Some((20.0, 6.0)));
+}
+
+#[test]
+fn input_state_gamepads() {
+  let mut input_state = InputState::new();
+  assert!(input_state.gamepad(1).is_none());
+
+  input_state.handle_input(&Input::GamepadConnected(1));
+  input_state.handle_input(&Input::GamepadButton(1, GamepadButton::A, Action::Press));
+  input_state.handle_input(&Input::GamepadAxis(1, GamepadAxis::LeftY, -0.75));
+  assert_eq!(input_state.gamepad_ids(), vec![1]);
+  assert!(input_state.gamepad(1).unwrap().is_down(GamepadButton::A));
+  assert!(input_state.gamepad_button_just_pressed(1, GamepadButton::A));
+  assert_eq!(input_state.gamepad_axis(1, GamepadAxis::LeftY), -0.75);
+  assert_eq!(input_state.gamepad_axis(1, GamepadAxis::LeftTrigger), -1.0);
+
+  input_state.begin_frame(0.016);
+  input_state.handle_input(&Input::GamepadButton(1, GamepadButton::A, Action::Release));
+  input_state.handle_input(&Input::GamepadDisconnected(1));
+  assert!(input_state.gamepad_button_just_released(1, GamepadButton::A));
+  assert!(input_state.gamepad(1).is_none());
+  assert_eq!(input_state.gamepad_axis(1, GamepadAxis::LeftY), 0.0);
+
+  // Unplugged while held, only that pad's buttons are released
+  input_state.handle_input(&Input::GamepadConnected(1));
+  input_state.handle_input(&Input::GamepadConnected(2));
+  input_state.handle_input(&Input::GamepadButton(1, GamepadButton::X, Action::Press));
+  input_state.handle_input(&Input::GamepadButton(2, GamepadButton::X, Action::Press));
+  input_state.begin_frame(0.016);
+  input_state.handle_input(&Input::GamepadDisconnected(1));
+  assert!(input_state.gamepad_button_just_released(1, GamepadButton::X));
+  assert!(!input_state.gamepad_button_just_released(2, GamepadButton::X));
+  input_state.handle_input(&Input::GamepadButton(1, GamepadButton::X, Action::Press));
+  assert!(input_state.gamepad_button_just_pressed(1, GamepadButton::X));
+}
+
+#[test]
+fn input_state_scroll_and_cursor() {
+  let mut input_state = InputState::new();
+  input_state.handle_input(&Input::CursorEnter);
+  input_state.handle_input(&Input::Scroll { x: 0.0, y: 1.0 });
+  input_state.handle_input(&Input::Scroll { x: 0.5, y: 2.0 });
+  assert!(input_state.is_cursor_inside());
+  assert_eq!(input_state.scroll_delta(), (0.5, 3.0));
+
+  input_state.begin_frame(0.016);
+  input_state.handle_input(&Input::CursorLeave);
+  assert!(!input_state.is_cursor_inside());
+  assert_eq!(input_state.scroll_delta(), (0.0, 0.0));
+
+  input_state.handle_input(&Input::Key(Key::W, 0, Action::Press, Modifier::empty()));
+  input_state.handle_input(&Input::FocusLost);
+  assert!(!input_state.is_focused());
+  assert!(!input_state.is_down(Key::W));
+}
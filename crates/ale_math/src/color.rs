@@ -1,5 +1,8 @@
+use cgmath::{Vector3, Vector4};
+
+// Channels are in 0..1, linear or sRGB is up to the caller, use to_linear/to_srgb to convert
 #[allow(dead_code)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Color {
   pub r: f32,
   pub g: f32,
@@ -28,6 +31,14 @@ impl Color {
     return Color::from_rgb(1.0, 1.0, 1.0);
   }
 
+  pub fn black() -> Color {
+    return Color::from_rgb(0.0, 0.0, 0.0);
+  }
+
+  pub fn transparent() -> Color {
+    return Color::from_rgba(0.0, 0.0, 0.0, 0.0);
+  }
+
   pub fn light_blue() -> Color { return Color::from_rgb(0.123f32, 0.54, 0.514); }
 
   pub fn from_rgb(r: f32, g: f32, b: f32) -> Color {
@@ -38,6 +49,15 @@ impl Color {
     Color { r, g, b, a }
   }
 
+  pub fn from_rgba8(r: u8, g: u8, b: u8, a: u8) -> Color {
+    Color::from_rgba(
+      r as f32 / 255.0,
+      g as f32 / 255.0,
+      b as f32 / 255.0,
+      a as f32 / 255.0,
+    )
+  }
+
   pub fn get_rgb(&self) -> (f32, f32, f32) {
     (self.r, self.g, self.b)
   }
@@ -45,4 +65,310 @@ impl Color {
   pub fn get_rgba(&self) -> (f32, f32, f32, f32) {
     (self.r, self.g, self.b, self.a)
   }
+
+  // Clamped and rounded to 0..255
+  pub fn get_rgba8(&self) -> (u8, u8, u8, u8) {
+    (
+      intern_to_u8(self.r),
+      intern_to_u8(self.g),
+      intern_to_u8(self.b),
+      intern_to_u8(self.a),
+    )
+  }
+
+  // Accepts #RGB, #RGBA, #RRGGBB and #RRGGBBAA, the # is optional
+  pub fn from_hex(hex: &str) -> Option<Color> {
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+    if !hex.is_ascii() {
+      return None;
+    }
+
+    let channel = |i: usize, width: usize| -> Option<u8> {
+      let value = u8::from_str_radix(&hex[i * width..(i + 1) * width], 16).ok()?;
+      // #RGB shorthand repeats each digit, f -> ff
+      Some(if width == 1 { value * 17 } else { value })
+    };
+
+    match hex.len() {
+      3 => Some(Color::from_rgba8(channel(0, 1)?, channel(1, 1)?, channel(2, 1)?, 255)),
+      4 => Some(Color::from_rgba8(channel(0, 1)?, channel(1, 1)?, channel(2, 1)?, channel(3, 1)?)),
+      6 => Some(Color::from_rgba8(channel(0, 2)?, channel(1, 2)?, channel(2, 2)?, 255)),
+      8 => Some(Color::from_rgba8(channel(0, 2)?, channel(1, 2)?, channel(2, 2)?, channel(3, 2)?)),
+      _ => None,
+    }
+  }
+
+  // Always #RRGGBBAA
+  pub fn to_hex(&self) -> String {
+    let (r, g, b, a) = self.get_rgba8();
+    format!("#{:02X}{:02X}{:02X}{:02X}", r, g, b, a)
+  }
+
+  // sRGB encoded to linear, alpha is untouched
+  pub fn to_linear(&self) -> Color {
+    Color::from_rgba(
+      intern_srgb_to_linear(self.r),
+      intern_srgb_to_linear(self.g),
+      intern_srgb_to_linear(self.b),
+      self.a,
+    )
+  }
+
+  // Linear to sRGB encoded, alpha is untouched
+  pub fn to_srgb(&self) -> Color {
+    Color::from_rgba(
+      intern_linear_to_srgb(self.r),
+      intern_linear_to_srgb(self.g),
+      intern_linear_to_srgb(self.b),
+      self.a,
+    )
+  }
+
+  // Hue in degrees, wraps around 360
+  pub fn from_hsv(hue: f32, saturation: f32, value: f32) -> Color {
+    let chroma = value * saturation;
+    let (r, g, b) = intern_hue_to_rgb(hue, chroma);
+    let m = value - chroma;
+    Color::from_rgb(r + m, g + m, b + m)
+  }
+
+  // (hue in degrees, saturation, value)
+  pub fn to_hsv(&self) -> (f32, f32, f32) {
+    let (max, _, chroma) = self.intern_max_min_chroma();
+    let saturation = if max > 0.0 { chroma / max } else { 0.0 };
+    (self.intern_hue(max, chroma), saturation, max)
+  }
+
+  // Hue in degrees, wraps around 360
+  pub fn from_hsl(hue: f32, saturation: f32, lightness: f32) -> Color {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let (r, g, b) = intern_hue_to_rgb(hue, chroma);
+    let m = lightness - chroma / 2.0;
+    Color::from_rgb(r + m, g + m, b + m)
+  }
+
+  // (hue in degrees, saturation, lightness)
+  pub fn to_hsl(&self) -> (f32, f32, f32) {
+    let (max, min, chroma) = self.intern_max_min_chroma();
+    let lightness = (max + min) / 2.0;
+    let saturation = if chroma > 0.0 {
+      chroma / (1.0 - (2.0 * lightness - 1.0).abs())
+    } else {
+      0.0
+    };
+    (self.intern_hue(max, chroma), saturation, lightness)
+  }
+
+  // Component wise, alpha included
+  pub fn lerp(&self, other: &Color, t: f32) -> Color {
+    Color::from_rgba(
+      self.r + (other.r - self.r) * t,
+      self.g + (other.g - self.g) * t,
+      self.b + (other.b - self.b) * t,
+      self.a + (other.a - self.a) * t,
+    )
+  }
+
+  pub fn premultiplied(&self) -> Color {
+    Color::from_rgba(self.r * self.a, self.g * self.a, self.b * self.a, self.a)
+  }
+
+  // A fully transparent color stays as is
+  pub fn unpremultiplied(&self) -> Color {
+    if self.a <= 0.0 {
+      return *self;
+    }
+    Color::from_rgba(self.r / self.a, self.g / self.a, self.b / self.a, self.a)
+  }
+
+  // Relative luminance (Rec. 709), expects a linear color
+  pub fn luminance(&self) -> f32 {
+    0.2126 * self.r + 0.7152 * self.g + 0.0722 * self.b
+  }
+
+  pub fn with_alpha(&self, a: f32) -> Color {
+    Color::from_rgba(self.r, self.g, self.b, a)
+  }
+
+  fn intern_max_min_chroma(&self) -> (f32, f32, f32) {
+    let max = self.r.max(self.g).max(self.b);
+    let min = self.r.min(self.g).min(self.b);
+    (max, min, max - min)
+  }
+
+  fn intern_hue(&self, max: f32, chroma: f32) -> f32 {
+    if chroma <= 0.0 {
+      return 0.0;
+    }
+    let hue = if max == self.r {
+      ((self.g - self.b) / chroma).rem_euclid(6.0)
+    } else if max == self.g {
+      (self.b - self.r) / chroma + 2.0
+    } else {
+      (self.r - self.g) / chroma + 4.0
+    };
+    hue * 60.0
+  }
+}
+
+impl From<Vector3<f32>> for Color {
+  fn from(v: Vector3<f32>) -> Self {
+    Color::from_rgb(v.x, v.y, v.z)
+  }
+}
+
+impl From<Vector4<f32>> for Color {
+  fn from(v: Vector4<f32>) -> Self {
+    Color::from_rgba(v.x, v.y, v.z, v.w)
+  }
+}
+
+// Alpha is dropped
+impl From<Color> for Vector3<f32> {
+  fn from(c: Color) -> Self {
+    Vector3::new(c.r, c.g, c.b)
+  }
+}
+
+impl From<Color> for Vector4<f32> {
+  fn from(c: Color) -> Self {
+    Vector4::new(c.r, c.g, c.b, c.a)
+  }
+}
+
+// Color stops sorted by position, sampled with linear interpolation
+#[derive(Debug, Clone)]
+pub struct Gradient {
+  stops: Vec<(f32, Color)>,
+}
+
+impl Gradient {
+  pub fn new(mut stops: Vec<(f32, Color)>) -> Gradient {
+    stops.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    Gradient { stops }
+  }
+
+  pub fn add_stop(&mut self, position: f32, color: Color) {
+    let index = self.stops.iter().position(|(p, _)| *p > position).unwrap_or(self.stops.len());
+    self.stops.insert(index, (position, color));
+  }
+
+  pub fn stops(&self) -> &[(f32, Color)] {
+    &self.stops
+  }
+
+  // Clamps to the first and last stop, transparent when there's no stop
+  pub fn sample(&self, position: f32) -> Color {
+    let (first, last) = match (self.stops.first(), self.stops.last()) {
+      (Some(first), Some(last)) => (first, last),
+      _ => return Color::transparent(),
+    };
+    if position <= first.0 {
+      return first.1;
+    }
+    if position >= last.0 {
+      return last.1;
+    }
+
+    for pair in self.stops.windows(2) {
+      let ((p0, c0), (p1, c1)) = (pair[0], pair[1]);
+      if position <= p1 {
+        let t = if p1 > p0 { (position - p0) / (p1 - p0) } else { 1.0 };
+        return c0.lerp(&c1, t);
+      }
+    }
+    last.1
+  }
+}
+
+fn intern_to_u8(value: f32) -> u8 {
+  (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+fn intern_srgb_to_linear(value: f32) -> f32 {
+  if value <= 0.04045 {
+    value / 12.92
+  } else {
+    ((value + 0.055) / 1.055).powf(2.4)
+  }
+}
+
+fn intern_linear_to_srgb(value: f32) -> f32 {
+  if value <= 0.0031308 {
+    value * 12.92
+  } else {
+    1.055 * value.powf(1.0 / 2.4) - 0.055
+  }
+}
+
+// rgb of a hue with the given chroma, before adding the lightness/value offset
+fn intern_hue_to_rgb(hue: f32, chroma: f32) -> (f32, f32, f32) {
+  let sector = hue.rem_euclid(360.0) / 60.0;
+  let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+  match sector as u32 {
+    0 => (chroma, x, 0.0),
+    1 => (x, chroma, 0.0),
+    2 => (0.0, chroma, x),
+    3 => (0.0, x, chroma),
+    4 => (x, 0.0, chroma),
+    _ => (chroma, 0.0, x),
+  }
+}
+
+#[test]
+fn color_hex_roundtrip() {
+  assert_eq!(Color::from_hex("#FF8000"), Some(Color::from_rgba8(255, 128, 0, 255)));
+  assert_eq!(Color::from_hex("ff800080").unwrap().to_hex(), "#FF800080");
+  assert_eq!(Color::from_hex("#f00"), Some(Color::red()));
+  assert_eq!(Color::from_hex("#0000"), Some(Color::transparent()));
+  assert_eq!(Color::from_hex("#12345"), None);
+  assert_eq!(Color::from_hex("#GG0000"), None);
+  assert_eq!(Color::white().to_hex(), "#FFFFFFFF");
+}
+
+#[test]
+fn color_srgb_linear_and_luminance() {
+  let color = Color::from_rgba(0.5, 0.0, 1.0, 0.25);
+  let linear = color.to_linear();
+  assert!((linear.r - 0.214).abs() < 0.001);
+  assert_eq!(linear.a, 0.25);
+  let back = linear.to_srgb();
+  assert!((back.r - 0.5).abs() < 0.0001 && (back.b - 1.0).abs() < 0.0001);
+
+  assert!((Color::white().luminance() - 1.0).abs() < 0.0001);
+  assert!(Color::green().luminance() > Color::red().luminance());
+}
+
+#[test]
+fn color_hsv_hsl() {
+  assert_eq!(Color::from_hsv(0.0, 1.0, 1.0), Color::red());
+  assert_eq!(Color::from_hsv(480.0, 1.0, 1.0), Color::green());
+  assert_eq!(Color::from_hsl(240.0, 1.0, 0.5), Color::blue());
+
+  let (h, s, v) = Color::yellow().to_hsv();
+  assert_eq!((h, s, v), (60.0, 1.0, 1.0));
+  let (h, s, l) = Color::from_rgb(0.25, 0.5, 0.75).to_hsl();
+  assert!((h - 210.0).abs() < 0.001 && (s - 0.5).abs() < 0.001 && (l - 0.5).abs() < 0.001);
+  assert_eq!(Color::from_rgb(0.5, 0.5, 0.5).to_hsv(), (0.0, 0.0, 0.5));
+}
+
+#[test]
+fn color_lerp_gradient_and_premultiply() {
+  assert_eq!(Color::black().lerp(&Color::white(), 0.5), Color::from_rgb(0.5, 0.5, 0.5));
+
+  let mut gradient = Gradient::new(vec![(1.0, Color::blue()), (0.0, Color::red())]);
+  gradient.add_stop(0.5, Color::green());
+  assert_eq!(gradient.sample(-1.0), Color::red());
+  assert_eq!(gradient.sample(0.25), Color::from_rgb(0.5, 0.5, 0.0));
+  assert_eq!(gradient.sample(0.5), Color::green());
+  assert_eq!(gradient.sample(2.0), Color::blue());
+  assert_eq!(Gradient::new(vec![]).sample(0.5), Color::transparent());
+
+  let color = Color::from_rgba(1.0, 0.5, 0.0, 0.5);
+  assert_eq!(color.premultiplied(), Color::from_rgba(0.5, 0.25, 0.0, 0.5));
+  assert_eq!(color.premultiplied().unpremultiplied(), color);
+
+  let v: Vector4<f32> = color.into();
+  assert_eq!(Color::from(v), color);
+  assert_eq!(Vector3::from(color), Vector3::new(1.0, 0.5, 0.0));
 }
@@ -0,0 +1,553 @@
+use cgmath::InnerSpace;
+use std::f32::consts::PI;
+
+// A curve over Vector2 or Vector3, t goes from 0 at the start to 1 at the end of the whole curve
+pub trait Curve<V: InnerSpace<Scalar = f32>> {
+  fn point(&self, t: f32) -> V;
+
+  // Derivative along t, not normalized
+  fn derivative(&self, t: f32) -> V;
+
+  fn tangent(&self, t: f32) -> V {
+    let derivative = self.derivative(t);
+    if derivative.magnitude2() > 0.0 {
+      derivative.normalize()
+    } else {
+      derivative
+    }
+  }
+
+  // Approximated with straight segments, see ArcLength to reuse the table
+  fn length(&self, segments: usize) -> f32 {
+    ArcLength::new(self, segments).length()
+  }
+
+  fn point_at_distance(&self, arc_length: &ArcLength, distance: f32) -> V {
+    self.point(arc_length.t_at_distance(distance))
+  }
+
+  // Checks samples evenly spaced points then refines around the closest one
+  fn nearest_t(&self, point: V, samples: usize) -> f32 {
+    let samples = samples.max(1);
+    let distance2 = |t: f32| (self.point(t) - point).magnitude2();
+
+    let mut nearest = 0;
+    let mut nearest_distance2 = f32::MAX;
+    for i in 0..=samples {
+      let d = distance2(i as f32 / samples as f32);
+      if d < nearest_distance2 {
+        nearest = i;
+        nearest_distance2 = d;
+      }
+    }
+
+    // Ternary search, the distance is assumed to have a single minimum between the neighbours
+    let step = 1.0 / samples as f32;
+    let mut low = (nearest as f32 - 1.0).max(0.0) * step;
+    let mut high = ((nearest + 1) as f32 * step).min(1.0);
+    for _ in 0..32 {
+      let a = low + (high - low) / 3.0;
+      let b = high - (high - low) / 3.0;
+      if distance2(a) < distance2(b) {
+        high = b;
+      } else {
+        low = a;
+      }
+    }
+    (low + high) / 2.0
+  }
+
+  fn nearest_point(&self, point: V, samples: usize) -> V {
+    self.point(self.nearest_t(point, samples))
+  }
+}
+
+// Cumulative lengths at evenly spaced t, used to move along a curve at constant speed
+#[derive(Debug, Clone)]
+pub struct ArcLength {
+  lengths: Vec<f32>,
+}
+
+impl ArcLength {
+  pub fn new<V: InnerSpace<Scalar = f32>, C: Curve<V> + ?Sized>(curve: &C, segments: usize) -> ArcLength {
+    let segments = segments.max(1);
+    let mut lengths = Vec::with_capacity(segments + 1);
+    lengths.push(0.0);
+
+    let mut previous = curve.point(0.0);
+    let mut length = 0.0;
+    for i in 1..=segments {
+      let current = curve.point(i as f32 / segments as f32);
+      length += (current - previous).magnitude();
+      lengths.push(length);
+      previous = current;
+    }
+
+    ArcLength { lengths }
+  }
+
+  pub fn length(&self) -> f32 {
+    *self.lengths.last().unwrap()
+  }
+
+  // Clamped to the curve
+  pub fn t_at_distance(&self, distance: f32) -> f32 {
+    let segments = (self.lengths.len() - 1) as f32;
+    if distance <= 0.0 {
+      return 0.0;
+    }
+    if distance >= self.length() {
+      return 1.0;
+    }
+
+    let i = match self.lengths.binary_search_by(|l| l.partial_cmp(&distance).unwrap()) {
+      Ok(i) => return i as f32 / segments,
+      Err(i) => i - 1,
+    };
+    let (start, end) = (self.lengths[i], self.lengths[i + 1]);
+    let fraction = if end > start { (distance - start) / (end - start) } else { 0.0 };
+    (i as f32 + fraction) / segments
+  }
+
+  pub fn distance_at_t(&self, t: f32) -> f32 {
+    let (i, fraction) = intern_segment(t, self.lengths.len() - 1);
+    self.lengths[i] + (self.lengths[i + 1] - self.lengths[i]) * fraction
+  }
+}
+
+// Straight lines between the points
+#[derive(Debug, Clone)]
+pub struct Linear<V> {
+  pub points: Vec<V>,
+}
+
+impl<V: InnerSpace<Scalar = f32>> Linear<V> {
+  pub fn new(points: Vec<V>) -> Linear<V> {
+    Linear { points }
+  }
+}
+
+impl<V: InnerSpace<Scalar = f32>> Curve<V> for Linear<V> {
+  fn point(&self, t: f32) -> V {
+    match self.points.len() {
+      0 => V::zero(),
+      1 => self.points[0],
+      n => {
+        let (i, u) = intern_segment(t, n - 1);
+        self.points[i].lerp(self.points[i + 1], u)
+      }
+    }
+  }
+
+  fn derivative(&self, t: f32) -> V {
+    match self.points.len() {
+      0 | 1 => V::zero(),
+      n => {
+        let (i, _) = intern_segment(t, n - 1);
+        (self.points[i + 1] - self.points[i]) * (n - 1) as f32
+      }
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct QuadraticBezier<V> {
+  pub p0: V,
+  pub p1: V,
+  pub p2: V,
+}
+
+impl<V: InnerSpace<Scalar = f32>> QuadraticBezier<V> {
+  pub fn new(p0: V, p1: V, p2: V) -> QuadraticBezier<V> {
+    QuadraticBezier { p0, p1, p2 }
+  }
+}
+
+impl<V: InnerSpace<Scalar = f32>> Curve<V> for QuadraticBezier<V> {
+  fn point(&self, t: f32) -> V {
+    let t = intern_clamp01(t);
+    let u = 1.0 - t;
+    self.p0 * (u * u) + self.p1 * (2.0 * u * t) + self.p2 * (t * t)
+  }
+
+  fn derivative(&self, t: f32) -> V {
+    let t = intern_clamp01(t);
+    (self.p1 - self.p0) * (2.0 * (1.0 - t)) + (self.p2 - self.p1) * (2.0 * t)
+  }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct CubicBezier<V> {
+  pub p0: V,
+  pub p1: V,
+  pub p2: V,
+  pub p3: V,
+}
+
+impl<V: InnerSpace<Scalar = f32>> CubicBezier<V> {
+  pub fn new(p0: V, p1: V, p2: V, p3: V) -> CubicBezier<V> {
+    CubicBezier { p0, p1, p2, p3 }
+  }
+}
+
+impl<V: InnerSpace<Scalar = f32>> Curve<V> for CubicBezier<V> {
+  fn point(&self, t: f32) -> V {
+    let t = intern_clamp01(t);
+    let u = 1.0 - t;
+    self.p0 * (u * u * u) + self.p1 * (3.0 * u * u * t) + self.p2 * (3.0 * u * t * t) + self.p3 * (t * t * t)
+  }
+
+  fn derivative(&self, t: f32) -> V {
+    let t = intern_clamp01(t);
+    let u = 1.0 - t;
+    (self.p1 - self.p0) * (3.0 * u * u) + (self.p2 - self.p1) * (6.0 * u * t) + (self.p3 - self.p2) * (3.0 * t * t)
+  }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct HermiteKey<V> {
+  pub position: V,
+  // Per segment, a segment goes from one key to the next
+  pub tangent: V,
+}
+
+// Passes through every key with the given tangents
+#[derive(Debug, Clone)]
+pub struct Hermite<V> {
+  pub keys: Vec<HermiteKey<V>>,
+}
+
+impl<V: InnerSpace<Scalar = f32>> Hermite<V> {
+  pub fn new(keys: Vec<HermiteKey<V>>) -> Hermite<V> {
+    Hermite { keys }
+  }
+}
+
+impl<V: InnerSpace<Scalar = f32>> Curve<V> for Hermite<V> {
+  fn point(&self, t: f32) -> V {
+    match self.keys.len() {
+      0 => V::zero(),
+      1 => self.keys[0].position,
+      n => {
+        let (i, u) = intern_segment(t, n - 1);
+        let (a, b) = (&self.keys[i], &self.keys[i + 1]);
+        intern_hermite_point(a.position, a.tangent, b.position, b.tangent, u)
+      }
+    }
+  }
+
+  fn derivative(&self, t: f32) -> V {
+    match self.keys.len() {
+      0 | 1 => V::zero(),
+      n => {
+        let (i, u) = intern_segment(t, n - 1);
+        let (a, b) = (&self.keys[i], &self.keys[i + 1]);
+        intern_hermite_derivative(a.position, a.tangent, b.position, b.tangent, u) * (n - 1) as f32
+      }
+    }
+  }
+}
+
+// Uniform Catmull-Rom, passes through every point. The end points are repeated unless looped,
+// a looped spline also connects the last point back to the first.
+#[derive(Debug, Clone)]
+pub struct CatmullRom<V> {
+  pub points: Vec<V>,
+  pub looped: bool,
+}
+
+impl<V: InnerSpace<Scalar = f32>> CatmullRom<V> {
+  pub fn new(points: Vec<V>, looped: bool) -> CatmullRom<V> {
+    CatmullRom { points, looped }
+  }
+
+  fn segment_count(&self) -> usize {
+    if self.looped {
+      self.points.len()
+    } else {
+      self.points.len() - 1
+    }
+  }
+
+  fn point_at(&self, i: isize) -> V {
+    let n = self.points.len() as isize;
+    let i = if self.looped {
+      i.rem_euclid(n)
+    } else {
+      i.max(0).min(n - 1)
+    };
+    self.points[i as usize]
+  }
+
+  // (p1, m1, p2, m2) of segment i as hermite
+  fn segment(&self, i: usize) -> (V, V, V, V) {
+    let i = i as isize;
+    let (p0, p1, p2, p3) = (self.point_at(i - 1), self.point_at(i), self.point_at(i + 1), self.point_at(i + 2));
+    (p1, (p2 - p0) * 0.5, p2, (p3 - p1) * 0.5)
+  }
+}
+
+impl<V: InnerSpace<Scalar = f32>> Curve<V> for CatmullRom<V> {
+  fn point(&self, t: f32) -> V {
+    match self.points.len() {
+      0 => V::zero(),
+      1 => self.points[0],
+      _ => {
+        let (i, u) = intern_segment(t, self.segment_count());
+        let (p1, m1, p2, m2) = self.segment(i);
+        intern_hermite_point(p1, m1, p2, m2, u)
+      }
+    }
+  }
+
+  fn derivative(&self, t: f32) -> V {
+    match self.points.len() {
+      0 | 1 => V::zero(),
+      _ => {
+        let (i, u) = intern_segment(t, self.segment_count());
+        let (p1, m1, p2, m2) = self.segment(i);
+        intern_hermite_derivative(p1, m1, p2, m2, u) * self.segment_count() as f32
+      }
+    }
+  }
+}
+
+// Remaps t in 0..1, use with lerp or a curve to shape motion
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Easing {
+  Linear,
+  QuadIn,
+  QuadOut,
+  QuadInOut,
+  CubicIn,
+  CubicOut,
+  CubicInOut,
+  // Overshoots below 0 or above 1
+  BackIn,
+  BackOut,
+  BackInOut,
+  // Overshoots below 0 or above 1
+  ElasticIn,
+  ElasticOut,
+  ElasticInOut,
+  BounceIn,
+  BounceOut,
+  BounceInOut,
+}
+
+impl Easing {
+  // t is clamped to 0..1
+  pub fn apply(&self, t: f32) -> f32 {
+    let t = intern_clamp01(t);
+    match self {
+      Easing::Linear => t,
+      Easing::QuadIn => t * t,
+      Easing::QuadOut => 1.0 - (1.0 - t) * (1.0 - t),
+      Easing::QuadInOut => {
+        if t < 0.5 {
+          2.0 * t * t
+        } else {
+          1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+        }
+      }
+      Easing::CubicIn => t * t * t,
+      Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+      Easing::CubicInOut => {
+        if t < 0.5 {
+          4.0 * t * t * t
+        } else {
+          1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+        }
+      }
+      Easing::BackIn => BACK_C3 * t * t * t - BACK_C1 * t * t,
+      Easing::BackOut => 1.0 + BACK_C3 * (t - 1.0).powi(3) + BACK_C1 * (t - 1.0).powi(2),
+      Easing::BackInOut => {
+        let c2 = BACK_C1 * 1.525;
+        if t < 0.5 {
+          (2.0 * t).powi(2) * ((c2 + 1.0) * 2.0 * t - c2) / 2.0
+        } else {
+          ((2.0 * t - 2.0).powi(2) * ((c2 + 1.0) * (2.0 * t - 2.0) + c2) + 2.0) / 2.0
+        }
+      }
+      Easing::ElasticIn => {
+        if t <= 0.0 || t >= 1.0 {
+          return t;
+        }
+        -(2.0f32).powf(10.0 * t - 10.0) * ((10.0 * t - 10.75) * ELASTIC_C4).sin()
+      }
+      Easing::ElasticOut => {
+        if t <= 0.0 || t >= 1.0 {
+          return t;
+        }
+        (2.0f32).powf(-10.0 * t) * ((10.0 * t - 0.75) * ELASTIC_C4).sin() + 1.0
+      }
+      Easing::ElasticInOut => {
+        if t <= 0.0 || t >= 1.0 {
+          return t;
+        }
+        let c5 = (2.0 * PI) / 4.5;
+        if t < 0.5 {
+          -((2.0f32).powf(20.0 * t - 10.0) * ((20.0 * t - 11.125) * c5).sin()) / 2.0
+        } else {
+          (2.0f32).powf(-20.0 * t + 10.0) * ((20.0 * t - 11.125) * c5).sin() / 2.0 + 1.0
+        }
+      }
+      Easing::BounceIn => 1.0 - intern_bounce_out(1.0 - t),
+      Easing::BounceOut => intern_bounce_out(t),
+      Easing::BounceInOut => {
+        if t < 0.5 {
+          (1.0 - intern_bounce_out(1.0 - 2.0 * t)) / 2.0
+        } else {
+          (1.0 + intern_bounce_out(2.0 * t - 1.0)) / 2.0
+        }
+      }
+    }
+  }
+}
+
+const BACK_C1: f32 = 1.70158;
+const BACK_C3: f32 = BACK_C1 + 1.0;
+const ELASTIC_C4: f32 = (2.0 * PI) / 3.0;
+
+fn intern_bounce_out(t: f32) -> f32 {
+  let (n1, d1) = (7.5625, 2.75);
+  if t < 1.0 / d1 {
+    n1 * t * t
+  } else if t < 2.0 / d1 {
+    let t = t - 1.5 / d1;
+    n1 * t * t + 0.75
+  } else if t < 2.5 / d1 {
+    let t = t - 2.25 / d1;
+    n1 * t * t + 0.9375
+  } else {
+    let t = t - 2.625 / d1;
+    n1 * t * t + 0.984375
+  }
+}
+
+fn intern_clamp01(t: f32) -> f32 {
+  t.clamp(0.0, 1.0)
+}
+
+// (segment index, t inside the segment) for a curve made of count segments, count > 0
+fn intern_segment(t: f32, count: usize) -> (usize, f32) {
+  let scaled = intern_clamp01(t) * count as f32;
+  let i = (scaled as usize).min(count - 1);
+  (i, scaled - i as f32)
+}
+
+fn intern_hermite_point<V: InnerSpace<Scalar = f32>>(p0: V, m0: V, p1: V, m1: V, u: f32) -> V {
+  let (u2, u3) = (u * u, u * u * u);
+  p0 * (2.0 * u3 - 3.0 * u2 + 1.0) + m0 * (u3 - 2.0 * u2 + u) + p1 * (-2.0 * u3 + 3.0 * u2) + m1 * (u3 - u2)
+}
+
+fn intern_hermite_derivative<V: InnerSpace<Scalar = f32>>(p0: V, m0: V, p1: V, m1: V, u: f32) -> V {
+  let u2 = u * u;
+  p0 * (6.0 * u2 - 6.0 * u) + m0 * (3.0 * u2 - 4.0 * u + 1.0) + p1 * (-6.0 * u2 + 6.0 * u) + m1 * (3.0 * u2 - 2.0 * u)
+}
+
+#[test]
+fn curve_bezier_and_linear() {
+  use cgmath::Vector2;
+
+  let line = Linear::new(vec![Vector2::new(0.0f32, 0.0), Vector2::new(10.0, 0.0), Vector2::new(10.0, 10.0)]);
+  assert_eq!(line.point(0.25), Vector2::new(5.0, 0.0));
+  assert_eq!(line.point(0.75), Vector2::new(10.0, 5.0));
+  assert_eq!(line.tangent(0.75), Vector2::new(0.0, 1.0));
+  assert!((line.length(8) - 20.0).abs() < 0.001);
+
+  let quadratic = QuadraticBezier::new(Vector2::new(0.0f32, 0.0), Vector2::new(1.0, 2.0), Vector2::new(2.0, 0.0));
+  assert_eq!(quadratic.point(0.5), Vector2::new(1.0, 1.0));
+  assert_eq!(quadratic.tangent(0.5), Vector2::new(1.0, 0.0));
+
+  let cubic = CubicBezier::new(
+    Vector2::new(0.0f32, 0.0),
+    Vector2::new(0.0, 1.0),
+    Vector2::new(1.0, 1.0),
+    Vector2::new(1.0, 0.0),
+  );
+  assert_eq!(cubic.point(0.0), Vector2::new(0.0, 0.0));
+  assert_eq!(cubic.point(0.5), Vector2::new(0.5, 0.75));
+  assert_eq!(cubic.derivative(0.0), Vector2::new(0.0, 3.0));
+}
+
+#[test]
+fn curve_splines_pass_through_points() {
+  use cgmath::Vector3;
+
+  let points = vec![
+    Vector3::new(0.0f32, 0.0, 0.0),
+    Vector3::new(1.0, 2.0, 0.0),
+    Vector3::new(3.0, 2.0, 1.0),
+    Vector3::new(4.0, 0.0, 0.0),
+  ];
+  let spline = CatmullRom::new(points.clone(), false);
+  for (i, p) in points.iter().enumerate() {
+    assert!((spline.point(i as f32 / 3.0) - p).magnitude() < 0.0001);
+  }
+  let looped = CatmullRom::new(points.clone(), true);
+  assert!((looped.point(1.0) - points[0]).magnitude() < 0.0001);
+  assert!((looped.point(0.5) - points[2]).magnitude() < 0.0001);
+
+  let hermite = Hermite::new(vec![
+    HermiteKey { position: Vector3::new(0.0f32, 0.0, 0.0), tangent: Vector3::new(1.0, 0.0, 0.0) },
+    HermiteKey { position: Vector3::new(1.0, 1.0, 0.0), tangent: Vector3::new(0.0, 1.0, 0.0) },
+  ]);
+  assert_eq!(hermite.point(1.0), Vector3::new(1.0, 1.0, 0.0));
+  assert_eq!(hermite.derivative(0.0), Vector3::new(1.0, 0.0, 0.0));
+}
+
+#[test]
+fn curve_arc_length_and_nearest() {
+  use cgmath::Vector2;
+
+  // Control points bunched at the start, t isn't proportional to distance
+  let cubic = CubicBezier::new(
+    Vector2::new(0.0f32, 0.0),
+    Vector2::new(0.0, 0.0),
+    Vector2::new(0.0, 0.0),
+    Vector2::new(9.0, 0.0),
+  );
+  let arc_length = ArcLength::new(&cubic, 256);
+  assert!((arc_length.length() - 9.0).abs() < 0.001);
+  let halfway = cubic.point_at_distance(&arc_length, 4.5);
+  assert!((halfway.x - 4.5).abs() < 0.01);
+  assert!((arc_length.distance_at_t(arc_length.t_at_distance(3.0)) - 3.0).abs() < 0.01);
+  assert_eq!(arc_length.t_at_distance(100.0), 1.0);
+
+  let quadratic = QuadraticBezier::new(Vector2::new(-1.0f32, 0.0), Vector2::new(0.0, 2.0), Vector2::new(1.0, 0.0));
+  let t = quadratic.nearest_t(Vector2::new(0.0, 5.0), 8);
+  assert!((t - 0.5).abs() < 0.001);
+  assert!((quadratic.nearest_point(Vector2::new(2.0, -1.0), 8) - Vector2::new(1.0, 0.0)).magnitude() < 0.001);
+}
+
+#[test]
+fn curve_easing() {
+  let easings = [
+    Easing::Linear,
+    Easing::QuadIn,
+    Easing::QuadOut,
+    Easing::QuadInOut,
+    Easing::CubicIn,
+    Easing::CubicOut,
+    Easing::CubicInOut,
+    Easing::BackIn,
+    Easing::BackOut,
+    Easing::BackInOut,
+    Easing::ElasticIn,
+    Easing::ElasticOut,
+    Easing::ElasticInOut,
+    Easing::BounceIn,
+    Easing::BounceOut,
+    Easing::BounceInOut,
+  ];
+  for easing in easings.iter() {
+    assert!(easing.apply(0.0).abs() < 0.0001, "{:?}", easing);
+    assert!((easing.apply(1.0) - 1.0).abs() < 0.0001, "{:?}", easing);
+  }
+  assert_eq!(Easing::QuadIn.apply(0.5), 0.25);
+  assert_eq!(Easing::CubicInOut.apply(0.5), 0.5);
+  assert!(Easing::BackIn.apply(0.2) < 0.0);
+  assert!(Easing::ElasticOut.apply(0.2) > 1.0);
+  assert_eq!(Easing::Linear.apply(2.0), 1.0);
+}
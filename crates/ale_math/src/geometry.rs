@@ -0,0 +1,770 @@
+use cgmath::prelude::*;
+use cgmath::{Matrix3, Matrix4, Vector3, Vector4};
+
+const EPSILON: f32 = 1e-6;
+
+fn transform_point(matrix: &Matrix4<f32>, point: Vector3<f32>) -> Vector3<f32> {
+  let p = matrix * point.extend(1.0);
+  p.truncate() / p.w
+}
+
+fn transform_vector(matrix: &Matrix4<f32>, vector: Vector3<f32>) -> Vector3<f32> {
+  (matrix * vector.extend(0.0)).truncate()
+}
+
+fn matrix3(matrix: &Matrix4<f32>) -> Matrix3<f32> {
+  Matrix3::from_cols(matrix.x.truncate(), matrix.y.truncate(), matrix.z.truncate())
+}
+
+// Axis aligned bounding box
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Aabb {
+  pub min: Vector3<f32>,
+  pub max: Vector3<f32>,
+}
+
+impl Aabb {
+  pub fn new(min: Vector3<f32>, max: Vector3<f32>) -> Aabb {
+    Aabb { min, max }
+  }
+
+  pub fn from_center_half_extents(center: Vector3<f32>, half_extents: Vector3<f32>) -> Aabb {
+    Aabb {
+      min: center - half_extents,
+      max: center + half_extents,
+    }
+  }
+
+  // None when there are no points
+  pub fn from_points(points: &[Vector3<f32>]) -> Option<Aabb> {
+    let (first, rest) = points.split_first()?;
+    Some(
+      rest
+        .iter()
+        .fold(Aabb::new(*first, *first), |aabb, point| aabb.merge_point(*point)),
+    )
+  }
+
+  pub fn center(&self) -> Vector3<f32> {
+    (self.min + self.max) / 2.0
+  }
+
+  pub fn size(&self) -> Vector3<f32> {
+    self.max - self.min
+  }
+
+  pub fn half_extents(&self) -> Vector3<f32> {
+    self.size() / 2.0
+  }
+
+  pub fn corners(&self) -> [Vector3<f32>; 8] {
+    let (min, max) = (self.min, self.max);
+    [
+      Vector3::new(min.x, min.y, min.z),
+      Vector3::new(max.x, min.y, min.z),
+      Vector3::new(min.x, max.y, min.z),
+      Vector3::new(max.x, max.y, min.z),
+      Vector3::new(min.x, min.y, max.z),
+      Vector3::new(max.x, min.y, max.z),
+      Vector3::new(min.x, max.y, max.z),
+      Vector3::new(max.x, max.y, max.z),
+    ]
+  }
+
+  pub fn merge(&self, other: &Aabb) -> Aabb {
+    Aabb {
+      min: Vector3::new(
+        self.min.x.min(other.min.x),
+        self.min.y.min(other.min.y),
+        self.min.z.min(other.min.z),
+      ),
+      max: Vector3::new(
+        self.max.x.max(other.max.x),
+        self.max.y.max(other.max.y),
+        self.max.z.max(other.max.z),
+      ),
+    }
+  }
+
+  pub fn merge_point(&self, point: Vector3<f32>) -> Aabb {
+    self.merge(&Aabb::new(point, point))
+  }
+
+  pub fn contains_point(&self, point: Vector3<f32>) -> bool {
+    point.x >= self.min.x
+      && point.x <= self.max.x
+      && point.y >= self.min.y
+      && point.y <= self.max.y
+      && point.z >= self.min.z
+      && point.z <= self.max.z
+  }
+
+  pub fn contains_aabb(&self, other: &Aabb) -> bool {
+    self.contains_point(other.min) && self.contains_point(other.max)
+  }
+
+  // Returns the point itself when it's inside
+  pub fn closest_point(&self, point: Vector3<f32>) -> Vector3<f32> {
+    Vector3::new(
+      point.x.max(self.min.x).min(self.max.x),
+      point.y.max(self.min.y).min(self.max.y),
+      point.z.max(self.min.z).min(self.max.z),
+    )
+  }
+
+  // Touching boxes intersect
+  pub fn intersects_aabb(&self, other: &Aabb) -> bool {
+    self.min.x <= other.max.x
+      && self.max.x >= other.min.x
+      && self.min.y <= other.max.y
+      && self.max.y >= other.min.y
+      && self.min.z <= other.max.z
+      && self.max.z >= other.min.z
+  }
+
+  pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+    (self.closest_point(sphere.center) - sphere.center).magnitude2() <= sphere.radius * sphere.radius
+  }
+
+  // The box around the transformed box, it grows under rotation
+  pub fn transform(&self, matrix: &Matrix4<f32>) -> Aabb {
+    let translation = matrix.w.truncate();
+    let mut min = translation;
+    let mut max = translation;
+    for row in 0..3 {
+      for col in 0..3 {
+        let a = matrix[col][row] * self.min[col];
+        let b = matrix[col][row] * self.max[col];
+        min[row] += a.min(b);
+        max[row] += a.max(b);
+      }
+    }
+    Aabb { min, max }
+  }
+
+  pub fn bounding_sphere(&self) -> Sphere {
+    Sphere::new(self.center(), self.half_extents().magnitude())
+  }
+}
+
+impl From<(Vector3<f32>, Vector3<f32>)> for Aabb {
+  fn from((min, max): (Vector3<f32>, Vector3<f32>)) -> Aabb {
+    Aabb { min, max }
+  }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Sphere {
+  pub center: Vector3<f32>,
+  pub radius: f32,
+}
+
+impl Sphere {
+  pub fn new(center: Vector3<f32>, radius: f32) -> Sphere {
+    Sphere { center, radius }
+  }
+
+  // Smallest sphere enclosing both
+  pub fn merge(&self, other: &Sphere) -> Sphere {
+    let offset = other.center - self.center;
+    let distance = offset.magnitude();
+    if distance + other.radius <= self.radius {
+      return *self;
+    }
+    if distance + self.radius <= other.radius {
+      return *other;
+    }
+    let radius = (distance + self.radius + other.radius) / 2.0;
+    Sphere {
+      center: self.center + offset * ((radius - self.radius) / distance),
+      radius,
+    }
+  }
+
+  pub fn contains_point(&self, point: Vector3<f32>) -> bool {
+    (point - self.center).magnitude2() <= self.radius * self.radius
+  }
+
+  pub fn contains_sphere(&self, other: &Sphere) -> bool {
+    (other.center - self.center).magnitude() + other.radius <= self.radius
+  }
+
+  // On the surface, or the point itself when it's inside
+  pub fn closest_point(&self, point: Vector3<f32>) -> Vector3<f32> {
+    let offset = point - self.center;
+    if offset.magnitude2() <= self.radius * self.radius {
+      return point;
+    }
+    self.center + offset.normalize() * self.radius
+  }
+
+  pub fn intersects_sphere(&self, other: &Sphere) -> bool {
+    let radius = self.radius + other.radius;
+    (other.center - self.center).magnitude2() <= radius * radius
+  }
+
+  // Non uniform scales take the largest axis so the result still encloses the shape
+  pub fn transform(&self, matrix: &Matrix4<f32>) -> Sphere {
+    let scale = matrix
+      .x
+      .truncate()
+      .magnitude()
+      .max(matrix.y.truncate().magnitude())
+      .max(matrix.z.truncate().magnitude());
+    Sphere {
+      center: transform_point(matrix, self.center),
+      radius: self.radius * scale,
+    }
+  }
+
+  pub fn aabb(&self) -> Aabb {
+    Aabb::from_center_half_extents(self.center, Vector3::from_value(self.radius))
+  }
+}
+
+// Points where normal.dot(point) + distance == 0, positive in front of the normal
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Plane {
+  pub normal: Vector3<f32>,
+  pub distance: f32,
+}
+
+impl Plane {
+  // normal doesn't have to be normalized
+  pub fn new(normal: Vector3<f32>, distance: f32) -> Plane {
+    let magnitude = normal.magnitude();
+    Plane {
+      normal: normal / magnitude,
+      distance: distance / magnitude,
+    }
+  }
+
+  pub fn from_point_normal(point: Vector3<f32>, normal: Vector3<f32>) -> Plane {
+    let normal = normal.normalize();
+    Plane {
+      normal,
+      distance: -normal.dot(point),
+    }
+  }
+
+  // Counter clockwise points face the normal
+  pub fn from_points(a: Vector3<f32>, b: Vector3<f32>, c: Vector3<f32>) -> Plane {
+    Plane::from_point_normal(a, (b - a).cross(c - a))
+  }
+
+  // (a, b, c, d) of ax + by + cz + d = 0
+  pub fn from_vector4(plane: Vector4<f32>) -> Plane {
+    Plane::new(plane.truncate(), plane.w)
+  }
+
+  pub fn signed_distance(&self, point: Vector3<f32>) -> f32 {
+    self.normal.dot(point) + self.distance
+  }
+
+  pub fn closest_point(&self, point: Vector3<f32>) -> Vector3<f32> {
+    point - self.normal * self.signed_distance(point)
+  }
+
+  // None when the matrix can't be inverted
+  pub fn transform(&self, matrix: &Matrix4<f32>) -> Option<Plane> {
+    let inverse_transpose = matrix.invert()?.transpose();
+    Some(Plane::from_vector4(inverse_transpose * self.normal.extend(self.distance)))
+  }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Ray {
+  pub origin: Vector3<f32>,
+  // always normalized, hits are distances along it
+  pub direction: Vector3<f32>,
+}
+
+impl Ray {
+  pub fn new(origin: Vector3<f32>, direction: Vector3<f32>) -> Ray {
+    Ray {
+      origin,
+      direction: direction.normalize(),
+    }
+  }
+
+  pub fn at(&self, t: f32) -> Vector3<f32> {
+    self.origin + self.direction * t
+  }
+
+  pub fn closest_point(&self, point: Vector3<f32>) -> Vector3<f32> {
+    self.at((point - self.origin).dot(self.direction).max(0.0))
+  }
+
+  // Distance to the entry point, 0.0 when the origin is inside
+  pub fn intersect_aabb(&self, aabb: &Aabb) -> Option<f32> {
+    let mut t_min = 0.0f32;
+    let mut t_max = f32::INFINITY;
+    for axis in 0..3 {
+      if self.direction[axis].abs() < EPSILON {
+        if self.origin[axis] < aabb.min[axis] || self.origin[axis] > aabb.max[axis] {
+          return None;
+        }
+        continue;
+      }
+      let inverse = 1.0 / self.direction[axis];
+      let mut t0 = (aabb.min[axis] - self.origin[axis]) * inverse;
+      let mut t1 = (aabb.max[axis] - self.origin[axis]) * inverse;
+      if t0 > t1 {
+        std::mem::swap(&mut t0, &mut t1);
+      }
+      t_min = t_min.max(t0);
+      t_max = t_max.min(t1);
+      if t_max < t_min {
+        return None;
+      }
+    }
+    Some(t_min)
+  }
+
+  // Distance to the entry point, 0.0 when the origin is inside
+  pub fn intersect_sphere(&self, sphere: &Sphere) -> Option<f32> {
+    let offset = self.origin - sphere.center;
+    let b = offset.dot(self.direction);
+    let c = offset.magnitude2() - sphere.radius * sphere.radius;
+    if c > 0.0 && b > 0.0 {
+      return None;
+    }
+    let discriminant = b * b - c;
+    if discriminant < 0.0 {
+      return None;
+    }
+    Some((-b - discriminant.sqrt()).max(0.0))
+  }
+
+  // Either side of the plane, None when parallel or behind
+  pub fn intersect_plane(&self, plane: &Plane) -> Option<f32> {
+    let denominator = plane.normal.dot(self.direction);
+    if denominator.abs() < EPSILON {
+      return None;
+    }
+    let t = -plane.signed_distance(self.origin) / denominator;
+    if t >= 0.0 {
+      Some(t)
+    } else {
+      None
+    }
+  }
+
+  // Moller-Trumbore, both faces are hit
+  pub fn intersect_triangle(&self, triangle: &Triangle) -> Option<f32> {
+    let edge0 = triangle.b - triangle.a;
+    let edge1 = triangle.c - triangle.a;
+    let p = self.direction.cross(edge1);
+    let determinant = edge0.dot(p);
+    if determinant.abs() < EPSILON {
+      return None;
+    }
+    let inverse = 1.0 / determinant;
+
+    let offset = self.origin - triangle.a;
+    let u = offset.dot(p) * inverse;
+    if !(0.0..=1.0).contains(&u) {
+      return None;
+    }
+    let q = offset.cross(edge0);
+    let v = self.direction.dot(q) * inverse;
+    if v < 0.0 || u + v > 1.0 {
+      return None;
+    }
+    let t = edge1.dot(q) * inverse;
+    if t >= 0.0 {
+      Some(t)
+    } else {
+      None
+    }
+  }
+
+  pub fn transform(&self, matrix: &Matrix4<f32>) -> Ray {
+    Ray::new(
+      transform_point(matrix, self.origin),
+      transform_vector(matrix, self.direction),
+    )
+  }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Triangle {
+  pub a: Vector3<f32>,
+  pub b: Vector3<f32>,
+  pub c: Vector3<f32>,
+}
+
+impl Triangle {
+  pub fn new(a: Vector3<f32>, b: Vector3<f32>, c: Vector3<f32>) -> Triangle {
+    Triangle { a, b, c }
+  }
+
+  // Counter clockwise winding faces the normal
+  pub fn normal(&self) -> Vector3<f32> {
+    (self.b - self.a).cross(self.c - self.a).normalize()
+  }
+
+  pub fn area(&self) -> f32 {
+    (self.b - self.a).cross(self.c - self.a).magnitude() / 2.0
+  }
+
+  pub fn centroid(&self) -> Vector3<f32> {
+    (self.a + self.b + self.c) / 3.0
+  }
+
+  pub fn plane(&self) -> Plane {
+    Plane::from_points(self.a, self.b, self.c)
+  }
+
+  pub fn aabb(&self) -> Aabb {
+    Aabb::new(self.a, self.a).merge_point(self.b).merge_point(self.c)
+  }
+
+  // Real-Time Collision Detection 5.1.5, by the voronoi region the point falls in
+  pub fn closest_point(&self, point: Vector3<f32>) -> Vector3<f32> {
+    let (a, b, c) = (self.a, self.b, self.c);
+    let ab = b - a;
+    let ac = c - a;
+
+    let ap = point - a;
+    let d1 = ab.dot(ap);
+    let d2 = ac.dot(ap);
+    if d1 <= 0.0 && d2 <= 0.0 {
+      return a;
+    }
+
+    let bp = point - b;
+    let d3 = ab.dot(bp);
+    let d4 = ac.dot(bp);
+    if d3 >= 0.0 && d4 <= d3 {
+      return b;
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+      return a + ab * (d1 / (d1 - d3));
+    }
+
+    let cp = point - c;
+    let d5 = ab.dot(cp);
+    let d6 = ac.dot(cp);
+    if d6 >= 0.0 && d5 <= d6 {
+      return c;
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+      return a + ac * (d2 / (d2 - d6));
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+      return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+
+    let denominator = 1.0 / (va + vb + vc);
+    a + ab * (vb * denominator) + ac * (vc * denominator)
+  }
+
+  pub fn transform(&self, matrix: &Matrix4<f32>) -> Triangle {
+    Triangle {
+      a: transform_point(matrix, self.a),
+      b: transform_point(matrix, self.b),
+      c: transform_point(matrix, self.c),
+    }
+  }
+}
+
+// Oriented bounding box, axes are unit length and orthogonal
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Obb {
+  pub center: Vector3<f32>,
+  pub axes: [Vector3<f32>; 3],
+  pub half_extents: Vector3<f32>,
+}
+
+impl Obb {
+  pub fn from_aabb(aabb: &Aabb) -> Obb {
+    Obb {
+      center: aabb.center(),
+      axes: [Vector3::unit_x(), Vector3::unit_y(), Vector3::unit_z()],
+      half_extents: aabb.half_extents(),
+    }
+  }
+
+  pub fn contains_point(&self, point: Vector3<f32>) -> bool {
+    let offset = point - self.center;
+    (0..3).all(|axis| offset.dot(self.axes[axis]).abs() <= self.half_extents[axis])
+  }
+
+  // Returns the point itself when it's inside
+  pub fn closest_point(&self, point: Vector3<f32>) -> Vector3<f32> {
+    let offset = point - self.center;
+    (0..3).fold(self.center, |closest, axis| {
+      let half_extent = self.half_extents[axis];
+      let distance = offset.dot(self.axes[axis]).max(-half_extent).min(half_extent);
+      closest + self.axes[axis] * distance
+    })
+  }
+
+  pub fn corners(&self) -> [Vector3<f32>; 8] {
+    let x = self.axes[0] * self.half_extents.x;
+    let y = self.axes[1] * self.half_extents.y;
+    let z = self.axes[2] * self.half_extents.z;
+    let c = self.center;
+    [
+      c - x - y - z,
+      c + x - y - z,
+      c - x + y - z,
+      c + x + y - z,
+      c - x - y + z,
+      c + x - y + z,
+      c - x + y + z,
+      c + x + y + z,
+    ]
+  }
+
+  pub fn aabb(&self) -> Aabb {
+    let half_extents = (0..3).fold(Vector3::zero(), |half_extents: Vector3<f32>, axis| {
+      let extent = self.axes[axis] * self.half_extents[axis];
+      half_extents + Vector3::new(extent.x.abs(), extent.y.abs(), extent.z.abs())
+    });
+    Aabb::from_center_half_extents(self.center, half_extents)
+  }
+
+  // Shears can't be represented, the axes are re-orthogonalized from the transformed x and y
+  pub fn transform(&self, matrix: &Matrix4<f32>) -> Obb {
+    let linear = matrix3(matrix);
+    let x = linear * (self.axes[0] * self.half_extents.x);
+    let y = linear * (self.axes[1] * self.half_extents.y);
+    let z = linear * (self.axes[2] * self.half_extents.z);
+
+    let axis_x = x.normalize();
+    let axis_z = axis_x.cross(y).normalize();
+    let axis_y = axis_z.cross(axis_x);
+    let axis_z = if axis_z.dot(z) < 0.0 { -axis_z } else { axis_z };
+    Obb {
+      center: transform_point(matrix, self.center),
+      axes: [axis_x, axis_y, axis_z],
+      half_extents: Vector3::new(x.magnitude(), y.magnitude(), z.magnitude()),
+    }
+  }
+}
+
+// Planes face inward, order is left, right, bottom, top, near, far
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Frustum {
+  pub planes: [Plane; 6],
+}
+
+impl Frustum {
+  // From projection * view, with OpenGL's -1 to 1 clip depth
+  pub fn from_matrix(matrix: &Matrix4<f32>) -> Frustum {
+    let row = |i: usize| Vector4::new(matrix.x[i], matrix.y[i], matrix.z[i], matrix.w[i]);
+    let (r0, r1, r2, r3) = (row(0), row(1), row(2), row(3));
+    Frustum {
+      planes: [
+        Plane::from_vector4(r3 + r0),
+        Plane::from_vector4(r3 - r0),
+        Plane::from_vector4(r3 + r1),
+        Plane::from_vector4(r3 - r1),
+        Plane::from_vector4(r3 + r2),
+        Plane::from_vector4(r3 - r2),
+      ],
+    }
+  }
+
+  pub fn contains_point(&self, point: Vector3<f32>) -> bool {
+    self.planes.iter().all(|plane| plane.signed_distance(point) >= 0.0)
+  }
+
+  pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+    self
+      .planes
+      .iter()
+      .all(|plane| plane.signed_distance(sphere.center) >= -sphere.radius)
+  }
+
+  // Conservative, boxes near a corner of the frustum can pass while being outside
+  pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
+    self.planes.iter().all(|plane| {
+      let farthest = Vector3::new(
+        if plane.normal.x >= 0.0 { aabb.max.x } else { aabb.min.x },
+        if plane.normal.y >= 0.0 { aabb.max.y } else { aabb.min.y },
+        if plane.normal.z >= 0.0 { aabb.max.z } else { aabb.min.z },
+      );
+      plane.signed_distance(farthest) >= 0.0
+    })
+  }
+}
+
+#[test]
+fn aabb_merge_contains_and_closest_point() {
+  let a = Aabb::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 1.0, 1.0));
+  let b = Aabb::new(Vector3::new(2.0, -1.0, 0.5), Vector3::new(3.0, 0.5, 2.0));
+  let merged = a.merge(&b);
+  assert_eq!(merged, Aabb::new(Vector3::new(0.0, -1.0, 0.0), Vector3::new(3.0, 1.0, 2.0)));
+  assert!(merged.contains_aabb(&a) && merged.contains_aabb(&b));
+  assert!(!a.contains_aabb(&merged));
+
+  let points = [Vector3::new(1.0, 2.0, 3.0), Vector3::new(-1.0, 0.0, 5.0)];
+  assert_eq!(
+    Aabb::from_points(&points),
+    Some(Aabb::new(Vector3::new(-1.0, 0.0, 3.0), Vector3::new(1.0, 2.0, 5.0)))
+  );
+  assert_eq!(Aabb::from_points(&[]), None);
+
+  assert_eq!(a.closest_point(Vector3::new(0.5, 0.5, 0.5)), Vector3::new(0.5, 0.5, 0.5));
+  assert_eq!(a.closest_point(Vector3::new(2.0, 0.5, -3.0)), Vector3::new(1.0, 0.5, 0.0));
+}
+
+#[test]
+fn aabb_intersects_aabb_and_sphere() {
+  let a = Aabb::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 1.0, 1.0));
+  assert!(a.intersects_aabb(&Aabb::new(Vector3::new(0.5, 0.5, 0.5), Vector3::new(2.0, 2.0, 2.0))));
+  assert!(a.intersects_aabb(&Aabb::new(Vector3::new(1.0, 0.0, 0.0), Vector3::new(2.0, 1.0, 1.0))));
+  assert!(!a.intersects_aabb(&Aabb::new(Vector3::new(1.1, 0.0, 0.0), Vector3::new(2.0, 1.0, 1.0))));
+
+  assert!(a.intersects_sphere(&Sphere::new(Vector3::new(1.5, 0.5, 0.5), 0.6)));
+  assert!(!a.intersects_sphere(&Sphere::new(Vector3::new(1.5, 1.5, 1.5), 0.8)));
+}
+
+#[test]
+fn aabb_transform() {
+  let aabb = Aabb::new(Vector3::new(-1.0, -1.0, -1.0), Vector3::new(1.0, 1.0, 1.0));
+  let moved = aabb.transform(&(Matrix4::from_translation(Vector3::new(5.0, 0.0, 0.0)) * Matrix4::from_scale(2.0)));
+  assert_eq!(moved, Aabb::new(Vector3::new(3.0, -2.0, -2.0), Vector3::new(7.0, 2.0, 2.0)));
+
+  let rotated = aabb.transform(&Matrix4::from_angle_y(cgmath::Deg(45.0)));
+  let half = 2.0f32.sqrt();
+  assert!((rotated.max - Vector3::new(half, 1.0, half)).magnitude() < 1e-4);
+  assert!((rotated.min + Vector3::new(half, 1.0, half)).magnitude() < 1e-4);
+}
+
+#[test]
+fn sphere_merge_contains_and_transform() {
+  let a = Sphere::new(Vector3::new(0.0, 0.0, 0.0), 1.0);
+  let b = Sphere::new(Vector3::new(4.0, 0.0, 0.0), 1.0);
+  let merged = a.merge(&b);
+  assert_eq!(merged, Sphere::new(Vector3::new(2.0, 0.0, 0.0), 3.0));
+  assert!(merged.contains_sphere(&a) && merged.contains_sphere(&b));
+  assert_eq!(merged.merge(&a), merged);
+
+  assert!(a.contains_point(Vector3::new(0.5, 0.5, 0.0)));
+  assert!(!a.intersects_sphere(&b));
+  assert!(a.intersects_sphere(&Sphere::new(Vector3::new(1.5, 0.0, 0.0), 0.5)));
+  assert_eq!(a.closest_point(Vector3::new(0.0, 3.0, 0.0)), Vector3::new(0.0, 1.0, 0.0));
+
+  let transformed = a.transform(
+    &(Matrix4::from_translation(Vector3::new(0.0, 2.0, 0.0)) * Matrix4::from_nonuniform_scale(1.0, 3.0, 2.0)),
+  );
+  assert_eq!(transformed, Sphere::new(Vector3::new(0.0, 2.0, 0.0), 3.0));
+}
+
+#[test]
+fn plane_distance_closest_point_and_transform() {
+  let plane = Plane::from_point_normal(Vector3::new(0.0, 2.0, 0.0), Vector3::new(0.0, 3.0, 0.0));
+  assert_eq!(plane.signed_distance(Vector3::new(1.0, 5.0, 0.0)), 3.0);
+  assert_eq!(plane.signed_distance(Vector3::new(1.0, 0.0, 0.0)), -2.0);
+  assert_eq!(plane.closest_point(Vector3::new(1.0, 5.0, 1.0)), Vector3::new(1.0, 2.0, 1.0));
+
+  let from_points = Plane::from_points(
+    Vector3::new(0.0, 0.0, 0.0),
+    Vector3::new(1.0, 0.0, 0.0),
+    Vector3::new(0.0, 1.0, 0.0),
+  );
+  assert_eq!(from_points.normal, Vector3::unit_z());
+
+  let moved = plane
+    .transform(&Matrix4::from_translation(Vector3::new(0.0, 1.0, 0.0)))
+    .unwrap();
+  assert!((moved.normal - Vector3::unit_y()).magnitude() < 1e-5);
+  assert!((moved.signed_distance(Vector3::new(0.0, 3.0, 0.0))).abs() < 1e-5);
+  assert_eq!(plane.transform(&Matrix4::from_scale(0.0)), None);
+}
+
+#[test]
+fn ray_intersects_aabb_sphere_plane_and_triangle() {
+  let ray = Ray::new(Vector3::new(0.0, 0.0, -5.0), Vector3::new(0.0, 0.0, 2.0));
+  let aabb = Aabb::new(Vector3::new(-1.0, -1.0, -1.0), Vector3::new(1.0, 1.0, 1.0));
+  assert_eq!(ray.intersect_aabb(&aabb), Some(4.0));
+  assert_eq!(Ray::new(Vector3::new(0.0, 0.0, 0.0), Vector3::unit_x()).intersect_aabb(&aabb), Some(0.0));
+  assert_eq!(Ray::new(Vector3::new(0.0, 2.0, -5.0), Vector3::unit_z()).intersect_aabb(&aabb), None);
+  assert_eq!(Ray::new(Vector3::new(0.0, 0.0, -5.0), -Vector3::unit_z()).intersect_aabb(&aabb), None);
+
+  let sphere = Sphere::new(Vector3::new(0.0, 0.0, 0.0), 2.0);
+  assert_eq!(ray.intersect_sphere(&sphere), Some(3.0));
+  assert_eq!(Ray::new(Vector3::new(0.0, 3.0, -5.0), Vector3::unit_z()).intersect_sphere(&sphere), None);
+  assert_eq!(Ray::new(Vector3::new(0.0, 0.0, 5.0), Vector3::unit_z()).intersect_sphere(&sphere), None);
+
+  let plane = Plane::from_point_normal(Vector3::new(0.0, 0.0, 1.0), -Vector3::unit_z());
+  assert_eq!(ray.intersect_plane(&plane), Some(6.0));
+  assert_eq!(Ray::new(Vector3::new(0.0, 0.0, -5.0), Vector3::unit_x()).intersect_plane(&plane), None);
+
+  let triangle = Triangle::new(
+    Vector3::new(-1.0, -1.0, 0.0),
+    Vector3::new(1.0, -1.0, 0.0),
+    Vector3::new(0.0, 1.0, 0.0),
+  );
+  assert_eq!(ray.intersect_triangle(&triangle), Some(5.0));
+  assert_eq!(Ray::new(Vector3::new(0.9, 0.9, -5.0), Vector3::unit_z()).intersect_triangle(&triangle), None);
+  assert_eq!(Ray::new(Vector3::new(0.0, 0.0, 5.0), Vector3::unit_z()).intersect_triangle(&triangle), None);
+
+  let transformed = ray.transform(&Matrix4::from_translation(Vector3::new(1.0, 0.0, 0.0)));
+  assert_eq!(transformed, Ray::new(Vector3::new(1.0, 0.0, -5.0), Vector3::unit_z()));
+}
+
+#[test]
+fn triangle_closest_point() {
+  let triangle = Triangle::new(
+    Vector3::new(0.0, 0.0, 0.0),
+    Vector3::new(2.0, 0.0, 0.0),
+    Vector3::new(0.0, 2.0, 0.0),
+  );
+  assert_eq!(triangle.closest_point(Vector3::new(0.5, 0.5, 3.0)), Vector3::new(0.5, 0.5, 0.0));
+  assert_eq!(triangle.closest_point(Vector3::new(-1.0, -1.0, 0.0)), Vector3::new(0.0, 0.0, 0.0));
+  assert_eq!(triangle.closest_point(Vector3::new(1.0, -1.0, 0.0)), Vector3::new(1.0, 0.0, 0.0));
+  assert_eq!(triangle.closest_point(Vector3::new(2.0, 2.0, 0.0)), Vector3::new(1.0, 1.0, 0.0));
+  assert_eq!(triangle.normal(), Vector3::unit_z());
+  assert_eq!(triangle.area(), 2.0);
+}
+
+#[test]
+fn obb_contains_closest_point_and_aabb() {
+  let aabb = Aabb::new(Vector3::new(-1.0, -0.5, -0.5), Vector3::new(1.0, 0.5, 0.5));
+  let obb = Obb::from_aabb(&aabb).transform(&Matrix4::from_angle_z(cgmath::Deg(90.0)));
+  assert!(obb.contains_point(Vector3::new(0.0, 0.9, 0.0)));
+  assert!(!obb.contains_point(Vector3::new(0.9, 0.0, 0.0)));
+  assert!((obb.closest_point(Vector3::new(0.0, 3.0, 0.0)) - Vector3::new(0.0, 1.0, 0.0)).magnitude() < 1e-5);
+
+  let bounds = obb.aabb();
+  assert!((bounds.min - Vector3::new(-0.5, -1.0, -0.5)).magnitude() < 1e-5);
+  assert!((bounds.max - Vector3::new(0.5, 1.0, 0.5)).magnitude() < 1e-5);
+  let tolerance = Aabb::from_center_half_extents(bounds.center(), bounds.half_extents() + Vector3::from_value(1e-4));
+  assert!(obb.corners().iter().all(|corner| tolerance.contains_point(*corner)));
+}
+
+#[test]
+fn frustum_contains_and_culls() {
+  let projection = cgmath::perspective(cgmath::Deg(90.0), 1.0, 0.1, 100.0);
+  let view = Matrix4::look_at(
+    cgmath::Point3::new(0.0, 0.0, 0.0),
+    cgmath::Point3::new(0.0, 0.0, -1.0),
+    Vector3::unit_y(),
+  );
+  let frustum = Frustum::from_matrix(&(projection * view));
+
+  assert!(frustum.contains_point(Vector3::new(0.0, 0.0, -10.0)));
+  assert!(!frustum.contains_point(Vector3::new(0.0, 0.0, 10.0)));
+  assert!(!frustum.contains_point(Vector3::new(0.0, 0.0, -200.0)));
+  assert!(!frustum.contains_point(Vector3::new(20.0, 0.0, -10.0)));
+
+  let unit = Vector3::from_value(1.0);
+  assert!(frustum.intersects_aabb(&Aabb::from_center_half_extents(Vector3::new(0.0, 0.0, -10.0), unit)));
+  assert!(frustum.intersects_aabb(&Aabb::from_center_half_extents(Vector3::new(10.5, 0.0, -10.0), unit)));
+  assert!(!frustum.intersects_aabb(&Aabb::from_center_half_extents(Vector3::new(0.0, 0.0, 10.0), unit)));
+  assert!(!frustum.intersects_aabb(&Aabb::from_center_half_extents(Vector3::new(0.0, 20.0, -10.0), unit)));
+
+  assert!(frustum.intersects_sphere(&Sphere::new(Vector3::new(0.0, 0.0, 1.0), 2.0)));
+  assert!(!frustum.intersects_sphere(&Sphere::new(Vector3::new(0.0, 0.0, 5.0), 2.0)));
+}
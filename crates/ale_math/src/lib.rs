@@ -1,4 +1,8 @@
 pub mod color;
+pub mod curve;
+pub mod geometry;
+pub mod noise;
+pub mod random;
 pub mod rect;
 pub mod transform;
 use cgmath::num_traits::clamp;
@@ -0,0 +1,350 @@
+use cgmath::{Vector2, Vector3};
+
+use crate::random::Random;
+
+// Seeded gradient and value noise, every function returns roughly -1..1 and is 0 on integer
+// lattice points except value noise.
+//
+//   let noise = Noise::new(seed);
+//   let height = Fractal::default().fbm2(|p| noise.perlin2(p), Vector2::new(x, z) * 0.01);
+#[derive(Debug, Clone)]
+pub struct Noise {
+  // 0..256 shuffled, repeated twice so lookups don't need to wrap
+  permutation: Vec<u8>,
+}
+
+impl Noise {
+  pub fn new(seed: u64) -> Noise {
+    let mut table: Vec<u8> = (0..=255).collect();
+    Random::new(seed).shuffle(&mut table);
+
+    let mut permutation = table.clone();
+    permutation.extend(table);
+    Noise { permutation }
+  }
+
+  pub fn perlin2(&self, point: Vector2<f32>) -> f32 {
+    let (x0, y0) = (point.x.floor(), point.y.floor());
+    let (x, y) = (point.x - x0, point.y - y0);
+    let (xi, yi) = (intern_wrap(x0), intern_wrap(y0));
+    let (u, v) = (intern_fade(x), intern_fade(y));
+
+    let n00 = intern_gradient2(self.hash2(xi, yi), x, y);
+    let n10 = intern_gradient2(self.hash2(xi + 1, yi), x - 1.0, y);
+    let n01 = intern_gradient2(self.hash2(xi, yi + 1), x, y - 1.0);
+    let n11 = intern_gradient2(self.hash2(xi + 1, yi + 1), x - 1.0, y - 1.0);
+
+    // unit gradients peak at sqrt(2) / 2
+    intern_lerp(intern_lerp(n00, n10, u), intern_lerp(n01, n11, u), v) * std::f32::consts::SQRT_2
+  }
+
+  // Improved Perlin noise
+  pub fn perlin3(&self, point: Vector3<f32>) -> f32 {
+    let (x0, y0, z0) = (point.x.floor(), point.y.floor(), point.z.floor());
+    let (x, y, z) = (point.x - x0, point.y - y0, point.z - z0);
+    let (xi, yi, zi) = (intern_wrap(x0), intern_wrap(y0), intern_wrap(z0));
+    let (u, v, w) = (intern_fade(x), intern_fade(y), intern_fade(z));
+
+    let corner = |dx: usize, dy: usize, dz: usize| {
+      intern_gradient3(
+        self.hash3(xi + dx, yi + dy, zi + dz),
+        x - dx as f32,
+        y - dy as f32,
+        z - dz as f32,
+      )
+    };
+
+    intern_lerp(
+      intern_lerp(
+        intern_lerp(corner(0, 0, 0), corner(1, 0, 0), u),
+        intern_lerp(corner(0, 1, 0), corner(1, 1, 0), u),
+        v,
+      ),
+      intern_lerp(
+        intern_lerp(corner(0, 0, 1), corner(1, 0, 1), u),
+        intern_lerp(corner(0, 1, 1), corner(1, 1, 1), u),
+        v,
+      ),
+      w,
+    )
+  }
+
+  pub fn simplex2(&self, point: Vector2<f32>) -> f32 {
+    let f2 = 0.5 * (3.0f32.sqrt() - 1.0);
+    let g2 = (3.0 - 3.0f32.sqrt()) / 6.0;
+
+    // skew to find the simplex cell
+    let s = (point.x + point.y) * f2;
+    let (i, j) = ((point.x + s).floor(), (point.y + s).floor());
+    let t = (i + j) * g2;
+    let (x0, y0) = (point.x - (i - t), point.y - (j - t));
+
+    let (i1, j1) = if x0 > y0 { (1, 0) } else { (0, 1) };
+    let (x1, y1) = (x0 - i1 as f32 + g2, y0 - j1 as f32 + g2);
+    let (x2, y2) = (x0 - 1.0 + 2.0 * g2, y0 - 1.0 + 2.0 * g2);
+
+    let (ii, jj) = (intern_wrap(i), intern_wrap(j));
+    let corner = |hash: u8, x: f32, y: f32| {
+      let t = 0.5 - x * x - y * y;
+      if t < 0.0 {
+        0.0
+      } else {
+        t.powi(4) * intern_gradient3(hash % 12, x, y, 0.0)
+      }
+    };
+
+    let n0 = corner(self.hash2(ii, jj), x0, y0);
+    let n1 = corner(self.hash2(ii + i1, jj + j1), x1, y1);
+    let n2 = corner(self.hash2(ii + 1, jj + 1), x2, y2);
+    70.0 * (n0 + n1 + n2)
+  }
+
+  pub fn simplex3(&self, point: Vector3<f32>) -> f32 {
+    let (f3, g3) = (1.0 / 3.0, 1.0 / 6.0);
+
+    let s = (point.x + point.y + point.z) * f3;
+    let (i, j, k) = ((point.x + s).floor(), (point.y + s).floor(), (point.z + s).floor());
+    let t = (i + j + k) * g3;
+    let (x0, y0, z0) = (point.x - (i - t), point.y - (j - t), point.z - (k - t));
+
+    // which of the six tetrahedra of the cube
+    let ((i1, j1, k1), (i2, j2, k2)) = if x0 >= y0 {
+      if y0 >= z0 {
+        ((1, 0, 0), (1, 1, 0))
+      } else if x0 >= z0 {
+        ((1, 0, 0), (1, 0, 1))
+      } else {
+        ((0, 0, 1), (1, 0, 1))
+      }
+    } else if y0 < z0 {
+      ((0, 0, 1), (0, 1, 1))
+    } else if x0 < z0 {
+      ((0, 1, 0), (0, 1, 1))
+    } else {
+      ((0, 1, 0), (1, 1, 0))
+    };
+
+    let offsets = [
+      (0, 0, 0, 0.0),
+      (i1, j1, k1, g3),
+      (i2, j2, k2, 2.0 * g3),
+      (1, 1, 1, 3.0 * g3),
+    ];
+    let (ii, jj, kk) = (intern_wrap(i), intern_wrap(j), intern_wrap(k));
+
+    let mut sum = 0.0;
+    for (di, dj, dk, g) in offsets.iter() {
+      let x = x0 - *di as f32 + g;
+      let y = y0 - *dj as f32 + g;
+      let z = z0 - *dk as f32 + g;
+      let t = 0.6 - x * x - y * y - z * z;
+      if t > 0.0 {
+        let hash = self.hash3(ii + di, jj + dj, kk + dk) % 12;
+        sum += t.powi(4) * intern_gradient3(hash, x, y, z);
+      }
+    }
+    32.0 * sum
+  }
+
+  // Random values on the lattice, smoothly interpolated
+  pub fn value2(&self, point: Vector2<f32>) -> f32 {
+    let (x0, y0) = (point.x.floor(), point.y.floor());
+    let (xi, yi) = (intern_wrap(x0), intern_wrap(y0));
+    let (u, v) = (intern_fade(point.x - x0), intern_fade(point.y - y0));
+
+    let value = |dx: usize, dy: usize| intern_hash_to_value(self.hash2(xi + dx, yi + dy));
+    intern_lerp(
+      intern_lerp(value(0, 0), value(1, 0), u),
+      intern_lerp(value(0, 1), value(1, 1), u),
+      v,
+    )
+  }
+
+  pub fn value3(&self, point: Vector3<f32>) -> f32 {
+    let (x0, y0, z0) = (point.x.floor(), point.y.floor(), point.z.floor());
+    let (xi, yi, zi) = (intern_wrap(x0), intern_wrap(y0), intern_wrap(z0));
+    let (u, v, w) = (
+      intern_fade(point.x - x0),
+      intern_fade(point.y - y0),
+      intern_fade(point.z - z0),
+    );
+
+    let value = |dx: usize, dy: usize, dz: usize| intern_hash_to_value(self.hash3(xi + dx, yi + dy, zi + dz));
+    intern_lerp(
+      intern_lerp(
+        intern_lerp(value(0, 0, 0), value(1, 0, 0), u),
+        intern_lerp(value(0, 1, 0), value(1, 1, 0), u),
+        v,
+      ),
+      intern_lerp(
+        intern_lerp(value(0, 0, 1), value(1, 0, 1), u),
+        intern_lerp(value(0, 1, 1), value(1, 1, 1), u),
+        v,
+      ),
+      w,
+    )
+  }
+
+  // x and y are wrapped lattice coordinates, + 1 at most
+  fn hash2(&self, x: usize, y: usize) -> u8 {
+    self.permutation[self.permutation[x & 255] as usize + (y & 255)]
+  }
+
+  fn hash3(&self, x: usize, y: usize, z: usize) -> u8 {
+    self.permutation[self.hash2(x, y) as usize + (z & 255)]
+  }
+}
+
+// Fractal brownian motion, sums octaves of any noise with rising frequency and falling amplitude
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fractal {
+  pub octaves: u32,
+  // frequency multiplier per octave
+  pub lacunarity: f32,
+  // amplitude multiplier per octave
+  pub gain: f32,
+}
+
+impl Default for Fractal {
+  fn default() -> Self {
+    Fractal {
+      octaves: 4,
+      lacunarity: 2.0,
+      gain: 0.5,
+    }
+  }
+}
+
+impl Fractal {
+  pub fn new(octaves: u32, lacunarity: f32, gain: f32) -> Fractal {
+    Fractal {
+      octaves,
+      lacunarity,
+      gain,
+    }
+  }
+
+  // Normalized so the result stays in the noise's range
+  pub fn fbm2<F: Fn(Vector2<f32>) -> f32>(&self, noise: F, point: Vector2<f32>) -> f32 {
+    self.intern_sum(|frequency| noise(point * frequency))
+  }
+
+  pub fn fbm3<F: Fn(Vector3<f32>) -> f32>(&self, noise: F, point: Vector3<f32>) -> f32 {
+    self.intern_sum(|frequency| noise(point * frequency))
+  }
+
+  fn intern_sum<F: Fn(f32) -> f32>(&self, octave: F) -> f32 {
+    let (mut sum, mut total_amplitude) = (0.0, 0.0);
+    let (mut frequency, mut amplitude) = (1.0, 1.0);
+    for _ in 0..self.octaves.max(1) {
+      sum += octave(frequency) * amplitude;
+      total_amplitude += amplitude;
+      frequency *= self.lacunarity;
+      amplitude *= self.gain;
+    }
+    if total_amplitude > 0.0 {
+      sum / total_amplitude
+    } else {
+      0.0
+    }
+  }
+}
+
+// Lattice coordinate into 0..256, negative coordinates wrap too
+fn intern_wrap(value: f32) -> usize {
+  (value as i64).rem_euclid(256) as usize
+}
+
+// 6t^5 - 15t^4 + 10t^3
+fn intern_fade(t: f32) -> f32 {
+  t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn intern_lerp(a: f32, b: f32, t: f32) -> f32 {
+  a + (b - a) * t
+}
+
+fn intern_hash_to_value(hash: u8) -> f32 {
+  hash as f32 / 255.0 * 2.0 - 1.0
+}
+
+fn intern_gradient2(hash: u8, x: f32, y: f32) -> f32 {
+  let diagonal = std::f32::consts::FRAC_1_SQRT_2;
+  match hash & 7 {
+    0 => x,
+    1 => -x,
+    2 => y,
+    3 => -y,
+    4 => (x + y) * diagonal,
+    5 => (-x + y) * diagonal,
+    6 => (x - y) * diagonal,
+    _ => (-x - y) * diagonal,
+  }
+}
+
+// The 12 cube edge gradients, 4 repeated to fill 16
+fn intern_gradient3(hash: u8, x: f32, y: f32, z: f32) -> f32 {
+  let h = hash & 15;
+  let u = if h < 8 { x } else { y };
+  let v = if h < 4 {
+    y
+  } else if h == 12 || h == 14 {
+    x
+  } else {
+    z
+  };
+  (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+}
+
+#[test]
+fn noise_is_seeded_and_bounded() {
+  let noise = Noise::new(1);
+  let same = Noise::new(1);
+  let other = Noise::new(2);
+  let point = Vector3::new(1.3, -4.7, 2.1);
+  assert_eq!(noise.perlin3(point), same.perlin3(point));
+  assert_ne!(noise.perlin3(point), other.perlin3(point));
+
+  // Gradient noise is 0 on the lattice
+  assert_eq!(noise.perlin2(Vector2::new(3.0, -2.0)), 0.0);
+  assert_eq!(noise.perlin3(Vector3::new(3.0, -2.0, 7.0)), 0.0);
+
+  let mut random = Random::new(3);
+  for _ in 0..2000 {
+    let p3 = Vector3::new(
+      random.range_f32(-100.0, 100.0),
+      random.range_f32(-100.0, 100.0),
+      random.range_f32(-100.0, 100.0),
+    );
+    let p2 = Vector2::new(p3.x, p3.y);
+    for value in [
+      noise.perlin2(p2),
+      noise.perlin3(p3),
+      noise.simplex2(p2),
+      noise.simplex3(p3),
+      noise.value2(p2),
+      noise.value3(p3),
+    ]
+    .iter()
+    {
+      assert!(value.abs() <= 1.1, "{}", value);
+    }
+  }
+}
+
+#[test]
+fn noise_is_continuous_and_fbm_normalized() {
+  let noise = Noise::new(9);
+  let a = Vector2::new(10.5f32, 20.25);
+  let b = a + Vector2::new(0.001, 0.0);
+  assert!((noise.perlin2(a) - noise.perlin2(b)).abs() < 0.01);
+  assert!((noise.simplex2(a) - noise.simplex2(b)).abs() < 0.01);
+  assert!((noise.value2(a) - noise.value2(b)).abs() < 0.01);
+
+  let fractal = Fractal::default();
+  let constant = fractal.fbm3(|_| 1.0, Vector3::new(0.0, 0.0, 0.0));
+  assert!((constant - 1.0).abs() < 0.0001);
+  let value = fractal.fbm2(|p| noise.simplex2(p), a);
+  assert!(value.abs() <= 1.0);
+  assert_eq!(Fractal::new(1, 2.0, 0.5).fbm2(|p| noise.perlin2(p), a), noise.perlin2(a));
+}
@@ -0,0 +1,201 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use cgmath::{InnerSpace, Vector2, Vector3};
+use serde::{Deserialize, Serialize};
+
+const PCG_MULTIPLIER: u64 = 6364136223846793005;
+
+// PCG32 (XSH RR), the same seed and stream always give the same numbers on every platform.
+// Serialize it to save and restore the exact sequence, e.g. for replays.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Random {
+  state: u64,
+  // always odd
+  increment: u64,
+}
+
+impl Random {
+  pub fn new(seed: u64) -> Random {
+    Random::with_stream(seed, 0)
+  }
+
+  // Streams with the same seed are independent sequences
+  pub fn with_stream(seed: u64, stream: u64) -> Random {
+    let mut random = Random {
+      state: 0,
+      increment: (stream << 1) | 1,
+    };
+    random.next_u32();
+    random.state = random.state.wrapping_add(seed);
+    random.next_u32();
+    random
+  }
+
+  // Seed from the clock, log or keep the seed to reproduce the sequence later
+  pub fn seed_from_time() -> u64 {
+    let nanos = SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .map(|d| d.as_nanos() as u64)
+      .unwrap_or(0);
+    // splitmix64 so close timestamps give very different seeds
+    let mut z = nanos.wrapping_add(0x9E3779B97F4A7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+  }
+
+  // A new generator seeded from this one, give one to each system so they don't shift each other
+  pub fn fork(&mut self) -> Random {
+    let seed = self.next_u64();
+    let stream = self.next_u64();
+    Random::with_stream(seed, stream)
+  }
+
+  pub fn next_u32(&mut self) -> u32 {
+    let old_state = self.state;
+    self.state = old_state.wrapping_mul(PCG_MULTIPLIER).wrapping_add(self.increment);
+    let xorshifted = (((old_state >> 18) ^ old_state) >> 27) as u32;
+    let rotation = (old_state >> 59) as u32;
+    xorshifted.rotate_right(rotation)
+  }
+
+  pub fn next_u64(&mut self) -> u64 {
+    ((self.next_u32() as u64) << 32) | self.next_u32() as u64
+  }
+
+  // 0..1, 1 excluded
+  pub fn next_f32(&mut self) -> f32 {
+    (self.next_u32() >> 8) as f32 / (1u32 << 24) as f32
+  }
+
+  pub fn next_bool(&mut self) -> bool {
+    self.next_u32() & 1 == 1
+  }
+
+  // true with the given probability, 0..1
+  pub fn chance(&mut self, probability: f32) -> bool {
+    self.next_f32() < probability
+  }
+
+  // 0..bound, bound excluded, without modulo bias. 0 when bound is 0
+  pub fn below(&mut self, bound: u32) -> u32 {
+    if bound == 0 {
+      return 0;
+    }
+    let threshold = bound.wrapping_neg() % bound;
+    loop {
+      let value = self.next_u32();
+      if value >= threshold {
+        return value % bound;
+      }
+    }
+  }
+
+  // min..max, max excluded
+  pub fn range_i32(&mut self, min: i32, max: i32) -> i32 {
+    if max <= min {
+      return min;
+    }
+    let span = (max as i64 - min as i64) as u32;
+    (min as i64 + self.below(span) as i64) as i32
+  }
+
+  // min..max, max excluded
+  pub fn range_usize(&mut self, min: usize, max: usize) -> usize {
+    if max <= min {
+      return min;
+    }
+    min + self.below((max - min) as u32) as usize
+  }
+
+  // min..max, max excluded
+  pub fn range_f32(&mut self, min: f32, max: f32) -> f32 {
+    min + (max - min) * self.next_f32()
+  }
+
+  pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+    if items.is_empty() {
+      return None;
+    }
+    Some(&items[self.range_usize(0, items.len())])
+  }
+
+  // Fisher-Yates
+  pub fn shuffle<T>(&mut self, items: &mut [T]) {
+    for i in (1..items.len()).rev() {
+      let j = self.range_usize(0, i + 1);
+      items.swap(i, j);
+    }
+  }
+
+  // Evenly spread on the unit circle
+  pub fn unit_vector2(&mut self) -> Vector2<f32> {
+    let angle = self.range_f32(0.0, std::f32::consts::PI * 2.0);
+    Vector2::new(angle.cos(), angle.sin())
+  }
+
+  // Evenly spread on the unit sphere
+  pub fn unit_vector3(&mut self) -> Vector3<f32> {
+    let z = self.range_f32(-1.0, 1.0);
+    let angle = self.range_f32(0.0, std::f32::consts::PI * 2.0);
+    let radius = (1.0 - z * z).sqrt();
+    Vector3::new(radius * angle.cos(), radius * angle.sin(), z)
+  }
+
+  // Evenly spread inside the unit sphere
+  pub fn in_unit_sphere(&mut self) -> Vector3<f32> {
+    loop {
+      let point = Vector3::new(
+        self.range_f32(-1.0, 1.0),
+        self.range_f32(-1.0, 1.0),
+        self.range_f32(-1.0, 1.0),
+      );
+      if point.magnitude2() <= 1.0 {
+        return point;
+      }
+    }
+  }
+}
+
+#[test]
+fn random_is_deterministic() {
+  let mut a = Random::new(42);
+  let mut b = Random::new(42);
+  let sequence: Vec<u32> = (0..8).map(|_| a.next_u32()).collect();
+  assert_eq!(sequence, (0..8).map(|_| b.next_u32()).collect::<Vec<u32>>());
+  assert_ne!(Random::new(43).next_u32(), Random::new(42).next_u32());
+  assert_ne!(Random::with_stream(42, 1).next_u32(), Random::new(42).next_u32());
+
+  // Reference output of the PCG32 demo, seed 42 on stream 54
+  let mut pcg = Random::with_stream(42, 54);
+  assert_eq!(pcg.next_u32(), 0xa15c02b7);
+  assert_eq!(pcg.next_u32(), 0x7b47f409);
+
+  // Restoring a copy continues the same sequence
+  let saved = a.clone();
+  let next = a.next_u64();
+  assert_eq!(saved.clone().next_u64(), next);
+}
+
+#[test]
+fn random_ranges() {
+  let mut random = Random::new(7);
+  for _ in 0..1000 {
+    let f = random.next_f32();
+    assert!((0.0..1.0).contains(&f));
+    let i = random.range_i32(-3, 3);
+    assert!((-3..3).contains(&i));
+    assert!((random.unit_vector3().magnitude() - 1.0).abs() < 0.0001);
+    assert!(random.in_unit_sphere().magnitude() <= 1.0);
+  }
+  assert_eq!(random.range_i32(5, 5), 5);
+  assert_eq!(random.below(0), 0);
+  assert_eq!(random.range_i32(i32::MIN, i32::MIN + 1), i32::MIN);
+  assert_eq!(random.choose::<u32>(&[]), None);
+
+  let mut items = [1, 2, 3, 4, 5, 6, 7, 8];
+  random.shuffle(&mut items);
+  let mut sorted = items;
+  sorted.sort();
+  assert_eq!(sorted, [1, 2, 3, 4, 5, 6, 7, 8]);
+}
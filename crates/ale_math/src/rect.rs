@@ -1,25 +1,258 @@
-use cgmath::Vector2;
+use cgmath::num_traits::{NumCast, ToPrimitive};
+use cgmath::{BaseNum, Vector2};
 
-#[derive(Debug, Clone)]
-pub struct Rect {
-  pub position: Vector2<i32>,
-  pub size: Vector2<u32>,
+// position is the min corner, the top left in window space where y goes down
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect<T> {
+  pub position: Vector2<T>,
+  pub size: Vector2<T>,
 }
 
-impl Rect {
-  pub fn is_inside(&self, x: i32, y: i32) -> bool {
-    let xleft = self.position.x;
-    let xright = self.position.x + self.size.x as i32;
-    if x < xleft || x > xright {
-      return false;
+pub type RectF = Rect<f32>;
+pub type RectI = Rect<i32>;
+
+impl<T: BaseNum> Rect<T> {
+  pub fn new(position: Vector2<T>, size: Vector2<T>) -> Rect<T> {
+    Rect { position, size }
+  }
+
+  // A max smaller than min gives an empty rect at min
+  pub fn from_min_max(min: Vector2<T>, max: Vector2<T>) -> Rect<T> {
+    Rect {
+      position: min,
+      size: Vector2::new(intern_max(max.x - min.x, T::zero()), intern_max(max.y - min.y, T::zero())),
     }
+  }
+
+  pub fn min(&self) -> Vector2<T> {
+    self.position
+  }
+
+  pub fn max(&self) -> Vector2<T> {
+    self.position + self.size
+  }
+
+  pub fn to_min_max(&self) -> (Vector2<T>, Vector2<T>) {
+    (self.min(), self.max())
+  }
+
+  pub fn center(&self) -> Vector2<T> {
+    let two = T::one() + T::one();
+    self.position + self.size / two
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.size.x <= T::zero() || self.size.y <= T::zero()
+  }
+
+  // Edges are inside
+  pub fn is_inside(&self, x: T, y: T) -> bool {
+    self.contains_point(Vector2::new(x, y))
+  }
 
-    let ytop = self.position.y;
-    let ybot = self.position.y + self.size.y as i32;
-    if y < ytop || y > ybot {
-      return false;
+  // Edges are inside
+  pub fn contains_point(&self, point: Vector2<T>) -> bool {
+    let (min, max) = self.to_min_max();
+    point.x >= min.x && point.x <= max.x && point.y >= min.y && point.y <= max.y
+  }
+
+  pub fn contains_rect(&self, other: &Rect<T>) -> bool {
+    self.contains_point(other.min()) && self.contains_point(other.max())
+  }
+
+  // Rects only sharing an edge don't intersect
+  pub fn intersects(&self, other: &Rect<T>) -> bool {
+    self.intersection(other).is_some()
+  }
+
+  pub fn intersection(&self, other: &Rect<T>) -> Option<Rect<T>> {
+    let min = Vector2::new(
+      intern_max(self.position.x, other.position.x),
+      intern_max(self.position.y, other.position.y),
+    );
+    let (self_max, other_max) = (self.max(), other.max());
+    let max = Vector2::new(intern_min(self_max.x, other_max.x), intern_min(self_max.y, other_max.y));
+    if max.x <= min.x || max.y <= min.y {
+      return None;
     }
+    Some(Rect::from_min_max(min, max))
+  }
+
+  // Smallest rect containing both
+  pub fn union(&self, other: &Rect<T>) -> Rect<T> {
+    let (self_max, other_max) = (self.max(), other.max());
+    Rect::from_min_max(
+      Vector2::new(
+        intern_min(self.position.x, other.position.x),
+        intern_min(self.position.y, other.position.y),
+      ),
+      Vector2::new(intern_max(self_max.x, other_max.x), intern_max(self_max.y, other_max.y)),
+    )
+  }
+
+  // Moves every edge inward, never below an empty rect at the center
+  pub fn inset(&self, amount: T) -> Rect<T> {
+    let two = T::one() + T::one();
+    let inset_x = intern_min(amount, self.size.x / two);
+    let inset_y = intern_min(amount, self.size.y / two);
+    Rect::from_min_max(
+      self.position + Vector2::new(inset_x, inset_y),
+      self.max() - Vector2::new(inset_x, inset_y),
+    )
+  }
+
+  // Moves every edge outward
+  pub fn outset(&self, amount: T) -> Rect<T> {
+    Rect::from_min_max(
+      self.position - Vector2::new(amount, amount),
+      self.max() + Vector2::new(amount, amount),
+    )
+  }
+
+  // (left, right), fraction of the width goes left
+  pub fn split_horizontal(&self, fraction: f32) -> (Rect<T>, Rect<T>) {
+    let left = intern_scale(self.size.x, fraction);
+    (
+      Rect::new(self.position, Vector2::new(left, self.size.y)),
+      Rect::new(
+        Vector2::new(self.position.x + left, self.position.y),
+        Vector2::new(self.size.x - left, self.size.y),
+      ),
+    )
+  }
 
-    return true;
+  // (top, bottom), fraction of the height goes on top
+  pub fn split_vertical(&self, fraction: f32) -> (Rect<T>, Rect<T>) {
+    let top = intern_scale(self.size.y, fraction);
+    (
+      Rect::new(self.position, Vector2::new(self.size.x, top)),
+      Rect::new(
+        Vector2::new(self.position.x, self.position.y + top),
+        Vector2::new(self.size.x, self.size.y - top),
+      ),
+    )
   }
+
+  // Places a rect of size inside this one, anchor picks the point in this rect and pivot the
+  // point of the new rect that lands on it, both from (0, 0) top left to (1, 1) bottom right.
+  //
+  //   // 200x50 centered at the bottom, 10 units from the edge
+  //   let bar = screen.place(Vector2::new(200, 50), Vector2::new(0.5, 1.0), Vector2::new(0.5, 1.0), Vector2::new(0, -10));
+  pub fn place(&self, size: Vector2<T>, anchor: Vector2<f32>, pivot: Vector2<f32>, offset: Vector2<T>) -> Rect<T> {
+    let anchor_point = self.denormalize_point(anchor);
+    let pivot_offset = Vector2::new(intern_scale(size.x, pivot.x), intern_scale(size.y, pivot.y));
+    Rect::new(anchor_point - pivot_offset + offset, size)
+  }
+
+  // (0, 0) at the min corner to (1, 1) at the max corner
+  pub fn normalize_point(&self, point: Vector2<T>) -> Vector2<f32> {
+    let offset = point - self.position;
+    Vector2::new(
+      intern_to_f32(offset.x) / intern_to_f32(self.size.x),
+      intern_to_f32(offset.y) / intern_to_f32(self.size.y),
+    )
+  }
+
+  pub fn denormalize_point(&self, point: Vector2<f32>) -> Vector2<T> {
+    self.position + Vector2::new(intern_scale(self.size.x, point.x), intern_scale(self.size.y, point.y))
+  }
+
+  // The point at the same relative place in another rect, e.g. from the window to a viewport
+  pub fn transform_point(&self, point: Vector2<T>, to: &Rect<T>) -> Vector2<T> {
+    to.denormalize_point(self.normalize_point(point))
+  }
+
+  // None when a value doesn't fit in U
+  pub fn cast<U: BaseNum>(&self) -> Option<Rect<U>> {
+    Some(Rect {
+      position: self.position.cast()?,
+      size: self.size.cast()?,
+    })
+  }
+}
+
+fn intern_min<T: PartialOrd>(a: T, b: T) -> T {
+  if b < a {
+    b
+  } else {
+    a
+  }
+}
+
+fn intern_max<T: PartialOrd>(a: T, b: T) -> T {
+  if b > a {
+    b
+  } else {
+    a
+  }
+}
+
+fn intern_to_f32<T: ToPrimitive>(value: T) -> f32 {
+  value.to_f32().unwrap_or(0.0)
+}
+
+// Rounds towards zero for integers
+fn intern_scale<T: BaseNum>(value: T, fraction: f32) -> T {
+  NumCast::from(intern_to_f32(value) * fraction).unwrap_or(T::zero())
+}
+
+#[test]
+fn rect_min_max_contains_and_center() {
+  let rect = RectI::from_min_max(Vector2::new(10, 20), Vector2::new(30, 60));
+  assert_eq!(rect, Rect::new(Vector2::new(10, 20), Vector2::new(20, 40)));
+  assert_eq!(rect.to_min_max(), (Vector2::new(10, 20), Vector2::new(30, 60)));
+  assert_eq!(rect.center(), Vector2::new(20, 40));
+  assert!(rect.is_inside(30, 60));
+  assert!(!rect.contains_point(Vector2::new(31, 20)));
+  assert!(rect.contains_rect(&Rect::new(Vector2::new(15, 25), Vector2::new(5, 5))));
+  assert!(!rect.contains_rect(&Rect::new(Vector2::new(25, 25), Vector2::new(10, 5))));
+  assert!(RectI::from_min_max(Vector2::new(5, 5), Vector2::new(0, 10)).is_empty());
+}
+
+#[test]
+fn rect_intersection_and_union() {
+  let a = RectF::new(Vector2::new(0.0, 0.0), Vector2::new(10.0, 10.0));
+  let b = RectF::new(Vector2::new(5.0, -5.0), Vector2::new(10.0, 10.0));
+  assert_eq!(a.intersection(&b), Some(RectF::new(Vector2::new(5.0, 0.0), Vector2::new(5.0, 5.0))));
+  assert_eq!(a.union(&b), RectF::new(Vector2::new(0.0, -5.0), Vector2::new(15.0, 15.0)));
+
+  let touching = RectF::new(Vector2::new(10.0, 0.0), Vector2::new(5.0, 5.0));
+  assert_eq!(a.intersection(&touching), None);
+  assert!(!a.intersects(&touching));
+}
+
+#[test]
+fn rect_inset_outset_and_split() {
+  let rect = RectF::new(Vector2::new(0.0, 0.0), Vector2::new(100.0, 50.0));
+  assert_eq!(rect.inset(10.0), RectF::new(Vector2::new(10.0, 10.0), Vector2::new(80.0, 30.0)));
+  assert_eq!(rect.inset(40.0), RectF::new(Vector2::new(40.0, 25.0), Vector2::new(20.0, 0.0)));
+  assert_eq!(rect.outset(5.0), RectF::new(Vector2::new(-5.0, -5.0), Vector2::new(110.0, 60.0)));
+
+  let (left, right) = rect.split_horizontal(0.25);
+  assert_eq!(left, RectF::new(Vector2::new(0.0, 0.0), Vector2::new(25.0, 50.0)));
+  assert_eq!(right, RectF::new(Vector2::new(25.0, 0.0), Vector2::new(75.0, 50.0)));
+  let (top, bottom) = RectI::new(Vector2::new(0, 0), Vector2::new(10, 9)).split_vertical(0.5);
+  assert_eq!(top.size.y + bottom.size.y, 9);
+  assert_eq!(bottom.position.y, top.size.y);
+}
+
+#[test]
+fn rect_place_and_transform_point() {
+  let screen = RectI::new(Vector2::new(0, 0), Vector2::new(800, 600));
+  let bar = screen.place(
+    Vector2::new(200, 50),
+    Vector2::new(0.5, 1.0),
+    Vector2::new(0.5, 1.0),
+    Vector2::new(0, -10),
+  );
+  assert_eq!(bar, RectI::new(Vector2::new(300, 540), Vector2::new(200, 50)));
+
+  let viewport = RectI::new(Vector2::new(100, 100), Vector2::new(400, 300));
+  assert_eq!(screen.normalize_point(Vector2::new(400, 150)), Vector2::new(0.5, 0.25));
+  assert_eq!(screen.transform_point(Vector2::new(400, 150), &viewport), Vector2::new(300, 175));
+
+  assert_eq!(
+    viewport.cast::<f32>(),
+    Some(RectF::new(Vector2::new(100.0, 100.0), Vector2::new(400.0, 300.0)))
+  );
 }
@@ -1,5 +1,5 @@
 use cgmath::prelude::*;
-use cgmath::{Matrix4, One, Quaternion, Vector3};
+use cgmath::{Matrix3, Matrix4, One, Quaternion, Vector3};
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct AleTransform {
@@ -64,6 +64,26 @@ impl AleTransform {
     }
   }
 
+  // Decomposes an affine matrix into translation, rotation and scale, shear is lost.
+  // A negative determinant is put on the x scale.
+  pub fn from_matrix(matrix: Matrix4<f32>) -> AleTransform {
+    let position = matrix.w.truncate();
+    let (x, y, z) = (matrix.x.truncate(), matrix.y.truncate(), matrix.z.truncate());
+
+    let mut scale = Vector3::new(x.magnitude(), y.magnitude(), z.magnitude());
+    if Matrix3::from_cols(x, y, z).determinant() < 0.0 {
+      scale.x = -scale.x;
+    }
+
+    let lcl_rotation = if scale.x.abs() > f32::EPSILON && scale.y.abs() > f32::EPSILON && scale.z.abs() > f32::EPSILON {
+      Quaternion::from(Matrix3::from_cols(x / scale.x, y / scale.y, z / scale.z)).normalize()
+    } else {
+      Quaternion::one()
+    };
+
+    AleTransform::from_all(position, lcl_rotation, scale)
+  }
+
   pub fn translate(&mut self, unit: Vector3<f32>) {
     self.position += unit;
     self.matrix = None; // Destroy matrix cache
@@ -79,20 +99,187 @@ impl AleTransform {
     self.matrix = None; // Destroy matrix cache
   }
 
+  pub fn set_scale(&mut self, scale: Vector3<f32>) {
+    self.scale = scale;
+    self.matrix = None;
+  }
+
+  // Rotates around the local axes
+  pub fn rotate(&mut self, rotation: Quaternion<f32>) {
+    self.lcl_rotation = (self.lcl_rotation * rotation).normalize();
+    self.matrix = None;
+  }
+
+  // Rotates around the world axes
+  pub fn rotate_world(&mut self, rotation: Quaternion<f32>) {
+    self.lcl_rotation = (rotation * self.lcl_rotation).normalize();
+    self.matrix = None;
+  }
+
+  // Turns forward towards target, does nothing when target is on the position or straight above/below
+  pub fn look_at(&mut self, target: Vector3<f32>, up: Vector3<f32>) {
+    let forward = target - self.position;
+    if forward.magnitude2() <= f32::EPSILON {
+      return;
+    }
+    let forward = forward.normalize();
+    let right = forward.cross(up);
+    if right.magnitude2() <= f32::EPSILON {
+      return;
+    }
+    let right = right.normalize();
+    let up = right.cross(forward);
+    self.set_rotation(Quaternion::from(Matrix3::from_cols(right, up, -forward)));
+  }
+
+  // -z
+  pub fn forward(&self) -> Vector3<f32> {
+    self.lcl_rotation.rotate_vector(-Vector3::unit_z())
+  }
+
+  // +x
+  pub fn right(&self) -> Vector3<f32> {
+    self.lcl_rotation.rotate_vector(Vector3::unit_x())
+  }
+
+  // +y
+  pub fn up(&self) -> Vector3<f32> {
+    self.lcl_rotation.rotate_vector(Vector3::unit_y())
+  }
+
+  pub fn transform_point(&self, point: Vector3<f32>) -> Vector3<f32> {
+    self.position + self.transform_vector(point)
+  }
+
+  // Scale and rotation, no translation
+  pub fn transform_vector(&self, vector: Vector3<f32>) -> Vector3<f32> {
+    self.lcl_rotation.rotate_vector(self.scale.mul_element_wise(vector))
+  }
+
+  // Rotation only, length is kept
+  pub fn transform_direction(&self, direction: Vector3<f32>) -> Vector3<f32> {
+    self.lcl_rotation.rotate_vector(direction)
+  }
+
+  pub fn inverse_transform_point(&self, point: Vector3<f32>) -> Vector3<f32> {
+    self.inverse_transform_vector(point - self.position)
+  }
+
+  pub fn inverse_transform_vector(&self, vector: Vector3<f32>) -> Vector3<f32> {
+    self.lcl_rotation.invert().rotate_vector(vector).div_element_wise(self.scale)
+  }
+
+  // None when a scale axis is zero. Exact for uniform scale, otherwise the shear of the
+  // inverted matrix is lost
+  pub fn inverse(&self) -> Option<AleTransform> {
+    self.matrix().invert().map(AleTransform::from_matrix)
+  }
+
+  // World transform of child when self is its parent. Exact when the parent scale is uniform,
+  // otherwise scale is multiplied per axis like most engines do
+  pub fn combine(&self, child: &AleTransform) -> AleTransform {
+    AleTransform::from_all(
+      self.transform_point(child.position),
+      (self.lcl_rotation * child.lcl_rotation).normalize(),
+      self.scale.mul_element_wise(child.scale),
+    )
+  }
+
+  // Position and scale are lerped, rotation takes the shortest slerp
+  pub fn lerp(&self, other: &AleTransform, t: f32) -> AleTransform {
+    let other_rotation = if self.lcl_rotation.dot(other.lcl_rotation) < 0.0 {
+      -other.lcl_rotation
+    } else {
+      other.lcl_rotation
+    };
+
+    AleTransform::from_all(
+      self.position.lerp(other.position, t),
+      self.lcl_rotation.slerp(other_rotation, t).normalize(),
+      self.scale.lerp(other.scale, t),
+    )
+  }
+
   pub fn matrix_cache(&mut self) -> Matrix4<f32> {
     match self.matrix {
       None => {
-        let m = Matrix4::from_translation(self.position)
-          * Matrix4::from(self.lcl_rotation)
-          * Matrix4::from_nonuniform_scale(self.scale.x, self.scale.y, self.scale.z);
-        self.matrix = Some(m);
+        self.matrix = Some(self.intern_compute_matrix());
       }
       Some(_) => (),
     }
     self.matrix.unwrap()
   }
 
+  // Uses the cache when it's there, but doesn't fill it
+  pub fn matrix(&self) -> Matrix4<f32> {
+    self.matrix.unwrap_or_else(|| self.intern_compute_matrix())
+  }
+
+  fn intern_compute_matrix(&self) -> Matrix4<f32> {
+    Matrix4::from_translation(self.position)
+      * Matrix4::from(self.lcl_rotation)
+      * Matrix4::from_nonuniform_scale(self.scale.x, self.scale.y, self.scale.z)
+  }
+
   pub fn scale_matrix(&mut self) -> Matrix4<f32> {
     Matrix4::from_nonuniform_scale(self.scale.x, self.scale.y, self.scale.z)
   }
 }
+
+#[test]
+fn transform_matrix_decomposition() {
+  let transform = AleTransform::from_all(
+    Vector3::new(1.0, 2.0, 3.0),
+    Quaternion::from_angle_y(cgmath::Deg(90.0f32)),
+    Vector3::new(2.0, 1.0, 0.5),
+  );
+  let decomposed = AleTransform::from_matrix(transform.matrix());
+  assert!((decomposed.position - transform.position).magnitude() < 0.0001);
+  assert!((decomposed.scale - transform.scale).magnitude() < 0.0001);
+  assert!(decomposed.lcl_rotation.dot(transform.lcl_rotation).abs() > 0.9999);
+
+  let mirrored = AleTransform::from_matrix(Matrix4::from_nonuniform_scale(-1.0, 1.0, 1.0));
+  assert_eq!(mirrored.scale, Vector3::new(-1.0, 1.0, 1.0));
+}
+
+#[test]
+fn transform_points_inverse_and_combine() {
+  let parent = AleTransform::from_all(
+    Vector3::new(0.0, 0.0, -5.0),
+    Quaternion::from_angle_y(cgmath::Deg(90.0f32)),
+    Vector3::from_value(2.0),
+  );
+  let point = parent.transform_point(Vector3::new(1.0, 0.0, 0.0));
+  assert!((point - Vector3::new(0.0, 0.0, -7.0)).magnitude() < 0.0001);
+  assert!((parent.inverse_transform_point(point) - Vector3::new(1.0, 0.0, 0.0)).magnitude() < 0.0001);
+
+  let inverse = parent.inverse().unwrap();
+  assert!((inverse.transform_point(point) - Vector3::new(1.0, 0.0, 0.0)).magnitude() < 0.0001);
+  assert!(AleTransform::from_scale(Vector3::new(1.0, 0.0, 1.0)).inverse().is_none());
+
+  let child = AleTransform::from_position(Vector3::new(1.0, 0.0, 0.0));
+  let world = parent.combine(&child);
+  assert!((world.position - point).magnitude() < 0.0001);
+  let expected = parent.matrix() * child.matrix();
+  assert!((world.matrix().w - expected.w).magnitude() < 0.0001);
+}
+
+#[test]
+fn transform_look_at_directions_and_lerp() {
+  let mut transform = AleTransform::from_position(Vector3::new(0.0, 0.0, 0.0));
+  assert_eq!(transform.forward(), -Vector3::unit_z());
+  transform.look_at(Vector3::new(10.0, 0.0, 0.0), Vector3::unit_y());
+  assert!((transform.forward() - Vector3::unit_x()).magnitude() < 0.0001);
+  assert!((transform.right() - Vector3::unit_z()).magnitude() < 0.0001);
+  assert!((transform.up() - Vector3::unit_y()).magnitude() < 0.0001);
+
+  let start = AleTransform::new();
+  let mut end = AleTransform::from_position_scale(Vector3::new(2.0, 0.0, 0.0), Vector3::from_value(3.0));
+  end.set_rotation(-Quaternion::from_angle_y(cgmath::Deg(90.0f32)));
+  let half = start.lerp(&end, 0.5);
+  assert_eq!(half.position, Vector3::new(1.0, 0.0, 0.0));
+  assert_eq!(half.scale, Vector3::from_value(2.0));
+  // Shortest path even though end is stored negated
+  let expected = Quaternion::from_angle_y(cgmath::Deg(45.0f32));
+  assert!(half.lcl_rotation.dot(expected).abs() > 0.9999);
+}
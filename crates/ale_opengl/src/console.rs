@@ -17,8 +17,9 @@ pub fn ale_opengl_console_render(
     let font_size = 24;
 
     // Render current line buffer
-    let font_layout = font.layout(
+    let font_layout = font.layout_scaled(
       font_size,
+      text_renderer.content_scale(),
       &format!("{}_", &console.line_buffer),
       Some(screen_size.clone()),
     );
@@ -27,7 +28,7 @@ pub fn ale_opengl_console_render(
 
     // Render existing lines
     for line in console.lines.iter().rev() {
-      let font_layout = font.layout(font_size, line, Some(screen_size.clone()));
+      let font_layout = font.layout_scaled(font_size, text_renderer.content_scale(), line, Some(screen_size.clone()));
 
       curr_pos.y -= font_size as f32;
       text_renderer.render_layout(camera_render_info, &font_layout, &font, curr_pos);
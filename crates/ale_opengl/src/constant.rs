@@ -12,3 +12,6 @@ pub const ROUGHNESS: &str = "roughness";
 pub const AO: &str = "ao";
 
 pub const CAMERA_POSITION: &str = "camPos";
+pub const IRRADIANCE_MAP: &str = "irradianceMap";
+pub const LIGHT_POSITIONS: &str = "lightPositions";
+pub const LIGHT_COLORS: &str = "lightColors";
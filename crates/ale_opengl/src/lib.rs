@@ -1,4 +1,5 @@
 use ale_math::color::Color;
+use ale_math::Vector2;
 
 pub mod console;
 pub mod constant;
@@ -36,8 +37,21 @@ pub fn ale_opengl_blend_enable() {
   }
 }
 
+pub fn ale_opengl_viewport_set(size: Vector2<u32>) {
+  unsafe {
+    raw::set_viewport(0, 0, size.x, size.y);
+  }
+}
+
 pub fn ale_opengl_depth_test_enable() {
   unsafe {
     raw::enable_depth_test();
   }
 }
+
+// Call before clearing, the depth clear value and clip depth range change too
+pub fn ale_opengl_depth_reverse_z_set(is_reverse_z: bool) {
+  unsafe {
+    raw::set_depth_reverse_z(is_reverse_z);
+  }
+}
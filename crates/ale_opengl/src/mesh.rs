@@ -21,6 +21,13 @@ impl OpenGLMesh {
     })
   }
 
+  // Frees the gpu buffers, there's no Drop since the gl context may already be gone
+  pub fn delete(self) {
+    unsafe {
+      raw::delete_buffer(self.vao, self._vbo, self.ebo);
+    }
+  }
+
   pub fn activate(&self) {
     unsafe {
       raw::bind_vao(self.vao);
@@ -5,18 +5,18 @@ use ale_resources::{struct_id, struct_id_impl};
 
 pub struct Cubemap {
   id: CubemapId,
-  dimension: Rect,
+  dimension: Rect<i32>,
 }
 
 impl Cubemap {
-  pub fn new(dimension: Rect) -> Cubemap {
+  pub fn new(dimension: Rect<i32>) -> Cubemap {
     Cubemap {
       id: CubemapId::new(),
       dimension,
     }
   }
 
-  pub fn get_dimension(&self) -> &Rect {
+  pub fn get_dimension(&self) -> &Rect<i32> {
     &self.dimension
   }
 }
@@ -32,8 +32,8 @@ impl CubemapDrawInfo {
   pub fn new(cubemap: &Cubemap) -> Result<CubemapDrawInfo, CubemapError> {
     let cubemap_internal = unsafe {
       raw::create_cubemap(
-        cubemap.get_dimension().size.x,
-        cubemap.get_dimension().size.y,
+        cubemap.get_dimension().size.x as u32,
+        cubemap.get_dimension().size.y as u32,
       )
     };
     Ok(CubemapDrawInfo {
@@ -100,8 +100,8 @@ enum Renderable {
     cube_mesh_id: MeshId,
     projection_target: ProjectionTarget,
     cubemap_id: CubemapId,
-    projection_dimension: Rect,
-    original_dimension: Rect,
+    projection_dimension: Rect<i32>,
+    original_dimension: Rect<i32>,
 
     #[allow(dead_code)]
     shader_variables: Vec<Variable>,
@@ -137,8 +137,8 @@ pub trait RenderTasks {
     cube_mesh_id: MeshId,
     projection_target: ProjectionTarget,
     cubemap_id: CubemapId,
-    projection_dimension: Rect,
-    original_dimension: Rect,
+    projection_dimension: Rect<i32>,
+    original_dimension: Rect<i32>,
     shader_variables: Vec<Variable>,
   );
 
@@ -209,8 +209,8 @@ impl RenderTasks for SimpleRenderTasks {
     cube_mesh_id: MeshId,
     projection_target: ProjectionTarget,
     cubemap_id: CubemapId,
-    projection_dimension: Rect,
-    original_dimension: Rect,
+    projection_dimension: Rect<i32>,
+    original_dimension: Rect<i32>,
     shader_variables: Vec<Variable>,
   ) {
     self.renderables.push(Renderable::EquirectCubemapProjection {
@@ -339,7 +339,7 @@ impl RenderTasks for SimpleRenderTasks {
 
           unsafe {
             let (framebuffer, _) =
-              raw::create_framebuffer_cubemap(projection_dimension.size.x, projection_dimension.size.y);
+              raw::create_framebuffer_cubemap(projection_dimension.size.x as u32, projection_dimension.size.y as u32);
 
             raw::use_shader(equirect_shader);
             raw::uniform1i(equirect_shader, "equirectangularMaps", 0);
@@ -357,7 +357,7 @@ impl RenderTasks for SimpleRenderTasks {
               }
             };
 
-            raw::set_viewport(
+            raw::set_viewport_i32(
               projection_dimension.position.x,
               projection_dimension.position.y,
               projection_dimension.size.x,
@@ -376,7 +376,7 @@ impl RenderTasks for SimpleRenderTasks {
             raw::bind_vao(0);
             // unbind framebuffer
             raw::bind_framebuffer(0);
-            raw::set_viewport(0, 0, original_dimension.size.x, original_dimension.size.y);
+            raw::set_viewport_i32(0, 0, original_dimension.size.x, original_dimension.size.y);
           }
         }
         Renderable::Skybox {
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use ale_camera::CameraRenderInfo;
+use ale_math::color::Color;
 use ale_math::rect::Rect;
 use ale_math::transform::AleTransform;
 use ale_math::{perspective, Array, Deg, EuclideanSpace, Matrix, Matrix4, Point3, Vector2, Vector3, Zero};
@@ -33,7 +34,7 @@ pub struct OpenGLPBRContext {
   pub mesh: HashMap<MeshId, OpenGLMesh>,
 }
 
-pub fn ale_opengl_pbr_context_new(hdr_texture: &Texture, viewport_size: &Rect, meshes: Vec<&Mesh>) -> OpenGLPBRContext {
+pub fn ale_opengl_pbr_context_new(hdr_texture: &Texture, viewport_size: &Rect<i32>, meshes: Vec<&Mesh>) -> OpenGLPBRContext {
   let cube_mesh = OpenGLMesh::new(&Mesh::new_cube()).unwrap();
   let pbr_shader = OpenGLShader::new(&Shader::new(
     include_str!("../../../resources/shaders/pbr.vert").to_owned(),
@@ -62,7 +63,7 @@ pub fn ale_opengl_pbr_context_new(hdr_texture: &Texture, viewport_size: &Rect, m
     position: Vector2::zero(),
     size: Vector2::new(512, 512),
   };
-  let cubemap_id = unsafe { raw::create_cubemap(cubemap_size.size.x, cubemap_size.size.y) };
+  let cubemap_id = unsafe { raw::create_cubemap(cubemap_size.size.x as u32, cubemap_size.size.y as u32) };
 
   intern_opengl_pbr_equirect_project(
     &equirect_shader,
@@ -79,8 +80,8 @@ pub fn ale_opengl_pbr_context_new(hdr_texture: &Texture, viewport_size: &Rect, m
   };
   let convoluted_cubemap_id = unsafe {
     raw::create_cubemap(
-      convoluted_cubemap_size.size.x,
-      convoluted_cubemap_size.size.y,
+      convoluted_cubemap_size.size.x as u32,
+      convoluted_cubemap_size.size.y as u32,
     )
   };
 
@@ -113,7 +114,7 @@ pub fn ale_opengl_pbr_context_new(hdr_texture: &Texture, viewport_size: &Rect, m
 
 pub fn ale_opengl_pbr_render(
   opengl_pbr_context: &OpenGLPBRContext,
-  mesh: Vec<(&mut AleTransform, &mut MeshId, &Vector3<f32>)>,
+  mesh: Vec<(&mut AleTransform, &mut MeshId, &Color)>,
   camera_render_info: &CameraRenderInfo,
   textures: &Vec<OpenGLTexture>,
 ) {
@@ -152,7 +153,7 @@ pub fn ale_opengl_pbr_render(
     raw::matrix4f(pbr_shader.id, PROJECTION, camera_render_info.projection.as_ptr());
 
     for (t, m, color) in mesh {
-      pbr_shader.activate(&vec![Variable::F32_3("albedo".to_owned(), *color)]);
+      pbr_shader.activate(&vec![Variable::F32_3("albedo".to_owned(), (*color).into())]);
 
       let ogl_mesh = opengl_pbr_context
         .mesh
@@ -200,7 +201,7 @@ pub fn ale_opengl_pbr_render_debug(
   opengl_pbr_context: &OpenGLPBRContext,
   point: Vector3<f32>,
   size: f32,
-  color: Vector3<f32>,
+  color: Color,
   camera_render_info: &CameraRenderInfo,
 ) {
   unsafe {
@@ -209,7 +210,7 @@ pub fn ale_opengl_pbr_render_debug(
 
     // Bind shader
     pbr_shader.activate(&vec![
-      Variable::F32_3("albedo".to_owned(), color),
+      Variable::F32_3("albedo".to_owned(), color.into()),
       Variable::F32_1("metallic".to_owned(), 0.0f32),
       Variable::F32_1("roughness".to_owned(), 0.5f32),
       Variable::F32_1("ao".to_owned(), 0.5f32),
@@ -256,8 +257,8 @@ pub enum ProjectionTarget<'a> {
 fn intern_opengl_pbr_equirect_project(
   equirect_shader: &OpenGLShader,
   projection_target: ProjectionTarget,
-  original_dimension: &Rect,
-  projection_dimension: &Rect,
+  original_dimension: &Rect<i32>,
+  projection_dimension: &Rect<i32>,
   cubemap_id: u32,
   cube_mesh: &OpenGLMesh,
 ) {
@@ -273,7 +274,7 @@ fn intern_opengl_pbr_equirect_project(
 
   unsafe {
     let (framebuffer, _) =
-      raw::create_framebuffer_cubemap(projection_dimension.size.x, projection_dimension.size.y);
+      raw::create_framebuffer_cubemap(projection_dimension.size.x as u32, projection_dimension.size.y as u32);
 
     raw::use_shader(equirect_shader.id);
     raw::uniform1i(equirect_shader.id, "equirectangularMaps", 0);
@@ -289,7 +290,7 @@ fn intern_opengl_pbr_equirect_project(
       }
     };
 
-    raw::set_viewport(
+    raw::set_viewport_i32(
       projection_dimension.position.x,
       projection_dimension.position.y,
       projection_dimension.size.x,
@@ -308,6 +309,6 @@ fn intern_opengl_pbr_equirect_project(
     raw::bind_vao(0);
     // unbind framebuffer
     raw::bind_framebuffer(0);
-    raw::set_viewport(0, 0, original_dimension.size.x, original_dimension.size.y);
+    raw::set_viewport_i32(0, 0, original_dimension.size.x, original_dimension.size.y);
   }
 }
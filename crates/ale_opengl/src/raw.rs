@@ -2,9 +2,11 @@ use core::{mem, ptr};
 use std::convert::TryInto;
 use std::ffi::{c_void, CString};
 use std::ptr::null;
+use std::sync::Once;
 
 pub use gl::load_with;
 use gl::types::{GLchar, GLfloat, GLint, GLsizeiptr};
+use log::warn;
 use thiserror::Error;
 
 use ale_data::buffer::Buffer;
@@ -38,6 +40,31 @@ pub unsafe fn enable_depth_test() {
   gl::DepthFunc(gl::LEQUAL);
 }
 
+// 0..1 clip depth, GEQUAL and clearing to 0 for reverse-Z projections, -1..1, LEQUAL and 1 otherwise.
+// Without glClipControl (GL 4.5 or ARB_clip_control) the depth test still orders correctly,
+// only half of the depth buffer range is used, which is warned about once.
+pub unsafe fn set_depth_reverse_z(is_reverse_z: bool) {
+  static NO_CLIP_CONTROL_WARNING: Once = Once::new();
+
+  if is_reverse_z {
+    if gl::ClipControl::is_loaded() {
+      gl::ClipControl(gl::LOWER_LEFT, gl::ZERO_TO_ONE);
+    } else {
+      NO_CLIP_CONTROL_WARNING.call_once(|| {
+        warn!("reverse-Z without glClipControl (GL 4.5 or ARB_clip_control), depth precision is not improved")
+      });
+    }
+    gl::DepthFunc(gl::GEQUAL);
+    gl::ClearDepth(0.0);
+  } else {
+    if gl::ClipControl::is_loaded() {
+      gl::ClipControl(gl::LOWER_LEFT, gl::NEGATIVE_ONE_TO_ONE);
+    }
+    gl::DepthFunc(gl::LEQUAL);
+    gl::ClearDepth(1.0);
+  }
+}
+
 pub unsafe fn disable_depth_test() {
   gl::Disable(gl::DEPTH_TEST);
 }
@@ -46,6 +73,10 @@ pub unsafe fn use_shader(shader: u32) {
   gl::UseProgram(shader);
 }
 
+pub unsafe fn delete_shader(shader: u32) {
+  gl::DeleteProgram(shader);
+}
+
 pub unsafe fn active_texture(texture_slot_offset: u32) {
   gl::ActiveTexture(gl::TEXTURE0 + texture_slot_offset);
 }
@@ -182,6 +213,14 @@ pub unsafe fn create_buffer(
   Ok((vao, vbo, ebo, draw_size))
 }
 
+pub unsafe fn delete_buffer(vao: u32, vbo: u32, ebo: Option<u32>) {
+  gl::DeleteVertexArrays(1, &vao);
+  gl::DeleteBuffers(1, &vbo);
+  if let Some(ebo) = ebo {
+    gl::DeleteBuffers(1, &ebo);
+  }
+}
+
 #[derive(Error, Debug)]
 pub enum CreateShaderError {
   #[error("(CreateShaderError::VertexShaderError)\nCause: {}", .0)]
@@ -354,6 +393,10 @@ pub unsafe fn create_texture(texture: &Texture) -> Result<OpenGLTextureId, Creat
   return Ok(OpenGLTextureId(gl_texture));
 }
 
+pub unsafe fn delete_texture(texture: &OpenGLTextureId) {
+  gl::DeleteTextures(1, &texture.0);
+}
+
 fn texture_wrap_to_gl(wrap: &TextureWrapType) -> i32 {
   match wrap {
     TextureWrapType::ClampToEdge => gl::CLAMP_TO_EDGE as i32,
@@ -392,7 +435,8 @@ pub unsafe fn create_framebuffer_texcolor_rbodepth(
   let mut rbo = 0;
   gl::GenRenderbuffers(1, &mut rbo);
   gl::BindRenderbuffer(gl::RENDERBUFFER, rbo);
-  gl::RenderbufferStorage(gl::RENDERBUFFER, gl::DEPTH24_STENCIL8, w as i32, h as i32);
+  // float depth, reverse-Z keeps its precision spread over the whole range
+  gl::RenderbufferStorage(gl::RENDERBUFFER, gl::DEPTH32F_STENCIL8, w as i32, h as i32);
   gl::FramebufferRenderbuffer(gl::FRAMEBUFFER, gl::DEPTH_STENCIL_ATTACHMENT, gl::RENDERBUFFER, rbo);
 
   let status = gl::CheckFramebufferStatus(gl::FRAMEBUFFER);
@@ -409,6 +453,16 @@ pub unsafe fn create_framebuffer_texcolor_rbodepth(
   ));
 }
 
+pub unsafe fn delete_framebuffer_texcolor_rbodepth(
+  fbo: &OpenGLFramebufferId,
+  rbo: &OpenGLRenderbufferId,
+  texture: &OpenGLTextureId,
+) {
+  gl::DeleteFramebuffers(1, &fbo.0);
+  gl::DeleteRenderbuffers(1, &rbo.0);
+  gl::DeleteTextures(1, &texture.0);
+}
+
 pub unsafe fn create_framebuffer_cubemap(w: u32, h: u32) -> (OpenGLFramebufferId, OpenGLRenderbufferId) {
   let mut fbo = 0;
   gl::GenFramebuffers(1, &mut fbo);
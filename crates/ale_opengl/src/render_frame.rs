@@ -1,6 +1,9 @@
 use crate::mesh::{OpenGLMesh};
 use crate::raw;
-use crate::raw::{bind_framebuffer, create_framebuffer_texcolor_rbodepth, OpenGLFramebufferId};
+use crate::raw::{
+  bind_framebuffer, create_framebuffer_texcolor_rbodepth, delete_framebuffer_texcolor_rbodepth, OpenGLFramebufferId,
+  OpenGLRenderbufferId,
+};
 use crate::shader::{OpenGLShader};
 use crate::texture::OpenGLTextureId;
 use ale_math::Vector2;
@@ -9,6 +12,7 @@ use ale_variable::Variable;
 
 pub struct OpenGLRenderFrameContext {
   framebuffer: OpenGLFramebufferId,
+  renderbuffer: OpenGLRenderbufferId,
 
   texture: OpenGLTextureId,
 
@@ -22,12 +26,30 @@ pub fn ale_opengl_render_frame_new(screen_size: Vector2<u32>) -> OpenGLRenderFra
 
     OpenGLRenderFrameContext {
       framebuffer: fbo,
+      renderbuffer: rbo,
       texture,
       plane_mesh,
     }
   }
 }
 
+// Reallocates the frame for the new screen size, the old contents are lost
+pub fn ale_opengl_render_frame_resize(opengl_render_frame_context: &mut OpenGLRenderFrameContext, screen_size: Vector2<u32>) {
+  unsafe {
+    delete_framebuffer_texcolor_rbodepth(
+      &opengl_render_frame_context.framebuffer,
+      &opengl_render_frame_context.renderbuffer,
+      &opengl_render_frame_context.texture,
+    );
+    let (fbo, rbo, texture) = create_framebuffer_texcolor_rbodepth(screen_size.x, screen_size.y).unwrap();
+    bind_framebuffer(0);
+
+    opengl_render_frame_context.framebuffer = fbo;
+    opengl_render_frame_context.renderbuffer = rbo;
+    opengl_render_frame_context.texture = texture;
+  }
+}
+
 pub fn ale_opengl_render_frame_capture(opengl_render_frame_context: &OpenGLRenderFrameContext, mut func: impl FnMut()) {
   unsafe {
     bind_framebuffer(opengl_render_frame_context.framebuffer.0);
@@ -38,6 +60,19 @@ pub fn ale_opengl_render_frame_capture(opengl_render_frame_context: &OpenGLRende
   }
 }
 
+// Same as capture, but for callers that can't wrap their draw calls in a closure
+pub fn ale_opengl_render_frame_begin(opengl_render_frame_context: &OpenGLRenderFrameContext) {
+  unsafe {
+    bind_framebuffer(opengl_render_frame_context.framebuffer.0);
+  }
+}
+
+pub fn ale_opengl_render_frame_end() {
+  unsafe {
+    bind_framebuffer(0);
+  }
+}
+
 pub fn ale_opengl_render_frame_render(
   opengl_render_frame_context: &OpenGLRenderFrameContext,
   opengl_shader: &OpenGLShader,
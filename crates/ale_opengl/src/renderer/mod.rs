@@ -1,4 +1,5 @@
 pub mod renderframe;
 pub mod sprite;
+pub mod static_mesh;
 pub mod task;
 pub mod text;
@@ -1,13 +1,14 @@
 use thiserror::Error;
 
 use crate::constant::{COLOR, POSITION_SIZE, PROJECTION};
+use ale_data::alevec::Key;
 use ale_math::color::Color;
 use ale_math::{Array, Matrix4, Vector2, Vector4};
 use ale_resources::mesh::Mesh;
 use ale_resources::resources::Resources;
 use ale_resources::shader;
 use ale_resources::shader::Shader;
-use ale_variable::Variable;
+use ale_variable::{ToVariable, Variable};
 
 use crate::mesh::{OpenGLMesh, OpenGLMeshError};
 use crate::raw;
@@ -17,6 +18,8 @@ use crate::shader::{OpenGLShader, OpenGLShaderError};
 pub struct SpriteRenderer {
   plane_mesh: OpenGLMesh,
   flat_shader: OpenGLShader,
+  // Set when the shader comes from resources, used to match hot reloads
+  flat_shader_key: Option<Key<Shader>>,
 }
 
 impl SpriteRenderer {
@@ -24,10 +27,20 @@ impl SpriteRenderer {
     let plane_mesh_key = resources.meshes.register(Mesh::new_plane());
     let flat_shader_key = resources.shaders.stash.load("shaders/ui")?.remove(0);
 
-    SpriteRenderer::new(
+    let mut sprite_renderer = SpriteRenderer::new(
       resources.meshes.get(plane_mesh_key).unwrap(),
       resources.shaders.stash.get(flat_shader_key).unwrap(),
-    )
+    )?;
+    sprite_renderer.flat_shader_key = Some(flat_shader_key);
+    Ok(sprite_renderer)
+  }
+
+  // Recompiles the flat shader if key is the one this renderer was created from
+  pub fn reload_shader(&mut self, key: Key<Shader>, shader: &Shader) -> Result<(), OpenGLShaderError> {
+    if self.flat_shader_key != Some(key) {
+      return Ok(());
+    }
+    self.flat_shader.reload(shader)
   }
 
   pub fn new(plane_mesh: &Mesh, flat_shader: &Shader) -> Result<SpriteRenderer, SpriteRendererError> {
@@ -37,6 +50,7 @@ impl SpriteRenderer {
     Ok(SpriteRenderer {
       plane_mesh,
       flat_shader,
+      flat_shader_key: None,
     })
   }
 
@@ -47,7 +61,7 @@ impl SpriteRenderer {
         POSITION_SIZE.to_owned(),
         Vector4::new(position.x, position.y, size.x, size.y),
       ),
-      Variable::F32_4(COLOR.to_owned(), Vector4::new(color.r, color.g, color.b, color.a)),
+      color.to_variable(COLOR),
       Variable::F32_4_4(PROJECTION.to_owned(), ortho),
     ]);
     self.plane_mesh.draw();
@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+use ale_camera::CameraRenderInfo;
+use ale_data::alevec::Key;
+use ale_math::{Matrix, Vector3};
+use ale_resources::mesh::Mesh;
+use ale_resources::resources::Resources;
+use ale_resources::shader;
+use ale_resources::shader::Shader;
+use ale_variable::Variable;
+
+use crate::constant::{
+  ALBEDO, AO, CAMERA_POSITION, IRRADIANCE_MAP, LIGHT_COLORS, LIGHT_POSITIONS, METALLIC, MODEL, PROJECTION, ROUGHNESS,
+  VIEW,
+};
+use crate::mesh::{OpenGLMesh, OpenGLMeshError};
+use crate::pbr::OpenGLPBRContext;
+use crate::raw;
+use crate::shader::{OpenGLShader, OpenGLShaderError};
+use crate::renderer::task::{Material, StaticMesh};
+
+// Size of the lightPositions and lightColors arrays in shaders/pbr.frag
+pub const PBR_MAX_LIGHTS: usize = 4;
+
+// Point light for Material::Pbr, color is the radiance at distance 1, it falls off with the distance squared
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PbrLight {
+  pub position: Vector3<f32>,
+  pub color: Vector3<f32>,
+}
+
+// Draws RenderTask::StaticMesh, gpu meshes and shaders are created the first time a key is drawn
+pub struct StaticMeshRenderer {
+  opengl_pbr_context: Option<OpenGLPBRContext>,
+  pbr_lights: Vec<PbrLight>,
+  // Set when the pbr shader comes from resources, used to match hot reloads
+  pbr_shader_key: Option<Key<Shader>>,
+
+  meshes: HashMap<Key<Mesh>, OpenGLMesh>,
+  shaders: HashMap<Key<Shader>, OpenGLShader>,
+}
+
+impl StaticMeshRenderer {
+  pub fn new() -> StaticMeshRenderer {
+    StaticMeshRenderer {
+      opengl_pbr_context: None,
+      pbr_lights: vec![],
+      pbr_shader_key: None,
+      meshes: HashMap::new(),
+      shaders: HashMap::new(),
+    }
+  }
+
+  // Same as new, the pbr context's shader is then recompiled when shaders/pbr is hot reloaded
+  pub fn new_with_resource(resources: &mut Resources) -> Result<StaticMeshRenderer, StaticMeshRendererError> {
+    let mut static_mesh_renderer = StaticMeshRenderer::new();
+    static_mesh_renderer.pbr_shader_key = Some(resources.shaders.stash.load("shaders/pbr")?.remove(0));
+    Ok(static_mesh_renderer)
+  }
+
+  // Material::Pbr can't be drawn until a pbr context (environment map) is set
+  pub fn set_pbr_context(&mut self, opengl_pbr_context: OpenGLPBRContext) {
+    self.opengl_pbr_context = Some(opengl_pbr_context);
+  }
+
+  pub fn pbr_context(&self) -> Option<&OpenGLPBRContext> {
+    self.opengl_pbr_context.as_ref()
+  }
+
+  // Lights every Material::Pbr draw, only the first PBR_MAX_LIGHTS are used.
+  // Without lights pbr meshes are only lit by the environment map.
+  pub fn set_pbr_lights(&mut self, pbr_lights: Vec<PbrLight>) {
+    self.pbr_lights = pbr_lights;
+  }
+
+  pub fn pbr_lights(&self) -> &[PbrLight] {
+    &self.pbr_lights
+  }
+
+  pub fn render(
+    &mut self,
+    resources: &mut Resources,
+    static_mesh: &StaticMesh,
+    camera_render_info: &CameraRenderInfo,
+  ) -> Result<(), StaticMeshRendererError> {
+    if !self.meshes.contains_key(&static_mesh.mesh) {
+      let mesh = resources
+        .meshes
+        .get(static_mesh.mesh)
+        .ok_or(StaticMeshRendererError::MeshNotFound)?;
+      self.meshes.insert(static_mesh.mesh, OpenGLMesh::new(mesh)?);
+    }
+    let opengl_mesh = &self.meshes[&static_mesh.mesh];
+
+    let shader = match static_mesh.material {
+      Material::Pbr => {
+        let opengl_pbr_context = self
+          .opengl_pbr_context
+          .as_ref()
+          .ok_or(StaticMeshRendererError::PbrContextMissing)?;
+        let shader = &opengl_pbr_context.pbr_shader;
+
+        let mut variables = vec![
+          Variable::F32_3(ALBEDO.to_owned(), Vector3::new(0.7f32, 0.7, 0.7)),
+          Variable::F32_1(METALLIC.to_owned(), 0.0f32),
+          Variable::F32_1(ROUGHNESS.to_owned(), 0.5f32),
+          Variable::F32_1(AO.to_owned(), 0.5f32),
+        ];
+        variables.extend(intern_pbr_light_variables(&self.pbr_lights));
+        shader.activate(&variables);
+        unsafe {
+          raw::uniform1i(shader.id, IRRADIANCE_MAP, 0);
+          raw::active_texture(0);
+          raw::bind_cubemap(opengl_pbr_context.convoluted_cubemap);
+        }
+        shader
+      }
+      Material::Shader(shader_key) => {
+        if !self.shaders.contains_key(&shader_key) {
+          let shader = resources
+            .shaders
+            .stash
+            .get(shader_key)
+            .ok_or(StaticMeshRendererError::ShaderNotFound)?;
+          self.shaders.insert(shader_key, OpenGLShader::new(shader)?);
+        }
+        &self.shaders[&shader_key]
+      }
+    };
+
+    shader.activate(&static_mesh.variables);
+    unsafe {
+      let camera_position = camera_render_info.position;
+      raw::uniform3f(
+        shader.id,
+        CAMERA_POSITION,
+        camera_position.x,
+        camera_position.y,
+        camera_position.z,
+      );
+      raw::matrix4f(shader.id, VIEW, camera_render_info.view.as_ptr());
+      raw::matrix4f(shader.id, PROJECTION, camera_render_info.projection.as_ptr());
+      raw::matrix4f(shader.id, MODEL, static_mesh.transform.as_ptr());
+    }
+
+    opengl_mesh.activate();
+    opengl_mesh.draw();
+    Ok(())
+  }
+
+  // Recompiles a cached material shader or the pbr shader, the old program is kept if compilation fails
+  pub fn reload_shader(&mut self, key: Key<Shader>, shader: &Shader) -> Result<(), OpenGLShaderError> {
+    if self.pbr_shader_key == Some(key) {
+      return match &mut self.opengl_pbr_context {
+        None => Ok(()),
+        Some(opengl_pbr_context) => opengl_pbr_context.pbr_shader.reload(shader),
+      };
+    }
+    match self.shaders.get_mut(&key) {
+      None => Ok(()),
+      Some(opengl_shader) => opengl_shader.reload(shader),
+    }
+  }
+
+  // Recreated the next time it's drawn
+  pub fn reload_mesh(&mut self, key: Key<Mesh>) {
+    if let Some(opengl_mesh) = self.meshes.remove(&key) {
+      opengl_mesh.delete();
+    }
+  }
+}
+
+// Every slot is set, unused ones get a black light so lights from a previous draw don't linger
+fn intern_pbr_light_variables(pbr_lights: &[PbrLight]) -> Vec<Variable> {
+  let mut variables = vec![];
+  for i in 0..PBR_MAX_LIGHTS {
+    let (position, color) = match pbr_lights.get(i) {
+      None => (Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 0.0, 0.0)),
+      Some(pbr_light) => (pbr_light.position, pbr_light.color),
+    };
+    variables.push(Variable::F32_3(format!("{}[{}]", LIGHT_POSITIONS, i), position));
+    variables.push(Variable::F32_3(format!("{}[{}]", LIGHT_COLORS, i), color));
+  }
+  variables
+}
+
+#[derive(Error, Debug)]
+pub enum StaticMeshRendererError {
+  #[error("(StaticMeshRendererError::MeshNotFound)")]
+  MeshNotFound,
+  #[error("(StaticMeshRendererError::ShaderNotFound)")]
+  ShaderNotFound,
+  #[error("(StaticMeshRendererError::PbrContextMissing) set_pbr_context before drawing Material::Pbr")]
+  PbrContextMissing,
+  #[error("(StaticMeshRendererError::OpenGLMeshError) {}", .0)]
+  OpenGLMeshError(#[from] OpenGLMeshError),
+  #[error("(StaticMeshRendererError::OpenGLShaderError) {}", .0)]
+  OpenGLShaderError(#[from] OpenGLShaderError),
+  #[error("(StaticMeshRendererError::ShaderLoadError) {}", .0)]
+  ShaderLoadError(#[from] shader::LoadError),
+}
+
+#[test]
+fn static_mesh_pbr_light_variables() {
+  let pbr_light = PbrLight {
+    position: Vector3::new(1.0, 2.0, 3.0),
+    color: Vector3::new(10.0, 10.0, 10.0),
+  };
+  let pbr_lights = vec![pbr_light; PBR_MAX_LIGHTS + 1];
+  let variables = intern_pbr_light_variables(&pbr_lights[..1]);
+  assert_eq!(variables.len(), PBR_MAX_LIGHTS * 2);
+  let is_light = |variable: &Variable, expected_name: &str, expected: Vector3<f32>| match variable {
+    Variable::F32_3(name, value) => name == expected_name && *value == expected,
+    _ => false,
+  };
+  assert!(is_light(&variables[0], "lightPositions[0]", pbr_light.position));
+  assert!(is_light(&variables[1], "lightColors[0]", pbr_light.color));
+  assert!(is_light(&variables[7], "lightColors[3]", Vector3::new(0.0, 0.0, 0.0)));
+
+  // the shader only has room for PBR_MAX_LIGHTS
+  assert_eq!(intern_pbr_light_variables(&pbr_lights).len(), PBR_MAX_LIGHTS * 2);
+}
@@ -1,14 +1,32 @@
 use ale_data::alevec::Key;
 use ale_math::color::Color;
-use ale_math::Vector2;
+use ale_math::{Matrix4, Vector2};
+use ale_resources::mesh::Mesh;
+use ale_resources::shader::Shader;
 use ale_resources::texture::Texture;
+use ale_variable::Variable;
 
 pub enum RenderTask {
   StaticMesh(StaticMesh),
   Sprite(Sprite),
 }
 
-pub struct StaticMesh {}
+pub enum Material {
+  // Drawn with the pbr context and its lights (StaticMeshRenderer::set_pbr_lights),
+  // variables can override albedo, metallic, roughness and ao
+  Pbr,
+  // Any shader from resources, receives model, view, projection and camPos
+  Shader(Key<Shader>),
+}
+
+pub struct StaticMesh {
+  pub mesh: Key<Mesh>,
+  pub material: Material,
+  // world transform
+  pub transform: Matrix4<f32>,
+  // per draw shader variables
+  pub variables: Vec<Variable>,
+}
 
 pub struct Sprite {
   pub texture_sprite: Option<Key<Texture>>,
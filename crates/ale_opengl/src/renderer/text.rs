@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use thiserror::Error;
 
 use ale_camera::CameraRenderInfo;
+use ale_data::alevec::Key;
 use ale_math::color::Color;
 use ale_math::Vector2;
 use ale_resources::font::{Font, FontLayout, FontTextureKey};
@@ -13,13 +14,17 @@ use ale_resources::shader::Shader;
 
 use crate::mesh::OpenGLMesh;
 use crate::raw;
-use crate::shader::OpenGLShader;
+use crate::shader::{OpenGLShader, OpenGLShaderError};
 use crate::texture::OpenGLTexture;
 
 pub struct TextRenderer {
   text_shader: OpenGLShader,
+  // Set when the shader comes from resources, used to match hot reloads
+  text_shader_key: Option<Key<Shader>>,
   plane_mesh: OpenGLMesh,
   glyph_texture: HashMap<FontTextureKey, OpenGLTexture>,
+  // font sizes are logical, glyphs are rasterized for this many pixels per unit
+  content_scale: f32,
 }
 
 impl TextRenderer {
@@ -27,10 +32,20 @@ impl TextRenderer {
     let text_shader_key = resources.shaders.stash.load("shaders/text_2d")?.remove(0);
     let plane_mesh_key = resources.meshes.register(Mesh::new_plane());
 
-    TextRenderer::new(
+    let mut text_renderer = TextRenderer::new(
       resources.shaders.stash.get(text_shader_key).unwrap(),
       resources.meshes.get(plane_mesh_key).unwrap(),
-    )
+    )?;
+    text_renderer.text_shader_key = Some(text_shader_key);
+    Ok(text_renderer)
+  }
+
+  // Recompiles the text shader if key is the one this renderer was created from
+  pub fn reload_shader(&mut self, key: Key<Shader>, shader: &Shader) -> Result<(), OpenGLShaderError> {
+    if self.text_shader_key != Some(key) {
+      return Ok(());
+    }
+    self.text_shader.reload(shader)
   }
 
   pub fn new(text_shader: &Shader, plane_mesh: &Mesh) -> Result<TextRenderer, TextRendererError> {
@@ -39,11 +54,23 @@ impl TextRenderer {
 
     Ok(TextRenderer {
       text_shader,
+      text_shader_key: None,
       plane_mesh,
       glyph_texture: Default::default(),
+      content_scale: 1.0,
     })
   }
 
+  pub fn content_scale(&self) -> f32 {
+    self.content_scale
+  }
+
+  pub fn set_content_scale(&mut self, content_scale: f32) {
+    if content_scale > 0.0 {
+      self.content_scale = content_scale;
+    }
+  }
+
   pub fn render(
     &mut self,
     camera_render_info: &CameraRenderInfo,
@@ -53,7 +80,7 @@ impl TextRenderer {
     text: &str,
     bounds: Option<Vector2<i32>>,
   ) {
-    let layout = font.layout(font_size, text, bounds);
+    let layout = font.layout_scaled(font_size, self.content_scale, text, bounds);
     self.render_layout(camera_render_info, &layout, font, origin);
   }
 
@@ -76,10 +103,12 @@ impl TextRenderer {
 
       let opengl_texture = self.glyph_texture.get(&l.font_texture_key).unwrap();
 
-      opengl_texture.render(
+      let content_scale = font_layout.content_scale;
+      opengl_texture.render_sized(
         &self.plane_mesh,
         &self.text_shader,
-        origin + Vector2::new(l.offset_min.x as f32, l.offset_min.y as f32),
+        origin + Vector2::new(l.offset_min.x as f32, l.offset_min.y as f32) / content_scale,
+        Vector2::new(opengl_texture.width as f32, opengl_texture.height as f32) / content_scale,
         &Color::from_rgb(1.0, 1.0, 1.0),
         &camera_render_info,
       );
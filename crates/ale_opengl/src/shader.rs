@@ -23,6 +23,16 @@ impl OpenGLShader {
     Ok(OpenGLShader { id: shader })
   }
 
+  // Recompiles the program, the old program is kept if compilation fails
+  pub fn reload(&mut self, shader: &Shader) -> Result<(), OpenGLShaderError> {
+    let new_shader = OpenGLShader::new(shader)?;
+    unsafe {
+      raw::delete_shader(self.id);
+    }
+    self.id = new_shader.id;
+    Ok(())
+  }
+
   pub fn activate(&self, shader_vars: &Vec<Variable>) {
     unsafe {
       raw::use_shader(self.id);
@@ -31,6 +31,21 @@ impl OpenGLTexture {
     }
   }
 
+  // Uploads the texture again, the old gpu texture is kept if the upload fails
+  pub fn reload(&mut self, texture: &Texture) -> Result<(), OpenGLTextureError> {
+    let new_texture = OpenGLTexture::new(texture)?;
+    self.delete();
+    *self = new_texture;
+    Ok(())
+  }
+
+  // Frees the gpu texture, there's no Drop since the gl context may already be gone
+  pub fn delete(&self) {
+    unsafe {
+      raw::delete_texture(&self.id);
+    }
+  }
+
   pub fn render(
     &self,
     opengl_mesh_plane: &OpenGLMesh,
@@ -38,6 +53,26 @@ impl OpenGLTexture {
     position: Vector2<f32>,
     color: &Color,
     camera: &CameraRenderInfo,
+  ) {
+    self.render_sized(
+      opengl_mesh_plane,
+      opengl_shader_sprite,
+      position,
+      Vector2::new(self.width as f32, self.height as f32),
+      color,
+      camera,
+    );
+  }
+
+  // size in the orthographic camera's units instead of the texture's pixels
+  pub fn render_sized(
+    &self,
+    opengl_mesh_plane: &OpenGLMesh,
+    opengl_shader_sprite: &OpenGLShader,
+    position: Vector2<f32>,
+    size: Vector2<f32>,
+    color: &Color,
+    camera: &CameraRenderInfo,
   ) {
     unsafe {
       raw::bind_vao(opengl_mesh_plane.vao);
@@ -50,8 +85,8 @@ impl OpenGLTexture {
         "possize",
         position.x,
         position.y,
-        self.width as f32,
-        self.height as f32,
+        size.x,
+        size.y,
       );
 
       let (r, g, b, a) = color.get_rgba();
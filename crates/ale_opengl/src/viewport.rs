@@ -1,11 +1,11 @@
 use ale_math::rect::Rect;
 
 pub struct Viewport {
-    rect : Rect
+    rect : Rect<i32>
 }
 
 impl Viewport {
-    pub fn new(rect: Rect) -> Viewport {
+    pub fn new(rect: Rect<i32>) -> Viewport {
         return Viewport {
             rect
         }
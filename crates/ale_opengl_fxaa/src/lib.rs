@@ -5,6 +5,9 @@ use ale_opengl::shader::OpenGLShader;
 use ale_resources::shader::Shader;
 use ale_variable::{to_variable, ToVariable};
 
+#[cfg(feature = "plugin")]
+pub mod plugin;
+
 pub struct OpenGLFXAAContext {
   // The main fxaa shader
   pub(crate) fxaa_shader: OpenGLShader,
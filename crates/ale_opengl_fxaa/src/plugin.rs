@@ -0,0 +1,84 @@
+use ale_app::engine::Engine;
+use ale_app::plugin::Plugin;
+use ale_app::AppError;
+use ale_camera::CameraRenderInfo;
+use ale_input::Input;
+use ale_math::Vector2;
+use ale_opengl::render_frame::{
+  ale_opengl_render_frame_begin, ale_opengl_render_frame_end, ale_opengl_render_frame_new,
+  ale_opengl_render_frame_resize, OpenGLRenderFrameContext,
+};
+use ale_world::world::World;
+
+use crate::{
+  ale_opengl_fxaa_console_variable_refresh, ale_opengl_fxaa_console_variable_register, ale_opengl_fxaa_context_new,
+  ale_opengl_fxaa_render, OpenGLFXAAContext,
+};
+
+// Renders the whole frame into an offscreen frame, then resolves it to the screen with fxaa
+pub struct FXAAPlugin {
+  opengl_fxaa_context: Option<OpenGLFXAAContext>,
+  opengl_render_frame_context: Option<OpenGLRenderFrameContext>,
+}
+
+impl FXAAPlugin {
+  pub fn new() -> FXAAPlugin {
+    FXAAPlugin {
+      opengl_fxaa_context: None,
+      opengl_render_frame_context: None,
+    }
+  }
+}
+
+impl Default for FXAAPlugin {
+  fn default() -> FXAAPlugin {
+    FXAAPlugin::new()
+  }
+}
+
+impl Plugin for FXAAPlugin {
+  fn init(&mut self, engine: &mut Engine, _world: &mut World) -> Result<(), AppError> {
+    let framebuffer_size = match engine.windows.iter().next() {
+      Some(window) if engine.windows.has_gl_context() => window.get_framebuffer_size(),
+      _ => return Ok(()),
+    };
+
+    let opengl_fxaa_context = ale_opengl_fxaa_context_new();
+    ale_opengl_fxaa_console_variable_register(&opengl_fxaa_context, &mut engine.console);
+
+    self.opengl_fxaa_context = Some(opengl_fxaa_context);
+    self.opengl_render_frame_context = Some(ale_opengl_render_frame_new(framebuffer_size));
+    Ok(())
+  }
+
+  fn tick(&mut self, engine: &mut Engine, _world: &mut World, _delta_time: f32) {
+    if let Some(opengl_fxaa_context) = &mut self.opengl_fxaa_context {
+      ale_opengl_fxaa_console_variable_refresh(opengl_fxaa_context, &mut engine.console);
+    }
+  }
+
+  fn input(&mut self, _engine: &mut Engine, inputs: &Vec<Input>) {
+    for input in inputs {
+      if let (Input::FramebufferResize { width, height }, Some(opengl_render_frame_context)) =
+        (input, &mut self.opengl_render_frame_context)
+      {
+        ale_opengl_render_frame_resize(opengl_render_frame_context, Vector2::new(*width, *height));
+      }
+    }
+  }
+
+  fn pre_render(&mut self, _engine: &mut Engine) {
+    if let Some(opengl_render_frame_context) = &self.opengl_render_frame_context {
+      ale_opengl_render_frame_begin(opengl_render_frame_context);
+    }
+  }
+
+  fn post_render(&mut self, _engine: &mut Engine, _camera_render_info: &CameraRenderInfo) {
+    if let (Some(opengl_fxaa_context), Some(opengl_render_frame_context)) =
+      (&self.opengl_fxaa_context, &self.opengl_render_frame_context)
+    {
+      ale_opengl_render_frame_end();
+      ale_opengl_fxaa_render(opengl_fxaa_context, opengl_render_frame_context);
+    }
+  }
+}
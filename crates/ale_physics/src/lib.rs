@@ -14,6 +14,9 @@ use rapier3d::geometry::ContactEvent;
 use rapier3d::geometry::IntersectionEvent;
 use rapier3d::math::{AngVector, Isometry};
 
+#[cfg(feature = "plugin")]
+pub mod plugin;
+
 pub enum RigidBodyType {
   Kinematic,
   Dynamic,
@@ -176,13 +179,25 @@ pub fn ale_physics_context_update(
   objects: Vec<(&mut ale_math::transform::AleTransform, &RigidBodyHandle)>,
 ) {
   for (t, rigidbody_handle) in objects {
-    let rigidbody = physics_context.bodies.get(*rigidbody_handle).unwrap();
-    let position: [f32; 3] = rigidbody.position().translation.into();
-    let rotation = rigidbody.position().rotation;
-
-    t.set_position(ale_math::Vector3::new(position[0], position[1], position[2]));
-    t.set_rotation(ale_math::Quaternion::new(
-      rotation.w, rotation.i, rotation.j, rotation.k,
-    ));
+    ale_physics_object_transform_update(physics_context, *rigidbody_handle, t);
   }
 }
+
+// Copies the rigid body's position and rotation into transform, scale is left alone
+pub fn ale_physics_object_transform_update(
+  physics_context: &PhysicsContext,
+  rigidbody_handle: RigidBodyHandle,
+  transform: &mut ale_math::transform::AleTransform,
+) {
+  let rigidbody = match physics_context.bodies.get(rigidbody_handle) {
+    None => return,
+    Some(rigidbody) => rigidbody,
+  };
+  let position: [f32; 3] = rigidbody.position().translation.into();
+  let rotation = rigidbody.position().rotation;
+
+  transform.set_position(ale_math::Vector3::new(position[0], position[1], position[2]));
+  transform.set_rotation(ale_math::Quaternion::new(
+    rotation.w, rotation.i, rotation.j, rotation.k,
+  ));
+}
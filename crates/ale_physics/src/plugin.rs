@@ -0,0 +1,147 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use rapier3d::dynamics::RigidBodyHandle;
+
+use ale_app::engine::Engine;
+use ale_app::plugin::Plugin;
+use ale_math::transform::AleTransform;
+use ale_world::visitor::VisitorMut;
+use ale_world::world::World;
+
+use crate::{ale_physics_context_new, ale_physics_context_tick, ale_physics_object_transform_update, PhysicsContext};
+
+// Component whose transform follows its rigid body, wired with wire_component!(dyn PhysicsBody, T)
+pub trait PhysicsBody {
+  fn rigidbody_handle(&self) -> RigidBodyHandle;
+
+  fn transform_mut(&mut self) -> &mut AleTransform;
+}
+
+struct PhysicsBodyVisitor<'a> {
+  physics_context: &'a PhysicsContext,
+}
+
+impl<'a> VisitorMut<dyn PhysicsBody> for PhysicsBodyVisitor<'a> {
+  fn visit(&mut self, component: &mut (dyn PhysicsBody + 'static)) {
+    let rigidbody_handle = component.rigidbody_handle();
+    ale_physics_object_transform_update(self.physics_context, rigidbody_handle, component.transform_mut());
+  }
+}
+
+// Moves every PhysicsBody in the world to where its rigid body is
+pub fn ale_physics_world_update(physics_context: &PhysicsContext, world: &mut World) {
+  world.visit_mut(&mut PhysicsBodyVisitor { physics_context });
+}
+
+// Steps the physics context on every fixed tick, then writes the rigid bodies back to the PhysicsBody components.
+// Components that need the context can hold a clone of it via context()
+pub struct PhysicsPlugin {
+  physics_context: Rc<RefCell<PhysicsContext>>,
+}
+
+impl PhysicsPlugin {
+  pub fn new() -> PhysicsPlugin {
+    PhysicsPlugin {
+      physics_context: Rc::new(RefCell::new(ale_physics_context_new())),
+    }
+  }
+
+  pub fn context(&self) -> Rc<RefCell<PhysicsContext>> {
+    self.physics_context.clone()
+  }
+}
+
+impl Default for PhysicsPlugin {
+  fn default() -> PhysicsPlugin {
+    PhysicsPlugin::new()
+  }
+}
+
+impl Plugin for PhysicsPlugin {
+  fn fixed_tick(&mut self, _engine: &mut Engine, world: &mut World, delta_time: f32) {
+    let mut physics_context = self.physics_context.borrow_mut();
+    ale_physics_context_tick(&mut physics_context, delta_time);
+    ale_physics_world_update(&physics_context, world);
+  }
+}
+
+#[test]
+fn physics_world_update_moves_bodies() {
+  use ale_data::entity::Entity;
+  use ale_data::indexmap::Id;
+  use ale_data::wire_component;
+  use ale_math::{One, Quaternion, Vector3};
+  use ale_world::components::Spawnable;
+  use ale_world::event::world::SpawnCommand;
+
+  use crate::{ale_physics_object_new, RigidBodyShape, RigidBodyType};
+
+  struct Ball {
+    id: Id<Entity>,
+    rigidbody_handle: RigidBodyHandle,
+    transform: AleTransform,
+  }
+
+  impl Spawnable for Ball {
+    fn on_spawn(&mut self) {}
+
+    fn on_kill(&mut self) {}
+
+    fn id(&self) -> Id<Entity> {
+      self.id
+    }
+  }
+
+  impl PhysicsBody for Ball {
+    fn rigidbody_handle(&self) -> RigidBodyHandle {
+      self.rigidbody_handle
+    }
+
+    fn transform_mut(&mut self) -> &mut AleTransform {
+      &mut self.transform
+    }
+  }
+
+  struct Heights(Vec<f32>);
+  impl VisitorMut<dyn PhysicsBody> for Heights {
+    fn visit(&mut self, component: &mut (dyn PhysicsBody + 'static)) {
+      self.0.push(component.transform_mut().position.y);
+    }
+  }
+
+  let physics_plugin = PhysicsPlugin::new();
+  let physics_context = physics_plugin.context();
+  let position = Vector3::new(0.0, 10.0, 0.0);
+  let (rigidbody_handle, _) = ale_physics_object_new(
+    &mut physics_context.borrow_mut(),
+    position,
+    Quaternion::one(),
+    RigidBodyShape::Sphere(0.5),
+    RigidBodyType::Dynamic,
+    true,
+    false,
+  );
+
+  let mut world = World::new();
+  world.register_components(&[
+    wire_component!(dyn Spawnable, Ball),
+    wire_component!(dyn PhysicsBody, Ball),
+  ]);
+  world.spawn(SpawnCommand::new(Ball {
+    id: world.gen_entity_key(),
+    rigidbody_handle,
+    transform: AleTransform::from_position(position),
+  }));
+
+  for _ in 0..10 {
+    ale_physics_context_tick(&mut physics_context.borrow_mut(), 0.1);
+  }
+  ale_physics_world_update(&physics_context.borrow(), &mut world);
+
+  let mut heights = Heights(vec![]);
+  world.visit_mut(&mut heights);
+  assert_eq!(heights.0.len(), 1);
+  // falling for a second from 10
+  assert!(heights.0[0] < 6.0 && heights.0[0] > 4.0);
+}
@@ -1,8 +1,8 @@
 use ale_camera::Camera;
 use ale_math::num_traits::clamp;
 use ale_math::transform::AleTransform;
-use ale_math::{clamp_vec3, InnerSpace};
-use ale_math::{Vector3, GREEN, RED};
+use ale_math::clamp_vec3;
+use ale_math::{Vector2, Vector3, GREEN, RED};
 use ale_resources::mesh::sdf::{ale_mesh_sdf_distance, MeshSDF};
 
 pub struct Ray {
@@ -29,25 +29,19 @@ pub fn ale_raymarch_sdf_single(
   camera: &Camera,
   sdfs: Vec<(&mut AleTransform, &MeshSDF)>,
 ) -> Vec<(Vector3<f32>, Vector3<f32>)> {
-  let aspect_ratio = camera.aspect_ratio();
-  //let (x, y) = camera.viewport_size();
+  let viewport = camera.viewport();
   let (x, y) = (5, 5);
-  let position = camera.position();
-
-  let initial =
-    camera.position() + camera.forward_dir() - ((camera.right_dir() * aspect_ratio) / 2.0) - (camera.up_dir() / 2.0);
-  let right_step = (camera.right_dir() / x as f32 * aspect_ratio);
-  let down_step = camera.up_dir() / y as f32;
 
   let mut color = RED;
 
   let mut debug = vec![];
   for i in 0..x {
     for j in 0..y {
-      let direction = ((initial + right_step * i as f32 + down_step * j as f32) - position).normalize();
+      let cursor = viewport.denormalize_point(Vector2::new(i as f32 / x as f32, j as f32 / y as f32));
+      let picking_ray = camera.screen_to_world_ray(cursor, viewport);
       color = if color == RED { GREEN } else { RED };
 
-      let ray = ale_ray_new(position, direction);
+      let ray = ale_ray_new(picking_ray.origin, picking_ray.direction);
       for iter in 0..5 {
         let mut min_dist = f32::MAX;
         let mut curr_pos = ray.origin;
@@ -3,6 +3,7 @@ use std::fs::File;
 use std::io::Read;
 
 use rusttype::{point, Scale};
+use thiserror::Error;
 
 use ale_math::Vector2;
 use crate::stash::Load;
@@ -29,9 +30,11 @@ pub struct FontTextureKey {
   pub font_size: u32,
 }
 
+// Offsets and bounds are pixels, divide them by content_scale for logical units
 pub struct FontLayout {
   pub glyphs: Vec<FontGlyphLayout>,
   pub bounds: Vector2<i32>,
+  pub content_scale: f32,
 }
 
 pub struct FontGlyphLayout {
@@ -40,7 +43,8 @@ pub struct FontGlyphLayout {
   pub offset_max: Vector2<i32>,
 }
 
-#[derive(Debug)]
+#[derive(Error, Debug)]
+#[error("LoadError")]
 pub struct LoadError;
 pub struct Loader;
 impl Load<Font, LoadError> for Loader {
@@ -71,6 +75,26 @@ impl Font {
   }
 
   pub fn layout(&mut self, size: u32, text: &str, bounds: Option<Vector2<i32>>) -> FontLayout {
+    self.layout_scaled(size, 1.0, text, bounds)
+  }
+
+  // size and bounds are logical, glyphs are rasterized at size * content_scale pixels to stay crisp
+  pub fn layout_scaled(
+    &mut self,
+    size: u32,
+    content_scale: f32,
+    text: &str,
+    bounds: Option<Vector2<i32>>,
+  ) -> FontLayout {
+    let content_scale = if content_scale > 0.0 { content_scale } else { 1.0 };
+    let size = (size as f32 * content_scale).round().max(1.0) as u32;
+    let bounds = bounds.map(|bounds| {
+      Vector2::new(
+        (bounds.x as f32 * content_scale) as i32,
+        (bounds.y as f32 * content_scale) as i32,
+      )
+    });
+
     let scale = Scale {
       x: size as f32,
       y: size as f32,
@@ -149,6 +173,7 @@ impl Font {
     FontLayout {
       glyphs: glyph_layouts,
       bounds: all_bounding_box,
+      content_scale,
     }
   }
 }
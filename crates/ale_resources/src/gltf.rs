@@ -2,8 +2,9 @@ use std::collections::HashMap;
 
 use ::gltf::mesh::util::{ReadIndices, ReadTexCoords};
 use ::gltf::mesh::Reader;
+use ale_math::geometry::Aabb;
 use ale_math::transform::AleTransform;
-use ale_math::Quaternion;
+use ale_math::{Matrix4, Quaternion};
 
 use crate::gltf;
 use crate::mesh::Mesh;
@@ -18,7 +19,7 @@ pub fn load(path: &str) -> Vec<Mesh> {
 
     match node.transform() {
       ::gltf::scene::Transform::Matrix { matrix } => {
-        // do nothing for now
+        nodes.insert(node.index(), AleTransform::from_matrix(Matrix4::from(matrix)));
       }
       ::gltf::scene::Transform::Decomposed {
         translation,
@@ -57,7 +58,7 @@ pub fn load(path: &str) -> Vec<Mesh> {
       let ale_mesh = Mesh::new(
         vbuffer,
         Some(ibuffer),
-        (bb_min.into(), bb_max.into()),
+        Aabb::new(bb_min.into(), bb_max.into()),
         Some(transform.clone()),
       );
       objects.push(ale_mesh);
@@ -6,4 +6,5 @@ pub mod texture;
 pub mod shader;
 pub mod resources;
 pub mod path;
-pub mod stash;
\ No newline at end of file
+pub mod stash;
+pub mod watcher;
@@ -3,6 +3,7 @@ use thiserror::Error;
 use ale_data::alevec::Key;
 use ale_data::buffer::{Buffer, BufferBuilder};
 use ale_math::{Array, InnerSpace, Matrix4, Vector2, Vector3, Zero};
+use ale_math::geometry::Aabb;
 use ale_math::transform::AleTransform;
 
 use crate::{struct_id, struct_id_impl};
@@ -22,7 +23,7 @@ pub struct Mesh {
   pub id: MeshId,
   pub vertices: Buffer<f32>,
   pub indices: Option<Buffer<i32>>,
-  pub bounding_box: (Vector3<f32>, Vector3<f32>),
+  pub bounding_box: Aabb,
 
   // Cache so this can be faster
   pub position_offset: Option<usize>,
@@ -46,7 +47,7 @@ impl Mesh {
   pub fn new(
     vertices: Buffer<f32>,
     indices: Option<Buffer<i32>>,
-    bounding_box: (Vector3<f32>, Vector3<f32>),
+    bounding_box: Aabb,
     load_transform: Option<AleTransform>,
   ) -> Mesh {
     let position_offset = vertices.offset("position");
@@ -121,7 +122,7 @@ impl Mesh {
     .build()
     .unwrap();
 
-    let bounding_box = (Vector3::from_value(-1.0), Vector3::from_value(1.0));
+    let bounding_box = Aabb::new(Vector3::from_value(-1.0), Vector3::from_value(1.0));
 
     Mesh::new(vertices, None, bounding_box, None)
   }
@@ -132,7 +133,7 @@ impl Mesh {
       .build()
       .unwrap();
 
-    let bounding_box = (Vector3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 1.0, 0.0));
+    let bounding_box = Aabb::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 1.0, 0.0));
 
     Mesh::new(vertices, None, bounding_box, None)
   }
@@ -147,7 +148,7 @@ impl Mesh {
     .build()
     .unwrap();
 
-    let bounding_box = (Vector3::new(-1.0, -1.0, 0.0), Vector3::new(1.0, 1.0, 0.0));
+    let bounding_box = Aabb::new(Vector3::new(-1.0, -1.0, 0.0), Vector3::new(1.0, 1.0, 0.0));
 
     Mesh::new(vertices, None, bounding_box, None)
   }
@@ -204,15 +205,14 @@ impl Mesh {
       .build()
       .unwrap();
 
-    let bounding_box = (Vector3::from_value(-1.0), Vector3::from_value(1.0));
+    let bounding_box = Aabb::new(Vector3::from_value(-1.0), Vector3::from_value(1.0));
 
     Mesh::new(vertices, None, bounding_box, None)
   }
 
   pub fn bounding_box_matrix(&self) -> Matrix4<f32> {
-    let (min, max) = self.bounding_box;
-    let size = (max - min) / 2.0;
-    let center = (max + min) / 2.0;
+    let size = self.bounding_box.half_extents();
+    let center = self.bounding_box.center();
 
     //println!("min: {:?}, max: {:?}, center: {:?}, size: {:?}", min, max, center, size);
 
@@ -335,7 +335,7 @@ pub fn test_tri_get_no_ebo() {
     .build()
     .unwrap();
 
-  let mesh = Mesh::new(buffer, None, (Vector3::zero(), Vector3::zero()), None);
+  let mesh = Mesh::new(buffer, None, Aabb::new(Vector3::zero(), Vector3::zero()), None);
 
   assert_eq!(mesh.tri_len(), 3);
 
@@ -475,7 +475,7 @@ pub fn test_tri_get_with_ebo() {
   let indices: Vec<i32> = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 2, 5, 8, 4, 1, 7];
   let ibuffer = BufferBuilder::new(indices).info("index", 3).build().unwrap();
 
-  let mesh = Mesh::new(buffer, Some(ibuffer), (Vector3::zero(), Vector3::zero()), None);
+  let mesh = Mesh::new(buffer, Some(ibuffer), Aabb::new(Vector3::zero(), Vector3::zero()), None);
 
   assert_eq!(mesh.tri_len(), 5);
 
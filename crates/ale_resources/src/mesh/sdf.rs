@@ -1,6 +1,7 @@
 use std::time::Instant;
 
-use ale_math::{ale_bounding_box_closest_point, dot, InnerSpace, MetricSpace, Transform, Zero};
+use ale_math::geometry::Aabb;
+use ale_math::{dot, InnerSpace, MetricSpace, Transform, Zero};
 use ale_math::Vector3;
 use ale_math::transform::AleTransform;
 use crate::mesh::{Mesh, Tri};
@@ -8,7 +9,7 @@ use crate::mesh::{Mesh, Tri};
 pub struct MeshSDF {
   dist: Vec<Vec<Vec<f32>>>,
 
-  mesh_bounding_box: (Vector3<f32>, Vector3<f32>),
+  mesh_bounding_box: Aabb,
   initial: Vector3<f32>,
   step: Vector3<f32>,
 
@@ -231,7 +232,7 @@ pub fn ale_mesh_point_triangle_closest_point(triangle: &Tri, point: Vector3<f32>
 pub fn ale_mesh_sdf_new(mesh: &Mesh, reso: u32) -> MeshSDF {
   let start_time = Instant::now();
 
-  let (min, max) = mesh.bounding_box;
+  let Aabb { min, max } = mesh.bounding_box;
   let size = (max - min);
   let sdf_size = size + 0.4f32 * size;
   let step = sdf_size / reso as f32;
@@ -300,7 +301,7 @@ pub fn ale_mesh_sdf_new(mesh: &Mesh, reso: u32) -> MeshSDF {
 }
 
 pub fn ale_mesh_sdf_distance(sdf: &MeshSDF, point: Vector3<f32>, transform: &mut AleTransform) -> f32 {
-  let point_in_sdf = ale_bounding_box_closest_point(point.clone(), sdf.mesh_bounding_box);
+  let point_in_sdf = sdf.mesh_bounding_box.closest_point(point.clone());
   let (i, j, k) = ale_mesh_sdf_find_quadrant(sdf, point_in_sdf);
   //println!("{} {} {}", i, j, k);
 
@@ -1,17 +1,32 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+// Set once on startup (e.g. from the app config), falls back to the workspace resources folder
+static RESOURCE_ROOT: OnceLock<PathBuf> = OnceLock::new();
 
 pub struct ResourcePath;
 
 impl ResourcePath {
   pub fn find(path: &str) -> String {
-    let p = Path::new(env!("CARGO_MANIFEST_DIR"))
+    let p = ResourcePath::root().join(path);
+    p.to_str().unwrap().to_owned()
+  }
+
+  // Returns false if the root has already been set
+  pub fn set_root(root: PathBuf) -> bool {
+    RESOURCE_ROOT.set(root).is_ok()
+  }
+
+  pub fn root() -> PathBuf {
+    if let Some(root) = RESOURCE_ROOT.get() {
+      return root.clone();
+    }
+    Path::new(env!("CARGO_MANIFEST_DIR"))
       .parent()
       .unwrap()
       .parent()
       .unwrap()
       .join("resources")
-      .join(path);
-    p.to_str().unwrap().to_owned()
   }
 }
 
@@ -1,12 +1,14 @@
 use std::any::TypeId;
 
-use ale_data::alevec::AleVec;
+use thiserror::Error;
 
-use crate::{font, mesh, texture};
+use ale_data::alevec::{AleVec, Key};
+
+use crate::{font, mesh, shader, texture};
 use crate::font::Font;
 use crate::mesh::Mesh;
 use crate::shader::{Shader, ShaderStash};
-use crate::stash::Stash;
+use crate::stash::{Stash, StashReloadError};
 use crate::texture::Texture;
 
 pub struct Resources {
@@ -16,6 +18,25 @@ pub struct Resources {
   pub fonts: Stash<Font, font::LoadError, font::Loader>,
 }
 
+#[derive(Clone, Copy)]
+pub enum ResourceReloaded {
+  Shader(Key<Shader>),
+  Texture(Key<Texture>),
+  Mesh(Key<Mesh>),
+}
+
+#[derive(Error, Debug)]
+pub enum ReloadError {
+  #[error("(ReloadError::ShaderLoadError)\nPath: {}\n{}", .1, .0)]
+  ShaderLoadError(shader::LoadError, String),
+  #[error("(ReloadError::TextureLoadError)\nPath: {}\n{:?}", .1, .0)]
+  TextureLoadError(texture::LoadError, String),
+  #[error("(ReloadError::MeshLoadError)\nPath: {}\n{}", .1, .0)]
+  MeshLoadError(mesh::LoadError, String),
+  #[error("(ReloadError::CountChanged)\nPath: {}\n{} resources were loaded from it, now {}", .0, .1, .2)]
+  CountChanged(String, usize, usize),
+}
+
 impl Resources {
   pub fn new() -> Resources {
     Resources {
@@ -25,4 +46,32 @@ impl Resources {
       fonts: Stash::new(),
     }
   }
+
+  // Reloads every shader, texture and mesh loaded from changed_path in place.
+  // Returns what was reloaded along with the sources that failed, those keep their old resources.
+  pub fn reload(&mut self, changed_path: &str) -> (Vec<ResourceReloaded>, Vec<ReloadError>) {
+    let mut reloaded = vec![];
+    let mut errors = vec![];
+
+    let (keys, shader_errors) = self.shaders.stash.reload(changed_path);
+    reloaded.extend(keys.into_iter().map(ResourceReloaded::Shader));
+    errors.extend(shader_errors.into_iter().map(|e| intern_reload_error(e, ReloadError::ShaderLoadError)));
+
+    let (keys, texture_errors) = self.textures.reload(changed_path);
+    reloaded.extend(keys.into_iter().map(ResourceReloaded::Texture));
+    errors.extend(texture_errors.into_iter().map(|e| intern_reload_error(e, ReloadError::TextureLoadError)));
+
+    let (keys, mesh_errors) = self.meshes.reload(changed_path);
+    reloaded.extend(keys.into_iter().map(ResourceReloaded::Mesh));
+    errors.extend(mesh_errors.into_iter().map(|e| intern_reload_error(e, ReloadError::MeshLoadError)));
+
+    (reloaded, errors)
+  }
+}
+
+fn intern_reload_error<Err>(err: StashReloadError<Err>, load_error: fn(Err, String) -> ReloadError) -> ReloadError {
+  match err {
+    StashReloadError::LoadError(err, path) => load_error(err, path),
+    StashReloadError::CountChanged(path, before, now) => ReloadError::CountChanged(path, before, now),
+  }
 }
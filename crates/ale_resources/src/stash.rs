@@ -1,4 +1,5 @@
 use std::marker::PhantomData;
+use std::path::Path;
 use log::info;
 use ale_data::alevec::{AleVec, AleVecIter, AleVecIterMut, Key};
 use crate::path::ResourcePath;
@@ -7,10 +8,22 @@ pub trait Load<Resource, Err> {
     fn load(&self, path: &str) -> Result<Vec<Resource>, Err>;
 }
 
+// A source that couldn't be reloaded, its resources are left as they were
+#[derive(Debug)]
+pub enum StashReloadError<Err> {
+    // load error, resolved path
+    LoadError(Err, String),
+    // resolved path, number of resources loaded before, number loaded now
+    CountChanged(String, usize, usize),
+}
+
 pub struct Stash<Resource, Err, Loader: Load<Resource, Err> + Default> {
     resources: AleVec<Resource>,
     loader: Loader,
 
+    // Resolved path and the keys that came out of it, used for reloading
+    sources: Vec<(String, Vec<Key<Resource>>)>,
+
     phantom_err: PhantomData<Err>,
 }
 
@@ -19,6 +32,7 @@ impl<Resource, Err, Loader: Load<Resource, Err> + Default> Stash<Resource, Err,
         return Stash {
             resources: AleVec::new(),
             loader: Loader::default(),
+            sources: vec!(),
             phantom_err: Default::default(),
         };
     }
@@ -33,9 +47,46 @@ impl<Resource, Err, Loader: Load<Resource, Err> + Default> Stash<Resource, Err,
         for r in res {
             keys.push(self.resources.push(r));
         }
+        self.sources.push((resource_path.clone(), keys.clone()));
         Ok(keys)
     }
 
+    // Loads every resource that came from changed_path again, replacing them in place under the same keys.
+    // changed_path is an absolute path, it may also point to a file with extension that belongs to a
+    // resource loaded without one (e.g. shaders/pbr.frag for shaders/pbr).
+    // Every matching source is reloaded even if another one fails. A source that fails to load, or now
+    // yields a different number of resources, keeps all of its old resources and is returned as an error.
+    pub fn reload(&mut self, changed_path: &str) -> (Vec<Key<Resource>>, Vec<StashReloadError<Err>>) {
+        let mut reloaded = vec!();
+        let mut errors = vec!();
+        for (resource_path, keys) in &self.sources {
+            if !intern_path_matches(resource_path, changed_path) {
+                continue;
+            }
+
+            let res = match self.loader.load(resource_path) {
+                Ok(res) => res,
+                Err(err) => {
+                    errors.push(StashReloadError::LoadError(err, resource_path.clone()));
+                    continue;
+                }
+            };
+            if res.len() != keys.len() {
+                errors.push(StashReloadError::CountChanged(resource_path.clone(), keys.len(), res.len()));
+                continue;
+            }
+            info!("reload: {}", resource_path);
+
+            for (key, r) in keys.iter().zip(res) {
+                if let Some(resource) = self.resources.get_mut(*key) {
+                    *resource = r;
+                    reloaded.push(*key);
+                }
+            }
+        }
+        (reloaded, errors)
+    }
+
     pub fn register(&mut self, resource: Resource) -> Key<Resource> {
         self.resources.push(resource)
     }
@@ -59,4 +110,68 @@ impl<Resource, Err, Loader: Load<Resource, Err> + Default> Stash<Resource, Err,
     pub fn iter_mut(&mut self) -> AleVecIterMut<Resource> {
         self.resources.iter_mut()
     }
-}
\ No newline at end of file
+}
+
+fn intern_path_matches(resource_path: &str, changed_path: &str) -> bool {
+    let resource_path = Path::new(resource_path);
+    let changed_path = Path::new(changed_path);
+    resource_path == changed_path || resource_path == changed_path.with_extension("")
+}
+
+#[test]
+fn test_stash_path_matches() {
+    assert!(intern_path_matches("/res/shaders/pbr", "/res/shaders/pbr.frag"));
+    assert!(intern_path_matches("/res/shaders/pbr", "/res/shaders/pbr.vert"));
+    assert!(intern_path_matches("/res/texture/a.png", "/res/texture/a.png"));
+    assert!(!intern_path_matches("/res/texture/a.png", "/res/texture/b.png"));
+    assert!(!intern_path_matches("/res/shaders/pbr", "/res/shaders/pbr_light.frag"));
+}
+
+#[test]
+fn test_stash_reload_keeps_failed_sources() {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    // Serves whatever the test put in for a path, missing paths fail to load
+    #[derive(Default)]
+    struct TestLoader {
+        files: RefCell<HashMap<String, Vec<u32>>>,
+    }
+
+    impl Load<u32, String> for TestLoader {
+        fn load(&self, path: &str) -> Result<Vec<u32>, String> {
+            self.files.borrow().get(path).cloned().ok_or(format!("{} missing", path))
+        }
+    }
+
+    fn add_source(stash: &mut Stash<u32, String, TestLoader>, path: &str, resources: Vec<u32>) -> Vec<Key<u32>> {
+        let keys = resources.into_iter().map(|r| stash.register(r)).collect::<Vec<_>>();
+        stash.sources.push((path.to_owned(), keys.clone()));
+        keys
+    }
+
+    let mut stash: Stash<u32, String, TestLoader> = Stash::new();
+    let broken = add_source(&mut stash, "/res/a.frag", vec![1]);
+    let resized = add_source(&mut stash, "/res/a", vec![2, 3]);
+    let fine = add_source(&mut stash, "/res/a.vert", vec![4]);
+
+    // /res/a.frag is gone, /res/a yields one resource instead of two
+    stash.loader.files.borrow_mut().insert("/res/a".to_owned(), vec![20]);
+    stash.loader.files.borrow_mut().insert("/res/a.vert".to_owned(), vec![40]);
+
+    let (reloaded, errors) = stash.reload("/res/a.frag");
+    assert_eq!(reloaded, vec![]);
+    assert_eq!(errors.len(), 2);
+    assert!(matches!(&errors[0], StashReloadError::LoadError(_, path) if path == "/res/a.frag"));
+    assert!(matches!(&errors[1], StashReloadError::CountChanged(path, 2, 1) if path == "/res/a"));
+    assert_eq!(stash.get(broken[0]), Some(&1));
+    assert_eq!(stash.get(resized[0]), Some(&2));
+    assert_eq!(stash.get(resized[1]), Some(&3));
+
+    // a.vert reloads even though /res/a still fails
+    let (reloaded, errors) = stash.reload("/res/a.vert");
+    assert_eq!(reloaded, fine);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(stash.get(fine[0]), Some(&40));
+    assert_eq!(stash.get(resized[0]), Some(&2));
+}
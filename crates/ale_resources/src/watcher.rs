@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+// Polls modification time of every file under root, works on every platform without native notify apis
+pub struct ResourceWatcher {
+  root: PathBuf,
+  interval: Duration,
+  last_poll: Instant,
+  mtimes: HashMap<PathBuf, SystemTime>,
+}
+
+impl ResourceWatcher {
+  pub fn new(root: &Path, interval: Duration) -> ResourceWatcher {
+    let mut mtimes = HashMap::new();
+    intern_scan(root, &mut mtimes);
+
+    ResourceWatcher {
+      root: root.to_path_buf(),
+      interval,
+      last_poll: Instant::now(),
+      mtimes,
+    }
+  }
+
+  // Returns files that were modified or created since the last poll.
+  // Does nothing until interval has passed since the last poll.
+  pub fn poll(&mut self) -> Vec<PathBuf> {
+    if self.last_poll.elapsed() < self.interval {
+      return vec![];
+    }
+    self.last_poll = Instant::now();
+
+    let mut mtimes = HashMap::new();
+    intern_scan(&self.root, &mut mtimes);
+
+    let mut changed = vec![];
+    for (path, mtime) in &mtimes {
+      match self.mtimes.get(path) {
+        Some(old_mtime) if old_mtime == mtime => {}
+        _ => changed.push(path.clone()),
+      }
+    }
+    self.mtimes = mtimes;
+
+    changed
+  }
+}
+
+fn intern_scan(dir: &Path, mtimes: &mut HashMap<PathBuf, SystemTime>) {
+  let entries = match fs::read_dir(dir) {
+    Err(_) => return,
+    Ok(entries) => entries,
+  };
+
+  for entry in entries.flatten() {
+    let path = entry.path();
+    let metadata = match entry.metadata() {
+      Err(_) => continue,
+      Ok(metadata) => metadata,
+    };
+
+    if metadata.is_dir() {
+      intern_scan(&path, mtimes);
+    } else if let Ok(mtime) = metadata.modified() {
+      mtimes.insert(path, mtime);
+    }
+  }
+}
+
+#[test]
+fn test_watcher_detects_changes() {
+  let root = std::env::temp_dir().join(format!("ale_watcher_test_{}", std::process::id()));
+  let _ = fs::remove_dir_all(&root);
+  fs::create_dir_all(root.join("shaders")).unwrap();
+  let frag = root.join("shaders").join("pbr.frag");
+  fs::write(&frag, "void main() {}").unwrap();
+
+  let mut watcher = ResourceWatcher::new(&root, Duration::from_secs(0));
+  assert!(watcher.poll().is_empty());
+
+  let file = fs::OpenOptions::new().write(true).open(&frag).unwrap();
+  file.set_modified(SystemTime::now() + Duration::from_secs(10)).unwrap();
+  let vert = root.join("shaders").join("pbr.vert");
+  fs::write(&vert, "void main() {}").unwrap();
+
+  let mut changed = watcher.poll();
+  changed.sort();
+  assert_eq!(changed, vec![frag, vert]);
+  assert!(watcher.poll().is_empty());
+
+  let _ = fs::remove_dir_all(&root);
+}
@@ -1,4 +1,5 @@
 use crate::layout::TableLayoutTypeError::RowNotFound;
+use ale_math::rect::RectI;
 use ale_math::{Array, Vector2, Zero};
 use std::collections::HashMap;
 use thiserror::Error;
@@ -136,19 +137,15 @@ impl Layout {
     }
   }
 
-  pub fn is_inside(&self, x: i32, y: i32) -> bool {
-    let xleft = self.position.x;
-    let xright = self.position.x + self.size.x as i32;
-    if x < xleft || x > xright {
-      return false;
-    }
-
-    let ytop = self.position.y;
-    let ybot = self.position.y + self.size.y as i32;
-    if y < ytop || y > ybot {
-      return false;
-    }
+  // In window space, where cursor positions are
+  pub fn global_rect(&self) -> RectI {
+    RectI::new(
+      self.global_position,
+      Vector2::new(self.global_size.x as i32, self.global_size.y as i32),
+    )
+  }
 
-    return true;
+  pub fn is_inside(&self, x: i32, y: i32) -> bool {
+    self.global_rect().is_inside(x, y)
   }
 }
@@ -1,3 +1,4 @@
+use ale_math::color::Color;
 use ale_math::{Matrix4, Vector3, Vector4};
 use std::fmt::Debug;
 use std::hash::{Hash, Hasher};
@@ -65,6 +66,12 @@ impl ToVariable for Vector4<f32> {
   }
 }
 
+impl ToVariable for Color {
+  fn to_variable(&self, name: &str) -> Variable {
+    Variable::F32_4(name.to_owned(), (*self).into())
+  }
+}
+
 impl From<Variable> for f32 {
   fn from(v: Variable) -> Self {
     match v {
@@ -87,6 +94,17 @@ impl From<Variable> for bool {
   }
 }
 
+impl From<Variable> for Color {
+  fn from(v: Variable) -> Self {
+    match v {
+      Variable::F32_4(_, v) => v.into(),
+      _ => {
+        unimplemented!("variable mismatch, color expected {}", v.name_str())
+      }
+    }
+  }
+}
+
 #[macro_export]
 macro_rules! to_variable {
   ($e:ident) => {
@@ -1,24 +1,91 @@
-use crate::display::{DisplaySetting, TargetMonitor};
+use crate::display::{CursorMode, DisplaySetting, TargetMonitor, WindowIcon, WindowMode};
+#[cfg(feature = "glfw")]
+use crate::glfw_backend::GlfwBackend;
 use crate::window::Window;
 use ale_data::alevec::{AleVec, AleVecIter, AleVecIterMut, Key};
+use ale_input::{Action, Input};
 use ale_math::rect::Rect;
 use ale_math::Vector2;
-use ale_ui::panels::Panels;
-use glfw::ffi::GLFWcharfun;
-use glfw::{Context, CursorMode};
-use std::collections::HashMap;
+
+// Native windows are referred to by the id their backend returned from create
+pub type BackendWindowId = usize;
+
+// Platform layer under Windows, GlfwBackend for desktops and HeadlessBackend for tests
+pub trait WindowBackend {
+  // shared: an existing window whose GL context is shared with the new one
+  fn create(&mut self, display_setting: &DisplaySetting, shared: Option<BackendWindowId>) -> BackendWindowId;
+
+  fn destroy(&mut self, id: BackendWindowId);
+
+  // Pumps platform events, read them afterwards with input
+  fn poll(&mut self);
+
+  // Translated inputs of a window since the last call
+  fn input(&mut self, id: BackendWindowId) -> Vec<Input>;
+
+  // Gamepads aren't tied to a window
+  fn poll_gamepads(&mut self) -> Vec<Input>;
+
+  // False when windows have no GL context, nothing may call GL then
+  fn has_gl_context(&self) -> bool;
+
+  fn make_current(&mut self, id: BackendWindowId);
+
+  // Swap interval of the window's context, the window is left current
+  fn set_vsync(&mut self, id: BackendWindowId, vsync: bool);
+
+  fn swap(&mut self, id: BackendWindowId);
+
+  // In pixels, can differ from the window size on HiDPI displays
+  fn framebuffer_size(&self, id: BackendWindowId) -> Vector2<u32>;
+
+  // Pixels per logical unit
+  fn content_scale(&self, id: BackendWindowId) -> Vector2<f32>;
+
+  // Requests the window to close, it's destroyed on the next Windows::cleanup
+  fn close(&mut self, id: BackendWindowId);
+
+  fn is_closing(&self, id: BackendWindowId) -> bool;
+
+  fn is_focused(&self, id: BackendWindowId) -> bool;
+
+  fn set_cursor_mode(&mut self, id: BackendWindowId, cursor_mode: CursorMode);
+
+  // Returns false when enabling it on a platform without raw mouse motion
+  fn set_raw_mouse_motion(&mut self, id: BackendWindowId, raw_mouse_motion: bool) -> bool;
+
+  // windowed_size is logical, used when switching back to Windowed
+  fn set_window_mode(
+    &mut self,
+    id: BackendWindowId,
+    window_mode: WindowMode,
+    monitor: TargetMonitor,
+    windowed_size: Vector2<u32>,
+  );
+
+  fn set_title(&mut self, id: BackendWindowId, title: &str);
+
+  // None restores the platform's default icon
+  fn set_icon(&mut self, id: BackendWindowId, icon: Option<&WindowIcon>);
+
+  fn set_resizable(&mut self, id: BackendWindowId, is_resizable: bool);
+}
 
 pub struct Windows {
-  glfw: glfw::Glfw,
+  backend: Box<dyn WindowBackend>,
   windows: AleVec<Window>,
   context_window_key: Option<Key<Window>>,
 }
 
 impl Windows {
+  #[cfg(feature = "glfw")]
   pub fn new() -> Windows {
-    let glfw = glfw::init(glfw::FAIL_ON_ERRORS).unwrap();
+    Windows::with_backend(Box::new(GlfwBackend::new()))
+  }
+
+  pub fn with_backend(backend: Box<dyn WindowBackend>) -> Windows {
     let mut windows = Windows {
-      glfw,
+      backend,
       windows: AleVec::new(),
       context_window_key: None,
     };
@@ -27,92 +94,181 @@ impl Windows {
   }
 
   pub fn create_shared_context(&mut self) {
-    self.context_window_key = Some(self.add(DisplaySetting {
-      dimension: Rect {
-        position: Vector2::new(0, 0),
-        size: Vector2::new(1, 1),
-      },
-      initial_target: TargetMonitor::PRIMARY,
-      is_hidden: true,
-    }));
+    let mut display_setting = DisplaySetting::new(Rect {
+      position: Vector2::new(0, 0),
+      size: Vector2::new(1, 1),
+    });
+    display_setting.is_hidden = true;
+    self.context_window_key = Some(self.add(display_setting));
   }
 
   pub fn add(&mut self, display_setting: DisplaySetting) -> Key<Window> {
-    self.glfw.window_hint(glfw::WindowHint::ContextVersion(3, 3));
-    self
-      .glfw
-      .window_hint(glfw::WindowHint::OpenGlProfile(glfw::OpenGlProfileHint::Core));
+    let shared = self
+      .context_window_key
+      .map(|key| self.windows.get(key).expect("shared context missing").backend_id);
+    let backend_id = self.backend.create(&display_setting, shared);
 
-    if display_setting.is_hidden {
-      self.glfw.window_hint(glfw::WindowHint::Visible(false));
-    } else {
-      self.glfw.window_hint(glfw::WindowHint::Visible(true));
-    }
-    #[cfg(target_os = "macos")]
-    glfw.window_hint(glfw::WindowHint::OpenGlForwardCompat(true));
-    // glfw window creation
-    // --------------------
-
-    let (mut glfw_window, glfw_events) = match self.context_window_key {
-      None => {
-        let (mut glfw_window, glfw_events) = self
-          .glfw
-          .create_window(
-            display_setting.get_dimension().size.x,
-            display_setting.get_dimension().size.y,
-            "Alers",
-            glfw::WindowMode::Windowed,
-          )
-          .expect("Failed to create GLFW window");
-        (glfw_window, glfw_events)
-      }
-      Some(shared_key) => {
-        let shared_window = self.windows.get(shared_key).expect("shared context missing");
-        let (mut glfw_window, glfw_events) = shared_window
-          .glfw_window
-          .create_shared(
-            display_setting.get_dimension().size.x,
-            display_setting.get_dimension().size.y,
-            "Alers",
-            glfw::WindowMode::Windowed,
-          )
-          .expect("Failed to create GLFW window");
-        (glfw_window, glfw_events)
-      }
-    };
-
-    glfw_window.make_current();
-    glfw_window.set_char_polling(true);
-    glfw_window.set_key_polling(true);
-    glfw_window.set_cursor_pos_polling(true);
-    glfw_window.set_mouse_button_polling(true);
-    glfw_window.set_framebuffer_size_polling(true);
-    glfw_window.set_cursor_mode(CursorMode::Normal);
-
-    // gl: load all OpenGL function pointers
-    // ---------------------------------------
-    ale_opengl::raw::load_with(|symbol| glfw_window.get_proc_address(symbol) as *const _);
+    self.backend.set_cursor_mode(backend_id, display_setting.cursor_mode);
+    self
+      .backend
+      .set_raw_mouse_motion(backend_id, display_setting.raw_mouse_motion);
 
+    let mut window = Window::new(backend_id, display_setting, false);
+    window.set_content_scale(self.backend.content_scale(backend_id));
+    window.set_framebuffer_size(self.backend.framebuffer_size(backend_id));
+    let key = self.windows.push(window);
     self.make_primary_context_current();
+    key
+  }
 
+  // The first window that was added and is still open
+  pub fn primary(&self) -> Option<Key<Window>> {
     self
       .windows
-      .push(Window::new(glfw_window, glfw_events, display_setting, false))
+      .keys_iter()
+      .find(|key| Some(*key) != self.context_window_key)
   }
 
   pub fn make_primary_context_current(&mut self) {
     match self.context_window_key {
       None => {}
       Some(shared_key) => {
-        let mut shared_window = self.windows.get_mut(shared_key).
-          expect("primary context missing");
-        shared_window.glfw_window.make_current();
+        let shared_window = self.windows.get(shared_key).expect("primary context missing");
+        self.backend.make_current(shared_window.backend_id);
       }
     };
   }
 
   pub fn poll_inputs(&mut self) {
-    self.glfw.poll_events();
+    self.backend.poll();
+  }
+
+  pub fn poll_gamepads(&mut self) -> Vec<Input> {
+    self.backend.poll_gamepads()
+  }
+
+  // Inputs of the window since the last call, Escape is swallowed if the window closes on it.
+  // Resizes and scale changes are applied to the window, the logical size is remembered while Windowed.
+  pub fn input(&mut self, key: Key<Window>) -> Vec<Input> {
+    let window = match self.windows.get_mut(key) {
+      None => return vec![],
+      Some(window) => window,
+    };
+    let backend_id = window.backend_id;
+    let close_on_escape = window.display_setting.close_on_escape;
+
+    let mut inputs = self.backend.input(backend_id);
+    for input in &inputs {
+      match input {
+        Input::FramebufferResize { width, height } => window.set_framebuffer_size(Vector2::new(*width, *height)),
+        Input::ContentScale { x, y } => window.set_content_scale(Vector2::new(*x, *y)),
+        _ => {}
+      }
+    }
+    if close_on_escape {
+      let escape_index = inputs
+        .iter()
+        .position(|input| matches!(input, Input::Key(ale_input::Key::Escape, _, Action::Press, _)));
+      if let Some(index) = escape_index {
+        inputs.remove(index);
+        self.backend.close(backend_id);
+      }
+    }
+    inputs
+  }
+
+  pub fn has_gl_context(&self) -> bool {
+    self.backend.has_gl_context()
+  }
+
+  pub fn make_current(&mut self, key: Key<Window>) {
+    if let Some(window) = self.windows.get(key) {
+      self.backend.make_current(window.backend_id);
+    }
+  }
+
+  pub fn swap_buffers(&mut self, key: Key<Window>) {
+    if let Some(window) = self.windows.get(key) {
+      self.backend.swap(window.backend_id);
+    }
+  }
+
+  // Queries the backend, Window::get_framebuffer_size is the size as of the last input
+  pub fn framebuffer_size(&self, key: Key<Window>) -> Option<Vector2<u32>> {
+    let window = self.windows.get(key)?;
+    Some(self.backend.framebuffer_size(window.backend_id))
+  }
+
+  pub fn close(&mut self, key: Key<Window>) {
+    if let Some(window) = self.windows.get(key) {
+      self.backend.close(window.backend_id);
+    }
+  }
+
+  pub fn is_focused(&self, key: Key<Window>) -> bool {
+    match self.windows.get(key) {
+      None => false,
+      Some(window) => self.backend.is_focused(window.backend_id),
+    }
+  }
+
+  pub fn set_cursor_mode(&mut self, key: Key<Window>, cursor_mode: CursorMode) {
+    if let Some(window) = self.windows.get_mut(key) {
+      window.display_setting.cursor_mode = cursor_mode;
+      self.backend.set_cursor_mode(window.backend_id, cursor_mode);
+    }
+  }
+
+  // Returns false when enabling it on a platform without raw mouse motion
+  pub fn set_raw_mouse_motion(&mut self, key: Key<Window>, raw_mouse_motion: bool) -> bool {
+    match self.windows.get_mut(key) {
+      None => false,
+      Some(window) => {
+        window.display_setting.raw_mouse_motion = raw_mouse_motion;
+        self.backend.set_raw_mouse_motion(window.backend_id, raw_mouse_motion)
+      }
+    }
+  }
+
+  pub fn set_window_mode(&mut self, key: Key<Window>, window_mode: WindowMode, monitor: TargetMonitor) {
+    if let Some(window) = self.windows.get_mut(key) {
+      window.display_setting.window_mode = window_mode;
+      window.display_setting.monitor = monitor;
+      self
+        .backend
+        .set_window_mode(window.backend_id, window_mode, monitor, window.windowed_size);
+    }
+  }
+
+  pub fn set_title(&mut self, key: Key<Window>, title: &str) {
+    if let Some(window) = self.windows.get_mut(key) {
+      window.display_setting.title = title.to_owned();
+      self.backend.set_title(window.backend_id, title);
+    }
+  }
+
+  pub fn set_icon(&mut self, key: Key<Window>, icon: Option<WindowIcon>) {
+    if let Some(window) = self.windows.get_mut(key) {
+      self.backend.set_icon(window.backend_id, icon.as_ref());
+      window.display_setting.icon = icon;
+    }
+  }
+
+  pub fn set_resizable(&mut self, key: Key<Window>, is_resizable: bool) {
+    if let Some(window) = self.windows.get_mut(key) {
+      window.display_setting.is_resizable = is_resizable;
+      self.backend.set_resizable(window.backend_id, is_resizable);
+    }
+  }
+
+  // The window's context is made current to apply it
+  pub fn set_vsync(&mut self, key: Key<Window>, vsync: bool) {
+    if let Some(window) = self.windows.get_mut(key) {
+      if window.display_setting.vsync != vsync {
+        window.display_setting.vsync = vsync;
+        self.backend.set_vsync(window.backend_id, vsync);
+      }
+    }
   }
 
   pub fn cleanup(&mut self) {
@@ -120,11 +276,11 @@ impl Windows {
     let window_keys: Vec<Key<Window>> = self.windows.keys_iter().collect();
 
     for key in window_keys {
-      let window = self.windows.get_mut(key);
+      let window = self.windows.get(key);
       match window {
         None => {}
         Some(window) => {
-          if window.is_closing() {
+          if self.backend.is_closing(window.backend_id) {
             to_be_removed.push(key);
           }
         }
@@ -132,7 +288,9 @@ impl Windows {
     }
 
     for rem in to_be_removed {
-      self.windows.remove_drop(rem);
+      if let Some(window) = self.windows.remove_drop(rem) {
+        self.backend.destroy(window.backend_id);
+      }
     }
   }
 
@@ -156,3 +314,98 @@ impl Windows {
     return self.windows.iter_mut();
   }
 }
+
+#[test]
+fn windows_headless_input_and_close() {
+  use crate::headless::HeadlessBackend;
+  use ale_input::Modifier;
+
+  let backend = HeadlessBackend::new(Vector2::new(320, 240));
+  let input_sender = backend.input_sender();
+  let mut windows = Windows::with_backend(Box::new(backend));
+
+  let mut display_setting = DisplaySetting::new(Rect {
+    position: Vector2::new(0, 0),
+    size: Vector2::new(800, 600),
+  });
+  display_setting.close_on_escape = true;
+  let key = windows.add(display_setting);
+  assert!(windows.primary() == Some(key));
+  assert_eq!(windows.framebuffer_size(key), Some(Vector2::new(320, 240)));
+
+  input_sender.send(Input::Char('a')).unwrap();
+  assert!(windows.input(key).is_empty());
+  windows.poll_inputs();
+  assert_eq!(windows.input(key), vec![Input::Char('a')]);
+  assert!(windows.input(key).is_empty());
+
+  input_sender.send(Input::FramebufferResize { width: 1024, height: 768 }).unwrap();
+  windows.poll_inputs();
+  windows.input(key);
+  assert_eq!(windows.get(key).unwrap().get_screen_size(), Vector2::new(1024, 768));
+
+  windows.swap_buffers(key);
+  input_sender
+    .send(Input::Key(ale_input::Key::Escape, 0, Action::Press, Modifier::empty()))
+    .unwrap();
+  windows.poll_inputs();
+  assert!(windows.input(key).is_empty());
+  windows.cleanup();
+  assert_eq!(windows.len(), 0);
+  assert!(windows.primary().is_none());
+}
+
+#[test]
+fn windows_window_mode_keeps_windowed_size() {
+  use crate::headless::HeadlessBackend;
+
+  let backend = HeadlessBackend::new(Vector2::new(800, 600));
+  let input_sender = backend.input_sender();
+  let mut windows = Windows::with_backend(Box::new(backend));
+  let key = windows.add(DisplaySetting::new(Rect {
+    position: Vector2::new(0, 0),
+    size: Vector2::new(800, 600),
+  }));
+
+  windows.set_window_mode(key, WindowMode::Borderless, TargetMonitor::SECOND);
+  input_sender
+    .send(Input::FramebufferResize { width: 1920, height: 1080 })
+    .unwrap();
+  windows.poll_inputs();
+  windows.input(key);
+
+  let window = windows.get(key).unwrap();
+  assert_eq!(window.display_setting.window_mode, WindowMode::Borderless);
+  assert_eq!(window.display_setting.monitor, TargetMonitor::SECOND);
+  assert_eq!(window.get_screen_size(), Vector2::new(1920, 1080));
+  assert_eq!(window.windowed_size, Vector2::new(800, 600));
+
+  windows.set_title(key, "Tetris");
+  windows.set_vsync(key, true);
+  let display_setting = windows.get(key).unwrap().get_display_info();
+  assert_eq!(display_setting.title, "Tetris");
+  assert!(display_setting.vsync);
+}
+
+#[test]
+fn windows_content_scale_logical_size() {
+  use crate::headless::HeadlessBackend;
+
+  let backend = HeadlessBackend::new(Vector2::new(1600, 1200));
+  let input_sender = backend.input_sender();
+  let mut windows = Windows::with_backend(Box::new(backend));
+  let key = windows.add(DisplaySetting::new(Rect {
+    position: Vector2::new(0, 0),
+    size: Vector2::new(1600, 1200),
+  }));
+
+  input_sender.send(Input::ContentScale { x: 2.0, y: 2.0 }).unwrap();
+  windows.poll_inputs();
+  windows.input(key);
+
+  let window = windows.get(key).unwrap();
+  assert_eq!(window.get_content_scale(), Vector2::new(2.0, 2.0));
+  assert_eq!(window.get_framebuffer_size(), Vector2::new(1600, 1200));
+  assert_eq!(window.get_screen_size(), Vector2::new(800, 600));
+  assert_eq!(window.windowed_size, Vector2::new(800, 600));
+}
@@ -1,6 +1,7 @@
 use ale_math::rect::Rect;
+use ale_math::Vector2;
 
-#[derive(Clone)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum TargetMonitor {
   PRIMARY,
   SECOND,
@@ -9,19 +10,111 @@ pub enum TargetMonitor {
   FIFTH,
 }
 
+impl TargetMonitor {
+  // Index into the connected monitors, PRIMARY is always the first
+  pub fn index(&self) -> usize {
+    match self {
+      TargetMonitor::PRIMARY => 0,
+      TargetMonitor::SECOND => 1,
+      TargetMonitor::THIRD => 2,
+      TargetMonitor::FOURTH => 3,
+      TargetMonitor::FIFTH => 4,
+    }
+  }
+
+  // Indices past FIFTH fall back to PRIMARY
+  pub fn from_index(index: usize) -> TargetMonitor {
+    match index {
+      1 => TargetMonitor::SECOND,
+      2 => TargetMonitor::THIRD,
+      3 => TargetMonitor::FOURTH,
+      4 => TargetMonitor::FIFTH,
+      _ => TargetMonitor::PRIMARY,
+    }
+  }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct VideoMode {
+  pub width: u32,
+  pub height: u32,
+  // None picks the highest the monitor supports
+  pub refresh_rate: Option<u32>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum WindowMode {
+  Windowed,
+  // undecorated window covering the monitor at its current video mode
+  Borderless,
+  // exclusive, None keeps the monitor's current video mode
+  Fullscreen(Option<VideoMode>),
+}
+
+// Pixels are RGBA, row by row from the top left
+#[derive(Clone, Debug)]
+pub struct WindowIcon {
+  pub width: u32,
+  pub height: u32,
+  pub pixels: Vec<u8>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum CursorMode {
+  Normal,
+  // invisible over the window, but free to leave it
+  Hidden,
+  // invisible and locked to the window, for mouse-look
+  Captured,
+}
+
 #[derive(Clone)]
 pub struct DisplaySetting {
-  pub dimension: Rect,
-  pub initial_target: TargetMonitor,
+  // size follows framebuffer resizes, position is unused as windows are centered on their monitor
+  pub dimension: Rect<i32>,
+  pub window_mode: WindowMode,
+  // monitor used by Borderless and Fullscreen, missing monitors fall back to PRIMARY
+  pub monitor: TargetMonitor,
   pub is_hidden: bool,
+  pub is_resizable: bool,
+  pub title: String,
+  pub icon: Option<WindowIcon>,
+  pub vsync: bool,
+  // None disables multisampling
+  pub msaa_samples: Option<u32>,
+  // OpenGL core profile (major, minor)
+  pub gl_version: (u32, u32),
+  pub cursor_mode: CursorMode,
+  // Escape closes the window instead of reaching the game
+  pub close_on_escape: bool,
+  // unaccelerated motion while the cursor is Captured, if the platform supports it
+  pub raw_mouse_motion: bool,
 }
 
 impl DisplaySetting {
-  pub fn new(dimension: Rect) -> DisplaySetting {
-    DisplaySetting { dimension, initial_target: TargetMonitor::PRIMARY, is_hidden: false }
+  pub fn new(dimension: Rect<i32>) -> DisplaySetting {
+    DisplaySetting {
+      dimension,
+      window_mode: WindowMode::Windowed,
+      monitor: TargetMonitor::PRIMARY,
+      is_hidden: false,
+      is_resizable: true,
+      title: "Alers".to_owned(),
+      icon: None,
+      vsync: false,
+      msaa_samples: None,
+      gl_version: (3, 3),
+      cursor_mode: CursorMode::Normal,
+      close_on_escape: false,
+      raw_mouse_motion: false,
+    }
   }
 
-  pub fn get_dimension(&self) -> &Rect {
+  pub fn get_dimension(&self) -> &Rect<i32> {
     &self.dimension
   }
+
+  pub fn get_size(&self) -> Vector2<u32> {
+    Vector2::new(self.dimension.size.x.max(0) as u32, self.dimension.size.y.max(0) as u32)
+  }
 }
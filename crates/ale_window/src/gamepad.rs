@@ -0,0 +1,67 @@
+use std::collections::BTreeMap;
+
+use ale_input::{GamepadId, GamepadState, Input};
+use glfw::JoystickId;
+
+use crate::input_translator::{translate_gamepad_changes, translate_gamepad_state, translate_joystick_id};
+
+const JOYSTICK_IDS: [JoystickId; 16] = [
+  JoystickId::Joystick1,
+  JoystickId::Joystick2,
+  JoystickId::Joystick3,
+  JoystickId::Joystick4,
+  JoystickId::Joystick5,
+  JoystickId::Joystick6,
+  JoystickId::Joystick7,
+  JoystickId::Joystick8,
+  JoystickId::Joystick9,
+  JoystickId::Joystick10,
+  JoystickId::Joystick11,
+  JoystickId::Joystick12,
+  JoystickId::Joystick13,
+  JoystickId::Joystick14,
+  JoystickId::Joystick15,
+  JoystickId::Joystick16,
+];
+
+// Gamepads aren't tied to a window, glfw only reports them by polling.
+// Joysticks without a gamepad mapping are ignored.
+pub struct Gamepads {
+  gamepads: BTreeMap<GamepadId, GamepadState>,
+}
+
+impl Gamepads {
+  pub fn new() -> Gamepads {
+    Gamepads {
+      gamepads: BTreeMap::new(),
+    }
+  }
+
+  // Connect, disconnect, button and axis events since the last poll
+  pub fn poll(&mut self, glfw: &glfw::Glfw) -> Vec<Input> {
+    let mut inputs = vec![];
+    for joystick_id in JOYSTICK_IDS {
+      let joystick = glfw.get_joystick(joystick_id);
+      let current = match joystick.is_gamepad() {
+        false => None,
+        true => joystick.get_gamepad_state().map(|state| translate_gamepad_state(&state)),
+      };
+
+      let id = translate_joystick_id(joystick_id);
+      inputs.extend(translate_gamepad_changes(id, self.gamepads.get(&id), current.as_ref()));
+      match current {
+        None => self.gamepads.remove(&id),
+        Some(current) => self.gamepads.insert(id, current),
+      };
+    }
+    inputs
+  }
+
+  pub fn get(&self, id: GamepadId) -> Option<&GamepadState> {
+    self.gamepads.get(&id)
+  }
+
+  pub fn ids(&self) -> Vec<GamepadId> {
+    self.gamepads.keys().cloned().collect()
+  }
+}
@@ -0,0 +1,414 @@
+use std::collections::HashMap;
+use std::sync::mpsc::Receiver;
+
+use ale_input::Input;
+use ale_math::Vector2;
+use glfw::{Context, SwapInterval, WindowEvent};
+
+use crate::backend::{BackendWindowId, WindowBackend};
+use crate::display::{CursorMode, DisplaySetting, TargetMonitor, WindowIcon, WindowMode};
+use crate::gamepad::Gamepads;
+use crate::input_translator::{
+  translate_action, translate_key, translate_modifier, translate_mousebutton, translate_scancode,
+};
+
+struct GlfwWindow {
+  glfw_window: glfw::Window,
+  glfw_events: Receiver<(f64, WindowEvent)>,
+  mouse_position: Option<(f64, f64)>,
+}
+
+// Where a window goes on its monitor for a WindowMode
+struct GlfwPlacement<'a> {
+  mode: glfw::WindowMode<'a>,
+  position: Option<(i32, i32)>,
+  size: (u32, u32),
+  refresh_rate: Option<u32>,
+  is_decorated: bool,
+}
+
+// Desktop windows with an OpenGL core context each, 3.3 unless DisplaySetting asks otherwise
+pub struct GlfwBackend {
+  glfw: glfw::Glfw,
+  windows: HashMap<BackendWindowId, GlfwWindow>,
+  next_id: BackendWindowId,
+  gamepads: Gamepads,
+}
+
+impl GlfwBackend {
+  pub fn new() -> GlfwBackend {
+    GlfwBackend {
+      glfw: glfw::init(glfw::FAIL_ON_ERRORS).unwrap(),
+      windows: HashMap::new(),
+      next_id: 0,
+      gamepads: Gamepads::new(),
+    }
+  }
+
+  fn intern_window(&self, id: BackendWindowId) -> &GlfwWindow {
+    self.windows.get(&id).expect("glfw window missing")
+  }
+
+  fn intern_window_mut(&mut self, id: BackendWindowId) -> &mut GlfwWindow {
+    self.windows.get_mut(&id).expect("glfw window missing")
+  }
+}
+
+impl WindowBackend for GlfwBackend {
+  fn create(&mut self, display_setting: &DisplaySetting, shared: Option<BackendWindowId>) -> BackendWindowId {
+    let (gl_major, gl_minor) = display_setting.gl_version;
+    self.glfw.window_hint(glfw::WindowHint::ContextVersion(gl_major, gl_minor));
+    self
+      .glfw
+      .window_hint(glfw::WindowHint::OpenGlProfile(glfw::OpenGlProfileHint::Core));
+    self.glfw.window_hint(glfw::WindowHint::Visible(!display_setting.is_hidden));
+    self
+      .glfw
+      .window_hint(glfw::WindowHint::Resizable(display_setting.is_resizable));
+    self
+      .glfw
+      .window_hint(glfw::WindowHint::Samples(display_setting.msaa_samples));
+    // sizes are logical on every platform, not only where the OS scales windows itself
+    self.glfw.window_hint(glfw::WindowHint::ScaleToMonitor(true));
+    #[cfg(target_os = "macos")]
+    self.glfw.window_hint(glfw::WindowHint::OpenGlForwardCompat(true));
+
+    let windows = &self.windows;
+    let (mut glfw_window, glfw_events, position) = self
+      .glfw
+      .with_connected_monitors(|glfw, monitors| {
+        let placement = intern_placement(
+          display_setting.window_mode,
+          intern_monitor(monitors, display_setting.monitor),
+          display_setting.get_size(),
+        );
+        glfw.window_hint(glfw::WindowHint::Decorated(placement.is_decorated));
+        glfw.window_hint(glfw::WindowHint::RefreshRate(placement.refresh_rate));
+
+        let (width, height) = placement.size;
+        let created = match shared {
+          None => glfw.create_window(width, height, &display_setting.title, placement.mode),
+          Some(shared) => windows
+            .get(&shared)
+            .expect("glfw window missing")
+            .glfw_window
+            .create_shared(width, height, &display_setting.title, placement.mode),
+        };
+        created.map(|(glfw_window, glfw_events)| (glfw_window, glfw_events, placement.position))
+      })
+      .expect("Failed to create GLFW window");
+
+    if let Some((x, y)) = position {
+      glfw_window.set_pos(x, y);
+    }
+    if let Some(icon) = &display_setting.icon {
+      glfw_window.set_icon_from_pixels(vec![intern_pixel_image(icon)]);
+    }
+
+    glfw_window.make_current();
+    intern_set_swap_interval(&mut glfw_window, display_setting.vsync);
+    glfw_window.set_char_polling(true);
+    glfw_window.set_key_polling(true);
+    glfw_window.set_cursor_pos_polling(true);
+    glfw_window.set_mouse_button_polling(true);
+    glfw_window.set_scroll_polling(true);
+    glfw_window.set_cursor_enter_polling(true);
+    glfw_window.set_focus_polling(true);
+    glfw_window.set_framebuffer_size_polling(true);
+    glfw_window.set_content_scale_polling(true);
+
+    // gl: load all OpenGL function pointers
+    // ---------------------------------------
+    ale_opengl::raw::load_with(|symbol| glfw_window.get_proc_address(symbol) as *const _);
+
+    let id = self.next_id;
+    self.next_id += 1;
+    self.windows.insert(
+      id,
+      GlfwWindow {
+        glfw_window,
+        glfw_events,
+        mouse_position: None,
+      },
+    );
+    id
+  }
+
+  fn destroy(&mut self, id: BackendWindowId) {
+    self.windows.remove(&id);
+  }
+
+  fn poll(&mut self) {
+    self.glfw.poll_events();
+  }
+
+  fn input(&mut self, id: BackendWindowId) -> Vec<Input> {
+    let window = self.intern_window_mut(id);
+    let (width, height) = window.glfw_window.get_size();
+    let screen_to_logical = intern_screen_to_logical(&window.glfw_window);
+
+    let mut inputs = vec![];
+    for (_, event) in glfw::flush_messages(&window.glfw_events) {
+      match event {
+        WindowEvent::Key(key, scancode, action, modifier) => inputs.push(Input::Key(
+          translate_key(key),
+          translate_scancode(scancode),
+          translate_action(action),
+          translate_modifier(modifier),
+        )),
+        WindowEvent::CursorPos(x, y) => {
+          let (rel_x, rel_y) = match window.mouse_position {
+            None => (0.0f32, 0.0f32),
+            Some(mouse_position) => (
+              (x - mouse_position.0) as f32 / width.max(1) as f32,
+              (y - mouse_position.1) as f32 / height.max(1) as f32,
+            ),
+          };
+          window.mouse_position = Some((x, y));
+          inputs.push(Input::MouseMotion {
+            rel_x,
+            rel_y,
+            abs_x: x as f32 * screen_to_logical.x,
+            abs_y: y as f32 * screen_to_logical.y,
+          });
+        }
+        WindowEvent::Char(char) => {
+          inputs.push(Input::Char(char));
+        }
+        WindowEvent::MouseButton(mbtn, action, modifier) => inputs.push(Input::MouseButton(
+          translate_mousebutton(mbtn),
+          translate_action(action),
+          translate_modifier(modifier),
+        )),
+        WindowEvent::Scroll(x, y) => inputs.push(Input::Scroll {
+          x: x as f32,
+          y: y as f32,
+        }),
+        WindowEvent::CursorEnter(true) => inputs.push(Input::CursorEnter),
+        WindowEvent::CursorEnter(false) => {
+          // the cursor can come back anywhere, don't report the jump as motion
+          window.mouse_position = None;
+          inputs.push(Input::CursorLeave);
+        }
+        WindowEvent::Focus(true) => inputs.push(Input::FocusGained),
+        WindowEvent::Focus(false) => inputs.push(Input::FocusLost),
+        WindowEvent::FramebufferSize(width, height) if width > 0 && height > 0 => {
+          inputs.push(Input::FramebufferResize {
+            width: width as u32,
+            height: height as u32,
+          })
+        }
+        WindowEvent::ContentScale(x, y) => inputs.push(Input::ContentScale { x, y }),
+        _ => {}
+      }
+    }
+    inputs
+  }
+
+  fn poll_gamepads(&mut self) -> Vec<Input> {
+    self.gamepads.poll(&self.glfw)
+  }
+
+  fn has_gl_context(&self) -> bool {
+    true
+  }
+
+  fn make_current(&mut self, id: BackendWindowId) {
+    self.intern_window_mut(id).glfw_window.make_current();
+  }
+
+  // glfw applies the swap interval to the current context
+  fn set_vsync(&mut self, id: BackendWindowId, vsync: bool) {
+    let window = self.intern_window_mut(id);
+    window.glfw_window.make_current();
+    intern_set_swap_interval(&mut window.glfw_window, vsync);
+  }
+
+  fn swap(&mut self, id: BackendWindowId) {
+    self.intern_window_mut(id).glfw_window.swap_buffers();
+  }
+
+  fn framebuffer_size(&self, id: BackendWindowId) -> Vector2<u32> {
+    let (width, height) = self.intern_window(id).glfw_window.get_framebuffer_size();
+    Vector2::new(width.max(0) as u32, height.max(0) as u32)
+  }
+
+  fn content_scale(&self, id: BackendWindowId) -> Vector2<f32> {
+    let (x, y) = self.intern_window(id).glfw_window.get_content_scale();
+    Vector2::new(x, y)
+  }
+
+  fn close(&mut self, id: BackendWindowId) {
+    self.intern_window_mut(id).glfw_window.set_should_close(true);
+  }
+
+  fn is_closing(&self, id: BackendWindowId) -> bool {
+    self.intern_window(id).glfw_window.should_close()
+  }
+
+  fn is_focused(&self, id: BackendWindowId) -> bool {
+    self.intern_window(id).glfw_window.is_focused()
+  }
+
+  fn set_cursor_mode(&mut self, id: BackendWindowId, cursor_mode: CursorMode) {
+    self.intern_window_mut(id).glfw_window.set_cursor_mode(match cursor_mode {
+      CursorMode::Normal => glfw::CursorMode::Normal,
+      CursorMode::Hidden => glfw::CursorMode::Hidden,
+      CursorMode::Captured => glfw::CursorMode::Disabled,
+    });
+  }
+
+  fn set_raw_mouse_motion(&mut self, id: BackendWindowId, raw_mouse_motion: bool) -> bool {
+    if !self.glfw.supports_raw_motion() {
+      return !raw_mouse_motion;
+    }
+    self.intern_window_mut(id).glfw_window.set_raw_mouse_motion(raw_mouse_motion);
+    true
+  }
+
+  fn set_window_mode(
+    &mut self,
+    id: BackendWindowId,
+    window_mode: WindowMode,
+    monitor: TargetMonitor,
+    windowed_size: Vector2<u32>,
+  ) {
+    let window = self.windows.get_mut(&id).expect("glfw window missing");
+    let screen_to_logical = intern_screen_to_logical(&window.glfw_window);
+    let windowed_size = Vector2::new(
+      (windowed_size.x as f32 / screen_to_logical.x).round() as u32,
+      (windowed_size.y as f32 / screen_to_logical.y).round() as u32,
+    );
+    self.glfw.with_connected_monitors(|_, monitors| {
+      let placement = intern_placement(window_mode, intern_monitor(monitors, monitor), windowed_size);
+      let (x, y) = placement.position.unwrap_or_else(|| window.glfw_window.get_pos());
+      let (width, height) = placement.size;
+
+      window.glfw_window.set_decorated(placement.is_decorated);
+      window
+        .glfw_window
+        .set_monitor(placement.mode, x, y, width, height, placement.refresh_rate);
+    });
+    // the cursor lands somewhere else, don't report the jump as motion
+    window.mouse_position = None;
+  }
+
+  fn set_title(&mut self, id: BackendWindowId, title: &str) {
+    self.intern_window_mut(id).glfw_window.set_title(title);
+  }
+
+  fn set_icon(&mut self, id: BackendWindowId, icon: Option<&WindowIcon>) {
+    let images = icon.map(|icon| vec![intern_pixel_image(icon)]).unwrap_or_default();
+    self.intern_window_mut(id).glfw_window.set_icon_from_pixels(images);
+  }
+
+  fn set_resizable(&mut self, id: BackendWindowId, is_resizable: bool) {
+    self.intern_window_mut(id).glfw_window.set_resizable(is_resizable);
+  }
+}
+
+// The window's context has to be current
+fn intern_set_swap_interval(glfw_window: &mut glfw::Window, vsync: bool) {
+  if vsync {
+    glfw_window.glfw.set_swap_interval(SwapInterval::Sync(1));
+  } else {
+    glfw_window.glfw.set_swap_interval(SwapInterval::None);
+  }
+}
+
+// Cursor positions and window sizes are screen coordinates, which are pixels on Windows and
+// X11 but already logical on macOS. Going through pixels first gives logical units on both.
+fn intern_screen_to_logical(glfw_window: &glfw::Window) -> Vector2<f32> {
+  let (width, height) = glfw_window.get_size();
+  let (framebuffer_width, framebuffer_height) = glfw_window.get_framebuffer_size();
+  let (scale_x, scale_y) = glfw_window.get_content_scale();
+  if width <= 0 || height <= 0 || scale_x <= 0.0 || scale_y <= 0.0 {
+    return Vector2::new(1.0, 1.0);
+  }
+  Vector2::new(
+    framebuffer_width as f32 / width as f32 / scale_x,
+    framebuffer_height as f32 / height as f32 / scale_y,
+  )
+}
+
+// Falls back to the primary monitor when the target isn't connected
+fn intern_monitor<'a>(monitors: &'a [&mut glfw::Monitor], monitor: TargetMonitor) -> Option<&'a glfw::Monitor> {
+  monitors
+    .get(monitor.index())
+    .or_else(|| monitors.first())
+    .map(|monitor| &**monitor)
+}
+
+// Without a monitor every mode falls back to an uncentered Windowed
+fn intern_placement<'a>(
+  window_mode: WindowMode,
+  monitor: Option<&'a glfw::Monitor>,
+  windowed_size: Vector2<u32>,
+) -> GlfwPlacement<'a> {
+  let windowed = GlfwPlacement {
+    mode: glfw::WindowMode::Windowed,
+    position: None,
+    size: (windowed_size.x, windowed_size.y),
+    refresh_rate: None,
+    is_decorated: true,
+  };
+  let (monitor, video_mode) = match monitor.and_then(|monitor| monitor.get_video_mode().map(|mode| (monitor, mode))) {
+    None => return windowed,
+    Some(current) => current,
+  };
+  let (monitor_x, monitor_y) = monitor.get_pos();
+
+  match window_mode {
+    WindowMode::Windowed => GlfwPlacement {
+      position: Some((
+        monitor_x + (video_mode.width as i32 - windowed_size.x as i32).max(0) / 2,
+        monitor_y + (video_mode.height as i32 - windowed_size.y as i32).max(0) / 2,
+      )),
+      ..windowed
+    },
+    WindowMode::Borderless => GlfwPlacement {
+      mode: glfw::WindowMode::Windowed,
+      position: Some((monitor_x, monitor_y)),
+      size: (video_mode.width, video_mode.height),
+      refresh_rate: Some(video_mode.refresh_rate),
+      is_decorated: false,
+    },
+    WindowMode::Fullscreen(None) => GlfwPlacement {
+      mode: glfw::WindowMode::FullScreen(monitor),
+      position: None,
+      size: (video_mode.width, video_mode.height),
+      refresh_rate: Some(video_mode.refresh_rate),
+      is_decorated: true,
+    },
+    WindowMode::Fullscreen(Some(requested)) => {
+      let refresh_rate = requested.refresh_rate.or_else(|| {
+        monitor
+          .get_video_modes()
+          .iter()
+          .filter(|mode| mode.width == requested.width && mode.height == requested.height)
+          .map(|mode| mode.refresh_rate)
+          .max()
+      });
+      GlfwPlacement {
+        mode: glfw::WindowMode::FullScreen(monitor),
+        position: None,
+        size: (requested.width, requested.height),
+        refresh_rate,
+        is_decorated: true,
+      }
+    }
+  }
+}
+
+// glfw reads each u32 as 4 bytes in memory order, RGBA
+fn intern_pixel_image(icon: &WindowIcon) -> glfw::PixelImage {
+  glfw::PixelImage {
+    width: icon.width,
+    height: icon.height,
+    pixels: icon
+      .pixels
+      .chunks_exact(4)
+      .map(|rgba| u32::from_ne_bytes([rgba[0], rgba[1], rgba[2], rgba[3]]))
+      .collect(),
+  }
+}
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use ale_data::channel::{Channel, Sender};
+use ale_input::Input;
+use ale_math::Vector2;
+
+use crate::backend::{BackendWindowId, WindowBackend};
+use crate::display::{CursorMode, DisplaySetting, TargetMonitor, WindowIcon, WindowMode};
+
+struct HeadlessWindow {
+  framebuffer_size: Vector2<u32>,
+  is_closing: bool,
+}
+
+// No display and no GL context, swaps do nothing.
+// Inputs sent through input_sender are delivered to the window created first.
+//
+//   let backend = HeadlessBackend::new(Vector2::new(800, 600));
+//   let input_sender = backend.input_sender();
+//   let mut windows = Windows::with_backend(Box::new(backend));
+//   input_sender.send(Input::Char('a')).unwrap();
+pub struct HeadlessBackend {
+  framebuffer_size: Vector2<u32>,
+  windows: HashMap<BackendWindowId, HeadlessWindow>,
+  next_id: BackendWindowId,
+
+  inputs: Channel<Input>,
+  // polled but not yet read by input
+  pending: Vec<Input>,
+}
+
+impl HeadlessBackend {
+  // Every window reports framebuffer_size regardless of its DisplaySetting
+  pub fn new(framebuffer_size: Vector2<u32>) -> HeadlessBackend {
+    HeadlessBackend {
+      framebuffer_size,
+      windows: HashMap::new(),
+      next_id: 0,
+      inputs: Channel::new(),
+      pending: vec![],
+    }
+  }
+
+  pub fn input_sender(&self) -> Sender<Input> {
+    self.inputs.sender.clone()
+  }
+
+  fn intern_window(&self, id: BackendWindowId) -> &HeadlessWindow {
+    self.windows.get(&id).expect("headless window missing")
+  }
+}
+
+impl WindowBackend for HeadlessBackend {
+  fn create(&mut self, _display_setting: &DisplaySetting, _shared: Option<BackendWindowId>) -> BackendWindowId {
+    let id = self.next_id;
+    self.next_id += 1;
+    self.windows.insert(
+      id,
+      HeadlessWindow {
+        framebuffer_size: self.framebuffer_size,
+        is_closing: false,
+      },
+    );
+    id
+  }
+
+  fn destroy(&mut self, id: BackendWindowId) {
+    self.windows.remove(&id);
+  }
+
+  fn poll(&mut self) {
+    self.pending.extend(self.inputs.receiver.try_iter());
+  }
+
+  fn input(&mut self, id: BackendWindowId) -> Vec<Input> {
+    match self.windows.keys().min() {
+      Some(first) if *first == id => self.pending.drain(..).collect(),
+      _ => vec![],
+    }
+  }
+
+  fn poll_gamepads(&mut self) -> Vec<Input> {
+    vec![]
+  }
+
+  fn has_gl_context(&self) -> bool {
+    false
+  }
+
+  fn make_current(&mut self, _id: BackendWindowId) {}
+
+  fn set_vsync(&mut self, _id: BackendWindowId, _vsync: bool) {}
+
+  fn swap(&mut self, _id: BackendWindowId) {}
+
+  fn framebuffer_size(&self, id: BackendWindowId) -> Vector2<u32> {
+    self.intern_window(id).framebuffer_size
+  }
+
+  fn content_scale(&self, _id: BackendWindowId) -> Vector2<f32> {
+    Vector2::new(1.0, 1.0)
+  }
+
+  fn close(&mut self, id: BackendWindowId) {
+    if let Some(window) = self.windows.get_mut(&id) {
+      window.is_closing = true;
+    }
+  }
+
+  fn is_closing(&self, id: BackendWindowId) -> bool {
+    self.intern_window(id).is_closing
+  }
+
+  fn is_focused(&self, _id: BackendWindowId) -> bool {
+    true
+  }
+
+  fn set_cursor_mode(&mut self, _id: BackendWindowId, _cursor_mode: CursorMode) {}
+
+  fn set_raw_mouse_motion(&mut self, _id: BackendWindowId, _raw_mouse_motion: bool) -> bool {
+    true
+  }
+
+  fn set_window_mode(
+    &mut self,
+    _id: BackendWindowId,
+    _window_mode: WindowMode,
+    _monitor: TargetMonitor,
+    _windowed_size: Vector2<u32>,
+  ) {
+  }
+
+  fn set_title(&mut self, _id: BackendWindowId, _title: &str) {}
+
+  fn set_icon(&mut self, _id: BackendWindowId, _icon: Option<&WindowIcon>) {}
+
+  fn set_resizable(&mut self, _id: BackendWindowId, _is_resizable: bool) {}
+}
@@ -1,4 +1,5 @@
-use glfw::{Action, Key, MouseButton};
+use ale_input::{GamepadId, GamepadState, Input, GAMEPAD_AXES, GAMEPAD_BUTTONS};
+use glfw::{Action, GamepadAxis, GamepadButton, JoystickId, Key, MouseButton};
 
 pub fn translate_key(glfw_key: glfw::Key) -> ale_input::Key {
   match glfw_key {
@@ -173,3 +174,223 @@ pub fn translate_mousebutton(glfw_mouse: glfw::MouseButton) -> ale_input::MouseB
     MouseButton::Button8 => ale_input::MouseButton::Button8,
   }
 }
+
+pub fn translate_joystick_id(glfw_joystick_id: glfw::JoystickId) -> ale_input::GamepadId {
+  glfw_joystick_id as GamepadId
+}
+
+pub fn translate_gamepad_button(glfw_gamepad_button: glfw::GamepadButton) -> ale_input::GamepadButton {
+  match glfw_gamepad_button {
+    GamepadButton::ButtonA => ale_input::GamepadButton::A,
+    GamepadButton::ButtonB => ale_input::GamepadButton::B,
+    GamepadButton::ButtonX => ale_input::GamepadButton::X,
+    GamepadButton::ButtonY => ale_input::GamepadButton::Y,
+    GamepadButton::ButtonLeftBumper => ale_input::GamepadButton::LeftBumper,
+    GamepadButton::ButtonRightBumper => ale_input::GamepadButton::RightBumper,
+    GamepadButton::ButtonBack => ale_input::GamepadButton::Back,
+    GamepadButton::ButtonStart => ale_input::GamepadButton::Start,
+    GamepadButton::ButtonGuide => ale_input::GamepadButton::Guide,
+    GamepadButton::ButtonLeftThumb => ale_input::GamepadButton::LeftThumb,
+    GamepadButton::ButtonRightThumb => ale_input::GamepadButton::RightThumb,
+    GamepadButton::ButtonDpadUp => ale_input::GamepadButton::DpadUp,
+    GamepadButton::ButtonDpadRight => ale_input::GamepadButton::DpadRight,
+    GamepadButton::ButtonDpadDown => ale_input::GamepadButton::DpadDown,
+    GamepadButton::ButtonDpadLeft => ale_input::GamepadButton::DpadLeft,
+  }
+}
+
+pub fn untranslate_gamepad_button(gamepad_button: ale_input::GamepadButton) -> glfw::GamepadButton {
+  match gamepad_button {
+    ale_input::GamepadButton::A => GamepadButton::ButtonA,
+    ale_input::GamepadButton::B => GamepadButton::ButtonB,
+    ale_input::GamepadButton::X => GamepadButton::ButtonX,
+    ale_input::GamepadButton::Y => GamepadButton::ButtonY,
+    ale_input::GamepadButton::LeftBumper => GamepadButton::ButtonLeftBumper,
+    ale_input::GamepadButton::RightBumper => GamepadButton::ButtonRightBumper,
+    ale_input::GamepadButton::Back => GamepadButton::ButtonBack,
+    ale_input::GamepadButton::Start => GamepadButton::ButtonStart,
+    ale_input::GamepadButton::Guide => GamepadButton::ButtonGuide,
+    ale_input::GamepadButton::LeftThumb => GamepadButton::ButtonLeftThumb,
+    ale_input::GamepadButton::RightThumb => GamepadButton::ButtonRightThumb,
+    ale_input::GamepadButton::DpadUp => GamepadButton::ButtonDpadUp,
+    ale_input::GamepadButton::DpadRight => GamepadButton::ButtonDpadRight,
+    ale_input::GamepadButton::DpadDown => GamepadButton::ButtonDpadDown,
+    ale_input::GamepadButton::DpadLeft => GamepadButton::ButtonDpadLeft,
+  }
+}
+
+pub fn translate_gamepad_axis(glfw_gamepad_axis: glfw::GamepadAxis) -> ale_input::GamepadAxis {
+  match glfw_gamepad_axis {
+    GamepadAxis::AxisLeftX => ale_input::GamepadAxis::LeftX,
+    GamepadAxis::AxisLeftY => ale_input::GamepadAxis::LeftY,
+    GamepadAxis::AxisRightX => ale_input::GamepadAxis::RightX,
+    GamepadAxis::AxisRightY => ale_input::GamepadAxis::RightY,
+    GamepadAxis::AxisLeftTrigger => ale_input::GamepadAxis::LeftTrigger,
+    GamepadAxis::AxisRightTrigger => ale_input::GamepadAxis::RightTrigger,
+  }
+}
+
+pub fn untranslate_gamepad_axis(gamepad_axis: ale_input::GamepadAxis) -> glfw::GamepadAxis {
+  match gamepad_axis {
+    ale_input::GamepadAxis::LeftX => GamepadAxis::AxisLeftX,
+    ale_input::GamepadAxis::LeftY => GamepadAxis::AxisLeftY,
+    ale_input::GamepadAxis::RightX => GamepadAxis::AxisRightX,
+    ale_input::GamepadAxis::RightY => GamepadAxis::AxisRightY,
+    ale_input::GamepadAxis::LeftTrigger => GamepadAxis::AxisLeftTrigger,
+    ale_input::GamepadAxis::RightTrigger => GamepadAxis::AxisRightTrigger,
+  }
+}
+
+// glfw::GamepadState can only be created by glfw, this lets the translation run on any source
+pub trait GlfwGamepadState {
+  fn get_button_state(&self, button: glfw::GamepadButton) -> glfw::Action;
+  fn get_axis(&self, axis: glfw::GamepadAxis) -> f32;
+}
+
+impl GlfwGamepadState for glfw::GamepadState {
+  fn get_button_state(&self, button: glfw::GamepadButton) -> glfw::Action {
+    glfw::GamepadState::get_button_state(self, button)
+  }
+
+  fn get_axis(&self, axis: glfw::GamepadAxis) -> f32 {
+    glfw::GamepadState::get_axis(self, axis)
+  }
+}
+
+// Closures reporting button and axis state, for state that doesn't come from glfw
+impl<B, A> GlfwGamepadState for (B, A)
+where
+  B: Fn(glfw::GamepadButton) -> glfw::Action,
+  A: Fn(glfw::GamepadAxis) -> f32,
+{
+  fn get_button_state(&self, button: glfw::GamepadButton) -> glfw::Action {
+    (self.0)(button)
+  }
+
+  fn get_axis(&self, axis: glfw::GamepadAxis) -> f32 {
+    (self.1)(axis)
+  }
+}
+
+pub fn translate_gamepad_state<S: GlfwGamepadState>(glfw_gamepad_state: &S) -> GamepadState {
+  let mut gamepad_state = GamepadState::default();
+  for gamepad_button in GAMEPAD_BUTTONS {
+    gamepad_state.buttons[gamepad_button as usize] =
+      glfw_gamepad_state.get_button_state(untranslate_gamepad_button(gamepad_button)) != Action::Release;
+  }
+  for gamepad_axis in GAMEPAD_AXES {
+    gamepad_state.axes[gamepad_axis as usize] = glfw_gamepad_state.get_axis(untranslate_gamepad_axis(gamepad_axis));
+  }
+  gamepad_state
+}
+
+// Events turning previous into current, None is a disconnected gamepad.
+// Held buttons are released before a disconnect so nothing stays stuck down.
+pub fn translate_gamepad_changes(
+  id: GamepadId,
+  previous: Option<&GamepadState>,
+  current: Option<&GamepadState>,
+) -> Vec<Input> {
+  let mut inputs = vec![];
+  let rest = GamepadState::default();
+  let (previous, current, is_disconnected) = match (previous, current) {
+    (None, None) => return inputs,
+    (None, Some(current)) => {
+      inputs.push(Input::GamepadConnected(id));
+      (&rest, current, false)
+    }
+    (Some(previous), None) => (previous, &rest, true),
+    (Some(previous), Some(current)) => (previous, current, false),
+  };
+
+  for gamepad_button in GAMEPAD_BUTTONS {
+    match (previous.is_down(gamepad_button), current.is_down(gamepad_button)) {
+      (false, true) => inputs.push(Input::GamepadButton(id, gamepad_button, ale_input::Action::Press)),
+      (true, false) => inputs.push(Input::GamepadButton(id, gamepad_button, ale_input::Action::Release)),
+      _ => {}
+    }
+  }
+  for gamepad_axis in GAMEPAD_AXES {
+    if previous.axis(gamepad_axis) != current.axis(gamepad_axis) {
+      inputs.push(Input::GamepadAxis(id, gamepad_axis, current.axis(gamepad_axis)));
+    }
+  }
+
+  if is_disconnected {
+    inputs.push(Input::GamepadDisconnected(id));
+  }
+  inputs
+}
+
+#[test]
+fn translate_gamepad_state_standard_layout() {
+  // fabricated glfw state
+  let glfw_gamepad_state = (
+    |button: GamepadButton| match button {
+      GamepadButton::ButtonA | GamepadButton::ButtonDpadLeft => Action::Press,
+      _ => Action::Release,
+    },
+    |axis: GamepadAxis| match axis {
+      GamepadAxis::AxisLeftY => -0.5,
+      GamepadAxis::AxisLeftTrigger => -1.0,
+      GamepadAxis::AxisRightTrigger => 1.0,
+      _ => 0.0,
+    },
+  );
+  let gamepad_state = translate_gamepad_state(&glfw_gamepad_state);
+
+  assert!(gamepad_state.is_down(ale_input::GamepadButton::A));
+  assert!(gamepad_state.is_down(ale_input::GamepadButton::DpadLeft));
+  assert!(!gamepad_state.is_down(ale_input::GamepadButton::B));
+  assert_eq!(gamepad_state.axis(ale_input::GamepadAxis::LeftY), -0.5);
+  assert_eq!(gamepad_state.axis(ale_input::GamepadAxis::RightTrigger), 1.0);
+  assert_eq!(gamepad_state.axis(ale_input::GamepadAxis::LeftTrigger), -1.0);
+
+  for gamepad_button in GAMEPAD_BUTTONS {
+    assert_eq!(translate_gamepad_button(untranslate_gamepad_button(gamepad_button)), gamepad_button);
+  }
+  for gamepad_axis in GAMEPAD_AXES {
+    assert_eq!(translate_gamepad_axis(untranslate_gamepad_axis(gamepad_axis)), gamepad_axis);
+  }
+  assert_eq!(translate_joystick_id(JoystickId::Joystick3), 2);
+}
+
+#[test]
+fn translate_gamepad_changes_events() {
+  let triggers_released = |axis: GamepadAxis| match axis {
+    GamepadAxis::AxisLeftTrigger | GamepadAxis::AxisRightTrigger => -1.0,
+    _ => 0.0,
+  };
+  let idle = translate_gamepad_state(&(|_: GamepadButton| Action::Release, triggers_released));
+  let pressing = translate_gamepad_state(&(
+    |button: GamepadButton| match button {
+      GamepadButton::ButtonStart => Action::Press,
+      _ => Action::Release,
+    },
+    |axis: GamepadAxis| match axis {
+      GamepadAxis::AxisLeftX => 0.25,
+      axis => triggers_released(axis),
+    },
+  ));
+
+  assert_eq!(
+    translate_gamepad_changes(1, None, Some(&idle)),
+    vec![Input::GamepadConnected(1)]
+  );
+  assert!(translate_gamepad_changes(1, Some(&idle), Some(&idle)).is_empty());
+  assert_eq!(
+    translate_gamepad_changes(1, Some(&idle), Some(&pressing)),
+    vec![
+      Input::GamepadButton(1, ale_input::GamepadButton::Start, ale_input::Action::Press),
+      Input::GamepadAxis(1, ale_input::GamepadAxis::LeftX, 0.25),
+    ]
+  );
+  assert_eq!(
+    translate_gamepad_changes(1, Some(&pressing), None),
+    vec![
+      Input::GamepadButton(1, ale_input::GamepadButton::Start, ale_input::Action::Release),
+      Input::GamepadAxis(1, ale_input::GamepadAxis::LeftX, 0.0),
+      Input::GamepadDisconnected(1),
+    ]
+  );
+}
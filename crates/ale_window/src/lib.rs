@@ -1,7 +1,13 @@
 extern crate core;
 
 pub mod display;
+#[cfg(feature = "glfw")]
+pub mod gamepad;
+#[cfg(feature = "glfw")]
+pub mod glfw_backend;
+pub mod headless;
 pub mod backend;
+#[cfg(feature = "glfw")]
 pub mod input_translator;
 pub mod tick;
 pub mod window;
@@ -1,125 +1,82 @@
-use crate::display::{DisplaySetting, TargetMonitor};
-use crate::input_translator::{
-  translate_action, translate_key, translate_modifier, translate_mousebutton, translate_scancode,
-};
+use crate::backend::BackendWindowId;
+use crate::display::{CursorMode, DisplaySetting, WindowMode};
 use ale_data::alevec;
-use ale_input::Input;
-use ale_math::rect::Rect;
-use ale_math::{Vector2, Zero};
+use ale_math::Vector2;
 use ale_ui::element::Panel;
-use glfw::{Action, Context, CursorMode, Key, SwapInterval, WindowEvent};
-use std::sync::mpsc::Receiver;
-use std::sync::{Arc, RwLock};
 
+// Platform work (input, swapping, cursor) goes through Windows with the window's key
 pub struct Window {
-  pub glfw_window: glfw::Window,
-  glfw_events: Receiver<(f64, WindowEvent)>,
+  pub(crate) backend_id: BackendWindowId,
 
   pub is_hidden: bool, // hidden window, for context
   pub display_setting: DisplaySetting,
+  // size to restore when going back to Windowed
+  pub(crate) windowed_size: Vector2<u32>,
+  pub(crate) framebuffer_size: Vector2<u32>,
+  pub(crate) content_scale: Vector2<f32>,
 
-  mouse_position: Option<(f64, f64)>,
   pub panel_key: Option<alevec::Key<Panel>>,
 }
 
 impl Window {
-  pub fn new(
-    glfw_window: glfw::Window,
-    glfw_events: Receiver<(f64, WindowEvent)>,
-    display_setting: DisplaySetting,
-    is_hidden: bool,
-  ) -> Window {
+  pub fn new(backend_id: BackendWindowId, display_setting: DisplaySetting, is_hidden: bool) -> Window {
     Window {
-      glfw_window,
-      glfw_events,
+      backend_id,
       is_hidden,
+      windowed_size: display_setting.get_size(),
+      framebuffer_size: display_setting.get_size(),
+      content_scale: Vector2::new(1.0, 1.0),
       display_setting,
-      mouse_position: None,
       panel_key: None,
     }
   }
 
-  pub fn is_closing(&self) -> bool {
-    self.glfw_window.should_close()
+  pub fn cursor_mode(&self) -> CursorMode {
+    self.display_setting.cursor_mode
   }
 
-  pub fn close(&mut self) {
-    self.glfw_window.set_should_close(true);
+  pub fn set_close_on_escape(&mut self, close_on_escape: bool) {
+    self.display_setting.close_on_escape = close_on_escape;
   }
 
-  pub fn swap_buffers(&mut self) {
-    self.glfw_window.swap_buffers();
+  pub fn get_display_info(&self) -> &DisplaySetting {
+    &self.display_setting
   }
 
-  pub fn make_current(&mut self) {
-    self.glfw_window.make_current();
-    self.glfw_window.glfw.set_swap_interval(SwapInterval::None);
+  // Logical size, what layouts, cursor positions and orthographic cameras use
+  pub fn get_screen_size(&self) -> Vector2<u32> {
+    self.display_setting.get_size()
   }
 
-  pub fn input(&mut self) -> Vec<Input> {
-    let mut inputs = vec![];
-    for (_, event) in glfw::flush_messages(&self.glfw_events) {
-      match event {
-        glfw::WindowEvent::Key(Key::Escape, _, Action::Press, _) => self.glfw_window.set_should_close(true),
-        glfw::WindowEvent::Key(key, scancode, action, modifier) => {
-          let input = Input::Key(
-            translate_key(key),
-            translate_scancode(scancode),
-            translate_action(action),
-            translate_modifier(modifier),
-          );
-          if key == glfw::Key::I {
-            self.glfw_window.set_cursor_mode(CursorMode::Normal);
-          }
-          if key == glfw::Key::O {
-            self.glfw_window.set_cursor_mode(CursorMode::Disabled);
-          }
-          inputs.push(input);
-        }
-        glfw::WindowEvent::CursorPos(x, y) => inputs.push(match self.mouse_position {
-          None => {
-            self.mouse_position = Some((x, y));
-            Input::MouseMotion {
-              rel_x: 0.0f32,
-              rel_y: 0.0f32,
-              abs_x: x as f32,
-              abs_y: y as f32,
-            }
-          }
-          Some(mouse_position) => {
-            let result = Input::MouseMotion {
-              rel_x: (x - mouse_position.0) as f32 / self.display_setting.get_dimension().size.x as f32,
-              rel_y: (y - mouse_position.1) as f32 / self.display_setting.get_dimension().size.y as f32,
-              abs_x: x as f32,
-              abs_y: y as f32,
-            };
-            self.mouse_position = Some((x, y));
-            result
-          }
-        }),
-        glfw::WindowEvent::Char(char) => {
-          inputs.push(Input::Char(char));
-        }
-        glfw::WindowEvent::MouseButton(mbtn, action, modifier) => inputs.push(Input::MouseButton(
-          translate_mousebutton(mbtn),
-          translate_action(action),
-          translate_modifier(modifier),
-        )),
-        _ => {}
-      }
-    }
-    inputs
+  // Size in pixels, what viewports and render targets use
+  pub fn get_framebuffer_size(&self) -> Vector2<u32> {
+    self.framebuffer_size
   }
 
-  pub fn get_display_info(&self) -> &DisplaySetting {
-    &self.display_setting
+  // Pixels per logical unit, 2.0 on most HiDPI displays
+  pub fn get_content_scale(&self) -> Vector2<f32> {
+    self.content_scale
   }
 
-  pub fn get_screen_size(&self) -> Vector2<u32> {
-    Vector2::new(
-      self.display_setting.dimension.size.x,
-      self.display_setting.dimension.size.y,
-    )
+  pub(crate) fn set_framebuffer_size(&mut self, framebuffer_size: Vector2<u32>) {
+    self.framebuffer_size = framebuffer_size;
+    self.refresh_screen_size();
+  }
+
+  pub(crate) fn set_content_scale(&mut self, content_scale: Vector2<f32>) {
+    self.content_scale = content_scale;
+    self.refresh_screen_size();
+  }
+
+  fn refresh_screen_size(&mut self) {
+    let size = Vector2::new(
+      (self.framebuffer_size.x as f32 / self.content_scale.x).round() as i32,
+      (self.framebuffer_size.y as f32 / self.content_scale.y).round() as i32,
+    );
+    self.display_setting.dimension.size = size;
+    if self.display_setting.window_mode == WindowMode::Windowed {
+      self.windowed_size = self.display_setting.get_size();
+    }
   }
 
   pub fn attach_panel(&mut self, panel: alevec::Key<Panel>) {
@@ -0,0 +1,106 @@
+use ale_camera::component::Camera;
+use ale_camera::controller::CameraController;
+use ale_camera::follow::FollowCamera;
+use ale_camera::orbit::OrbitCamera;
+use ale_camera::pan_zoom::PanZoomCamera;
+use ale_camera::shake::CameraShake;
+use ale_data::entity::Entity;
+use ale_data::indexmap::Id;
+use ale_data::wire_component;
+use ale_input::state::InputState;
+use ale_input::Input;
+
+use crate::components::{Inputable, Spawnable, Tickable};
+use crate::world::World;
+
+// Wires the ale_camera controllers as Camera, Spawnable, Tickable and Inputable,
+// call once before spawning any of them
+pub fn register_components(world: &mut World) {
+  world.register_components(&[
+    wire_component!(dyn Camera, OrbitCamera),
+    wire_component!(dyn Spawnable, OrbitCamera),
+    wire_component!(dyn Tickable, OrbitCamera),
+    wire_component!(dyn Inputable, OrbitCamera),
+    wire_component!(dyn Camera, FollowCamera),
+    wire_component!(dyn Spawnable, FollowCamera),
+    wire_component!(dyn Tickable, FollowCamera),
+    wire_component!(dyn Inputable, FollowCamera),
+    wire_component!(dyn Camera, PanZoomCamera),
+    wire_component!(dyn Spawnable, PanZoomCamera),
+    wire_component!(dyn Tickable, PanZoomCamera),
+    wire_component!(dyn Inputable, PanZoomCamera),
+    wire_component!(dyn Camera, CameraShake<OrbitCamera>),
+    wire_component!(dyn Spawnable, CameraShake<OrbitCamera>),
+    wire_component!(dyn Tickable, CameraShake<OrbitCamera>),
+    wire_component!(dyn Inputable, CameraShake<OrbitCamera>),
+    wire_component!(dyn Camera, CameraShake<FollowCamera>),
+    wire_component!(dyn Spawnable, CameraShake<FollowCamera>),
+    wire_component!(dyn Tickable, CameraShake<FollowCamera>),
+    wire_component!(dyn Inputable, CameraShake<FollowCamera>),
+    wire_component!(dyn Camera, CameraShake<PanZoomCamera>),
+    wire_component!(dyn Spawnable, CameraShake<PanZoomCamera>),
+    wire_component!(dyn Tickable, CameraShake<PanZoomCamera>),
+    wire_component!(dyn Inputable, CameraShake<PanZoomCamera>),
+  ])
+}
+
+// ale_camera can't see the world traits, so they're implemented here
+macro_rules! impl_camera_controller_components {
+  ($controller:ty) => {
+    impl Spawnable for $controller {
+      fn on_spawn(&mut self) {}
+
+      fn on_kill(&mut self) {}
+
+      fn id(&self) -> Id<Entity> {
+        <$controller>::id(self)
+      }
+    }
+
+    impl Tickable for $controller {
+      fn fixed_tick(&mut self, _delta_time: f32) {}
+
+      fn tick(&mut self, delta_time: f32) {
+        CameraController::tick(self, delta_time);
+      }
+    }
+
+    impl Inputable for $controller {
+      fn input(&mut self, inputs: &Vec<Input>, input_state: &InputState) {
+        CameraController::input(self, inputs, input_state);
+      }
+    }
+  };
+}
+
+impl_camera_controller_components!(OrbitCamera);
+impl_camera_controller_components!(FollowCamera);
+impl_camera_controller_components!(PanZoomCamera);
+
+impl<C: CameraController + Spawnable> Spawnable for CameraShake<C> {
+  fn on_spawn(&mut self) {
+    self.inner_mut().on_spawn();
+  }
+
+  fn on_kill(&mut self) {
+    self.inner_mut().on_kill();
+  }
+
+  fn id(&self) -> Id<Entity> {
+    self.inner().id()
+  }
+}
+
+impl<C: CameraController + 'static> Tickable for CameraShake<C> {
+  fn fixed_tick(&mut self, _delta_time: f32) {}
+
+  fn tick(&mut self, delta_time: f32) {
+    CameraController::tick(self, delta_time);
+  }
+}
+
+impl<C: CameraController + 'static> Inputable for CameraShake<C> {
+  fn input(&mut self, inputs: &Vec<Input>, input_state: &InputState) {
+    CameraController::input(self, inputs, input_state);
+  }
+}
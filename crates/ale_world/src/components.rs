@@ -6,6 +6,7 @@ use ale_opengl::renderer;
 use ale_data::alevec::Key;
 use ale_data::entity::{Component, Entity};
 use ale_input::Input;
+use ale_input::state::InputState;
 
 pub trait Tickable: Component {
   fn fixed_tick(&mut self, delta_time: f32);
@@ -14,7 +15,8 @@ pub trait Tickable: Component {
 }
 
 pub trait Inputable: Component {
-  fn input(&mut self, inputs: &Vec<Input>);
+  // inputs are this frame's events, input_state has already been updated with them
+  fn input(&mut self, inputs: &Vec<Input>, input_state: &InputState);
 }
 
 pub trait Spawnable {
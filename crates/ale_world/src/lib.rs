@@ -4,3 +4,4 @@ pub mod world;
 pub mod components;
 pub mod visitor;
 pub mod event;
+pub mod camera;
@@ -6,4 +6,18 @@ pub trait Visitor<T : ?Sized> {
 
 pub trait VisitorMut<T: ?Sized>{
     fn visit(&mut self, component : &mut T);
-}
\ No newline at end of file
+}
+
+// Told whenever World::visit_mut_scoped moves on to another concrete type, e.g. to time each of them.
+// type_name is the implementing type as it was given to wire_component!
+pub trait VisitScope {
+    fn begin(&mut self, type_name: &str);
+
+    fn end(&mut self);
+}
+
+impl VisitScope for () {
+    fn begin(&mut self, _type_name: &str) {}
+
+    fn end(&mut self) {}
+}
@@ -10,7 +10,7 @@ use ale_data::indexmap::{AleIndexMap, AleIndexSet, Id};
 
 use crate::components::Spawnable;
 use crate::event::world::{KillCommand, SpawnCommand, WorldCommand};
-use crate::visitor::{Visitor, VisitorMut};
+use crate::visitor::{VisitScope, Visitor, VisitorMut};
 
 pub struct EntityMeta {
   impl_type: TypeId,
@@ -179,6 +179,12 @@ impl World {
   }
 
   pub fn visit_mut<T: ?Sized + 'static>(&mut self, visitor: &mut dyn VisitorMut<T>) {
+    self.visit_mut_scoped(visitor, &mut ());
+  }
+
+  // Same as visit_mut, with scope opened and closed around every run of the same concrete type.
+  // Components are still visited in spawn order, so a type can show up in more than one run.
+  pub fn visit_mut_scoped<T: ?Sized + 'static>(&mut self, visitor: &mut dyn VisitorMut<T>, scope: &mut dyn VisitScope) {
     let type_id = TypeId::of::<T>();
     let entity_keys = match self.component_to_entity.get(&type_id) {
       None => {
@@ -186,8 +192,27 @@ impl World {
       }
       Some(entity_keys) => entity_keys,
     };
+    let cast_table = self.registry.cast_into::<T>();
 
+    let mut current_type = None;
     for entity_key in entity_keys {
+      let impl_type = match self.entities_meta.get(entity_key) {
+        None => {
+          continue;
+        }
+        Some(entity_meta) => entity_meta.impl_type,
+      };
+      if current_type != Some(impl_type) {
+        if current_type.is_some() {
+          scope.end();
+        }
+        let type_name = cast_table
+          .and_then(|cast_table| cast_table.map.get(&impl_type))
+          .map_or("unknown", |entry| entry.into_name);
+        scope.begin(type_name);
+        current_type = Some(impl_type);
+      }
+
       let entity: &mut dyn Any = match self.entities.get_mut(entity_key) {
         None => {
           continue;
@@ -204,6 +229,9 @@ impl World {
         }
       }
     }
+    if current_type.is_some() {
+      scope.end();
+    }
   }
 
   pub fn resolve_world_commands(&mut self) {
@@ -222,3 +250,69 @@ impl World {
     return self.channel.sender.clone();
   }
 }
+
+#[test]
+fn world_visit_mut_scoped_names_each_type() {
+  use ale_data::wire_component;
+  use crate::components::Tickable;
+  use crate::visitor::VisitScope;
+
+  struct Ball(Id<Entity>);
+  struct Paddle(Id<Entity>);
+  macro_rules! impl_test_entity {
+    ($t:ty) => {
+      impl Spawnable for $t {
+        fn on_spawn(&mut self) {}
+
+        fn on_kill(&mut self) {}
+
+        fn id(&self) -> Id<Entity> {
+          self.0
+        }
+      }
+
+      impl Tickable for $t {
+        fn fixed_tick(&mut self, _delta_time: f32) {}
+
+        fn tick(&mut self, _delta_time: f32) {}
+      }
+    };
+  }
+  impl_test_entity!(Ball);
+  impl_test_entity!(Paddle);
+
+  struct CountTicks(usize);
+  impl VisitorMut<dyn Tickable> for CountTicks {
+    fn visit(&mut self, _component: &mut (dyn Tickable + 'static)) {
+      self.0 += 1;
+    }
+  }
+
+  struct RecordScopes(Vec<String>);
+  impl VisitScope for RecordScopes {
+    fn begin(&mut self, type_name: &str) {
+      self.0.push(format!("begin {}", type_name));
+    }
+
+    fn end(&mut self) {
+      self.0.push("end".to_owned());
+    }
+  }
+
+  let mut world = World::new();
+  world.register_components(&[
+    wire_component!(dyn Spawnable, Ball),
+    wire_component!(dyn Tickable, Ball),
+    wire_component!(dyn Spawnable, Paddle),
+    wire_component!(dyn Tickable, Paddle),
+  ]);
+  world.spawn(SpawnCommand::new(Paddle(world.gen_entity_key())));
+  world.spawn(SpawnCommand::new(Paddle(world.gen_entity_key())));
+  world.spawn(SpawnCommand::new(Ball(world.gen_entity_key())));
+
+  let mut count = CountTicks(0);
+  let mut scopes = RecordScopes(vec![]);
+  world.visit_mut_scoped(&mut count, &mut scopes);
+  assert_eq!(count.0, 3);
+  assert_eq!(scopes.0, vec!["begin Paddle", "end", "begin Ball", "end"]);
+}
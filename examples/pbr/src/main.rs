@@ -0,0 +1,76 @@
+use ale_app::app::{App, Genesis};
+use ale_app::engine::Engine;
+use ale_app::plugin::pbr::PbrPlugin;
+use ale_app::AppError;
+use ale_camera::orbit::OrbitCamera;
+use ale_camera::Camera;
+use ale_math::rect::Rect;
+use ale_math::{Vector2, Vector3, Zero};
+use ale_resources::mesh::Mesh;
+use ale_window::display::DisplaySetting;
+use ale_world::event::world::{SpawnCommand, WorldCommand};
+use ale_world::world::World;
+
+use crate::spinning_mesh::SpinningMesh;
+
+mod spinning_mesh;
+
+const CUBE_COUNT: usize = 5;
+
+// A row of cubes going from smooth to rough, every other one metallic.
+// Drag with the right mouse button to orbit, scroll to zoom.
+struct PbrShowcase;
+
+impl Genesis for PbrShowcase {
+  fn register_components(&self, world: &mut World) {
+    ale_world::camera::register_components(world);
+    SpinningMesh::register_components(world);
+  }
+
+  fn window(&self) -> DisplaySetting {
+    DisplaySetting::new(Rect {
+      position: Vector2::new(0, 0),
+      size: Vector2::new(800, 600),
+    })
+  }
+
+  fn init(&self, engine: &mut Engine, world: &mut World) -> Result<(), AppError> {
+    let wc_sender = world.get_world_command_sender();
+
+    let camera = Camera::new(
+      Vector3::zero(),
+      Rect {
+        position: Vector2::zero(),
+        size: Vector2::new(800, 600),
+      },
+      60.0,
+    );
+    let orbit_camera = OrbitCamera::new(camera, Vector3::zero(), 8.0);
+    wc_sender
+      .send(WorldCommand::Spawn(SpawnCommand::new(orbit_camera)))
+      .unwrap();
+
+    let cube = engine.resources.meshes.register(Mesh::new_cube());
+    for i in 0..CUBE_COUNT {
+      let position = Vector3::new((i as f32 - (CUBE_COUNT - 1) as f32 / 2.0) * 1.5, 0.0, 0.0);
+      let roughness = (i as f32 + 0.5) / CUBE_COUNT as f32;
+      let metallic = (i % 2) as f32;
+      let spinning_mesh = SpinningMesh::new(cube, position, metallic, roughness);
+      wc_sender
+        .send(WorldCommand::Spawn(SpawnCommand::new(spinning_mesh)))
+        .unwrap();
+    }
+
+    Ok(())
+  }
+}
+
+fn main() {
+  App::new(PbrShowcase)
+    .add_plugin(
+      PbrPlugin::new("hdr/GravelPlaza_Env.hdr")
+        .with_light(Vector3::new(-4.0, 4.0, 4.0), Vector3::new(60.0, 60.0, 60.0))
+        .with_light(Vector3::new(4.0, 2.0, 4.0), Vector3::new(30.0, 30.0, 40.0)),
+    )
+    .run();
+}
@@ -0,0 +1,80 @@
+use ale_data::alevec::Key;
+use ale_data::entity::Entity;
+use ale_data::indexmap::Id;
+use ale_data::wire_component;
+use ale_math::prelude::*;
+use ale_math::transform::AleTransform;
+use ale_math::{Deg, Quaternion, Vector3};
+use ale_opengl::constant::{METALLIC, ROUGHNESS};
+use ale_opengl::renderer::task::{Material, RenderTask, StaticMesh};
+use ale_render::component::Renderable;
+use ale_resources::mesh::Mesh;
+use ale_variable::Variable;
+use ale_world::components::{Spawnable, Tickable};
+use ale_world::world::World;
+
+const DEGREES_PER_SECOND: f32 = 30.0;
+
+// Mesh drawn with Material::Pbr, turning around a tilted axis
+pub struct SpinningMesh {
+  id: Id<Entity>,
+  mesh: Key<Mesh>,
+  transform: AleTransform,
+  metallic: f32,
+  roughness: f32,
+}
+
+impl SpinningMesh {
+  pub fn register_components(world: &mut World) {
+    world.register_components(&[
+      wire_component!(dyn Spawnable, SpinningMesh),
+      wire_component!(dyn Tickable, SpinningMesh),
+      wire_component!(dyn Renderable, SpinningMesh),
+    ]);
+  }
+
+  pub fn new(mesh: Key<Mesh>, position: Vector3<f32>, metallic: f32, roughness: f32) -> SpinningMesh {
+    SpinningMesh {
+      id: Id::new(),
+      mesh,
+      transform: AleTransform::from_position_scale(position, Vector3::new(0.5, 0.5, 0.5)),
+      metallic,
+      roughness,
+    }
+  }
+}
+
+impl Tickable for SpinningMesh {
+  fn fixed_tick(&mut self, _delta_time: f32) {}
+
+  fn tick(&mut self, delta_time: f32) {
+    let axis = Vector3::new(1.0, 1.0, 0.0).normalize();
+    self
+      .transform
+      .rotate(Quaternion::from_axis_angle(axis, Deg(DEGREES_PER_SECOND * delta_time)));
+  }
+}
+
+impl Renderable for SpinningMesh {
+  fn get_render_tasks(&mut self) -> Vec<RenderTask> {
+    vec![RenderTask::StaticMesh(StaticMesh {
+      mesh: self.mesh,
+      material: Material::Pbr,
+      transform: self.transform.matrix(),
+      variables: vec![
+        Variable::F32_1(METALLIC.to_owned(), self.metallic),
+        Variable::F32_1(ROUGHNESS.to_owned(), self.roughness),
+      ],
+    })]
+  }
+}
+
+impl Spawnable for SpinningMesh {
+  fn on_spawn(&mut self) {}
+
+  fn on_kill(&mut self) {}
+
+  fn id(&self) -> Id<Entity> {
+    self.id
+  }
+}
@@ -1,14 +1,14 @@
-use rand::random;
-
 use ale_window::display::DisplaySetting;
 use ale_window::window::Window;
 use ale_app::{ale_app_resource_path, ale_app_run, App, AppError};
 use ale_camera::Camera;
-use ale_input::{Action, Input, Key};
+use ale_camera::projection::Projection;
+use ale_input::{Action, GamepadAxis, GamepadId, Input, Key};
 use ale_math::color::Color;
+use ale_math::random::Random;
 use ale_math::rect::Rect;
 use ale_math::transform::AleTransform;
-use ale_math::{ale_bounding_box_size, Transform, Vector2, Vector3, Zero};
+use ale_math::{Transform, Vector2, Vector3, Zero};
 use ale_opengl::pbr::{
   ale_opengl_pbr_context_new, ale_opengl_pbr_render, ale_opengl_pbr_render_envmap, OpenGLPBRContext,
 };
@@ -26,14 +26,16 @@ use ale_resources::texture::Texture;
 
 use crate::Shape::{Cube, Sphere};
 
+// stick drift below this doesn't move the paddle
+const GAMEPAD_DEAD_ZONE: f32 = 0.15;
+
 fn main() {
-  ale_app_run(
-    Pong,
-    DisplaySetting::new(Rect {
-      position: Vector2::zero(),
-      size: Vector2::new(800, 600),
-    }),
-  );
+  let mut display_setting = DisplaySetting::new(Rect {
+    position: Vector2::zero(),
+    size: Vector2::new(800, 600),
+  });
+  display_setting.close_on_escape = true;
+  ale_app_run(Pong, display_setting);
 }
 
 struct Pong;
@@ -48,7 +50,7 @@ struct Object {
   rigidbody_handle: RigidBodyHandle,
   collider_handle: ColliderHandle,
   mesh_id: MeshId,
-  color: Vector3<f32>,
+  color: Color,
 }
 
 fn ale_create_pong_object(
@@ -57,13 +59,13 @@ fn ale_create_pong_object(
   mesh: &Mesh,
   shape: Shape,
   rigidbody_type: RigidBodyType,
-  color: Vector3<f32>,
+  color: Color,
   gravity_enable: bool,
   is_sensor: bool,
 ) -> Object {
   let mut bb = transform
     .scale_matrix()
-    .transform_vector(ale_bounding_box_size(mesh.bounding_box));
+    .transform_vector(mesh.bounding_box.size());
 
   let rigidbody_shape = match shape {
     Shape::Cube => RigidBodyShape::Cube(bb / 2.0),
@@ -104,7 +106,13 @@ struct State {
   paddle_left_velocity: Vector3<f32>,
   paddle_right_velocity: Vector3<f32>,
 
+  // first connected gamepad plays left, second plays right, otherwise right is AI
+  gamepads: Vec<GamepadId>,
+  gamepad_left_y: f32,
+  gamepad_right_y: f32,
+
   // Game state
+  random: Random,
   should_init: bool,
   score_left: i32,
   score_right: i32,
@@ -132,7 +140,7 @@ impl App<State> for Pong {
       &cube_mesh,
       Cube,
       RigidBodyType::Kinematic,
-      Vector3::new(0.0, 1.0, 0.0),
+      Color::green(),
       false,
       true,
     );
@@ -143,7 +151,7 @@ impl App<State> for Pong {
       &cube_mesh,
       Cube,
       RigidBodyType::Kinematic,
-      Vector3::new(1.0, 0.0, 0.0),
+      Color::red(),
       true,
       true,
     );
@@ -156,7 +164,7 @@ impl App<State> for Pong {
         &cube_mesh,
         Cube,
         RigidBodyType::Static,
-        Vector3::new(0.0, 0.0, 0.0),
+        Color::black(),
         false,
         false,
       ),
@@ -168,7 +176,7 @@ impl App<State> for Pong {
       &ball_mesh,
       Sphere,
       RigidBodyType::Dynamic,
-      Vector3::new(1.0, 1.0, 1.0),
+      Color::white(),
       true,
       false,
     );
@@ -183,10 +191,10 @@ impl App<State> for Pong {
     // ));
     // fly_camera.camera_mut().look_at(Vector3::new(0.0, 0.0, 0.0));
 
-    let mut camera = Camera::new(
+    let mut camera = Camera::with_projection(
       Vector3::new(0.0, 10.0, 0.0),
       window.get_display_info().dimension.clone(),
-      90.0,
+      Projection::perspective_infinite(90.0, 0.1),
     );
     camera.look_at(Vector3::zero());
 
@@ -211,11 +219,15 @@ impl App<State> for Pong {
       paddle_left,
       paddle_right,
       arena,
+      random: Random::new(Random::seed_from_time()),
       should_init: true,
       score_left: 0,
       score_right: 0,
       paddle_left_velocity: Vector3::zero(),
       paddle_right_velocity: Vector3::zero(),
+      gamepads: vec![],
+      gamepad_left_y: 0.0,
+      gamepad_right_y: 0.0,
     })
   }
 
@@ -235,25 +247,47 @@ impl App<State> for Pong {
         Input::Key(Key::S, _, Action::Release, _) => {
           s.paddle_left_velocity -= Vector3::new(0.0, 0.0, 1.0);
         }
+        Input::FramebufferResize { width, height } => {
+          s.camera.set_viewport(Vector2::zero(), Vector2::new(*width, *height));
+        }
+        Input::ContentScale { x, .. } => s.camera.set_content_scale(*x),
+        Input::GamepadConnected(id) => s.gamepads.push(*id),
+        Input::GamepadDisconnected(id) => {
+          s.gamepads.retain(|gamepad| gamepad != id);
+          s.gamepad_left_y = 0.0;
+          s.gamepad_right_y = 0.0;
+        }
+        // stick up is negative, same as W
+        Input::GamepadAxis(id, GamepadAxis::LeftY, value) => {
+          let value = if value.abs() < GAMEPAD_DEAD_ZONE { 0.0 } else { *value };
+          if s.gamepads.get(0) == Some(id) {
+            s.gamepad_left_y = value;
+          } else if s.gamepads.get(1) == Some(id) {
+            s.gamepad_right_y = value;
+          }
+        }
         _ => {}
       }
     }
+  }
 
-    // AI Input
-    if s.paddle_right.transform.position.z < s.ball.transform.position.z {
+  fn fixed_tick(&mut self, s: &mut State, delta_time: f32) {
+    // Second player or AI Input
+    if s.gamepads.len() >= 2 {
+      s.paddle_right_velocity = Vector3::new(0.0, 0.0, s.gamepad_right_y);
+    } else if s.paddle_right.transform.position.z < s.ball.transform.position.z {
       s.paddle_right_velocity = Vector3::new(0.0, 0.0, 1.0);
     } else {
       s.paddle_right_velocity = Vector3::new(0.0, 0.0, -1.0);
     }
-  }
 
-  fn fixed_tick(&mut self, s: &mut State, delta_time: f32) {
     // Move paddles
     let paddle_speed = 12.0f32;
+    let paddle_left_velocity = s.paddle_left_velocity + Vector3::new(0.0, 0.0, s.gamepad_left_y);
     ale_physics_object_position_set(
       &mut s.physics_context,
       s.paddle_left.rigidbody_handle,
-      s.paddle_left.transform.position + (s.paddle_left_velocity * paddle_speed * delta_time),
+      s.paddle_left.transform.position + (paddle_left_velocity * paddle_speed * delta_time),
     );
 
     ale_physics_object_position_set(
@@ -337,6 +371,24 @@ impl App<State> for Pong {
       );
     }
 
+    if s.paddle_right.transform.position.z < -10.0f32 {
+      s.paddle_right.transform.position.z = -10.0f32;
+      ale_physics_object_position_set(
+        &mut s.physics_context,
+        s.paddle_right.rigidbody_handle,
+        s.paddle_right.transform.position,
+      );
+    }
+
+    if s.paddle_right.transform.position.z > 10.0f32 {
+      s.paddle_right.transform.position.z = 10.0f32;
+      ale_physics_object_position_set(
+        &mut s.physics_context,
+        s.paddle_right.rigidbody_handle,
+        s.paddle_right.transform.position,
+      );
+    }
+
     // Check if ball passes through left goal or right goal
     let linvel = ale_physics_object_linear_velocity_get(&mut s.physics_context, s.ball.rigidbody_handle);
 
@@ -390,7 +442,7 @@ impl App<State> for Pong {
       let speed = 20.0f32;
       let initial_velocity = Vector3::new(0.4f32, 0.0, 0.4);
       let random_velocity_base = Vector3::new(0.1f32, 0.0, 0.1);
-      let random_mult: Vector3<f32> = Vector3::new(random(), 0.0, random());
+      let random_mult: Vector3<f32> = Vector3::new(s.random.next_f32(), 0.0, s.random.next_f32());
       let mut velocity = initial_velocity
         + Vector3::new(
         random_mult.x * random_velocity_base.x,
@@ -398,8 +450,8 @@ impl App<State> for Pong {
         random_mult.z * random_velocity_base.z,
       );
 
-      let go_top: bool = random();
-      let go_left: bool = random();
+      let go_top = s.random.next_bool();
+      let go_left = s.random.next_bool();
       if go_left {
         velocity.x = velocity.x * -1.0f32;
       }
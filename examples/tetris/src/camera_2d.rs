@@ -4,7 +4,7 @@ use ale_data::entity::Entity;
 use ale_data::indexmap::Id;
 use ale_data::wire_component;
 use ale_math::rect::Rect;
-use ale_math::Vector3;
+use ale_math::{Vector2, Vector3};
 use ale_world::components::Spawnable;
 use ale_world::world::World;
 
@@ -21,7 +21,7 @@ impl Camera2D {
     ])
   }
 
-  pub fn new(position: Vector3<f32>, rect: Rect) -> Camera2D {
+  pub fn new(position: Vector3<f32>, rect: Rect<i32>) -> Camera2D {
     Camera2D {
       id: Id::new(),
       camera: ale_camera::Camera::new(position, rect, 90.0),
@@ -33,6 +33,14 @@ impl Camera for Camera2D {
   fn get_camera_info(&mut self) -> (Id<Entity>, CameraRenderInfo) {
     (self.id, self.camera.camera_render_info())
   }
+
+  fn set_viewport(&mut self, position: Vector2<i32>, size: Vector2<u32>) {
+    self.camera.set_viewport(position, size);
+  }
+
+  fn set_content_scale(&mut self, content_scale: f32) {
+    self.camera.set_content_scale(content_scale);
+  }
 }
 
 impl Spawnable for Camera2D {
@@ -3,7 +3,7 @@ use ale_app::engine::Engine;
 use ale_app::AppError;
 use ale_math::rect::Rect;
 use ale_math::{Vector2, Vector3, Zero};
-use ale_window::display::{DisplaySetting, TargetMonitor};
+use ale_window::display::DisplaySetting;
 use ale_world::components::Spawnable;
 use ale_world::event::world::{SpawnCommand, WorldCommand};
 use ale_world::world::World;
@@ -26,14 +26,10 @@ impl Genesis for TetrisGame {
   }
 
   fn window(&self) -> DisplaySetting {
-    DisplaySetting {
-      dimension: Rect {
-        position: Vector2::new(0, 0),
-        size: Vector2::new(800, 600),
-      },
-      initial_target: TargetMonitor::PRIMARY,
-      is_hidden: false,
-    }
+    DisplaySetting::new(Rect {
+      position: Vector2::new(0, 0),
+      size: Vector2::new(800, 600),
+    })
   }
 
   fn init(&self, engine: &mut Engine, world: &mut World) -> Result<(), AppError> {
@@ -49,7 +45,7 @@ impl Genesis for TetrisGame {
     let wc_sender = world.get_world_command_sender();
 
     // create world
-    let tetris = GameCoordinator::new(wc_sender.clone());
+    let tetris = GameCoordinator::new(wc_sender.clone(), engine.random.fork());
 
     // send spawn commands to world
     wc_sender
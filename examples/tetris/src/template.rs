@@ -1,8 +1,8 @@
 use std::collections::HashMap;
 
 use ale_math::color::Color;
+use ale_math::random::Random;
 use enumn::N;
-use rand::Rng;
 
 #[derive(Eq, PartialEq, Hash, N)]
 #[repr(usize)]
@@ -93,11 +93,10 @@ impl Templates {
     );
   }
 
-  pub fn random_one_piece(&self) -> RandomTetrisInfo {
-    let mut rand = rand::thread_rng();
-    let block_type = BlockTypeId::n(rand.gen_range(0..self.blocks.len())).unwrap();
+  pub fn random_one_piece(&self, random: &mut Random) -> RandomTetrisInfo {
+    let block_type = BlockTypeId::n(random.range_usize(0, self.blocks.len())).unwrap();
     let blocks_template = self.blocks.get(&block_type).unwrap().clone();
-    let rotation_type = rand.gen_range(0..blocks_template.len());
+    let rotation_type = random.range_usize(0, blocks_template.len());
 
     RandomTetrisInfo {
       rotation_type,
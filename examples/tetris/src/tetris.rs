@@ -5,8 +5,10 @@ use ale_data::timer::{Recurrence, Timer};
 use ale_data::wire_component;
 use ale_input::Action::{Press, Release};
 use ale_input::Input;
-use ale_input::Key::{Down, Left, Right, Space};
+use ale_input::state::InputState;
+use ale_input::Key::{Down, Escape, Left, Right, Space};
 use ale_math::color::Color;
+use ale_math::random::Random;
 use ale_math::Vector2;
 use ale_opengl::renderer::task::{RenderTask, Sprite};
 use ale_render::component::Renderable;
@@ -45,6 +47,7 @@ pub struct TetrisInfo {
 pub struct GameCoordinator {
   pub id: Id<Entity>,
   pub templates: Templates,
+  pub random: Random,
   pub wc_sender: Sender<WorldCommand>,
 
   // Arena state
@@ -57,6 +60,7 @@ pub struct GameCoordinator {
   pub is_right_pressed: bool,
   pub is_left_pressed: bool,
   pub rotate_is_pressed: bool,
+  pub is_paused: bool,
 
   pub move_down_timer: Timer,
 }
@@ -71,7 +75,7 @@ impl GameCoordinator {
     ]);
   }
 
-  pub fn new(wc_sender: Sender<WorldCommand>) -> GameCoordinator {
+  pub fn new(wc_sender: Sender<WorldCommand>, random: Random) -> GameCoordinator {
     let arena = vec![vec![NotFilled; COLUMN_GRID_SIZE]; ROW_GRID_SIZE + HIDDEN_ROW_GRID_SIZE];
     let mut templates = Templates::new();
     templates.add_all();
@@ -79,6 +83,7 @@ impl GameCoordinator {
     GameCoordinator {
       id: Id::new(),
       templates,
+      random,
       wc_sender,
       arena,
       selected: None,
@@ -88,6 +93,7 @@ impl GameCoordinator {
       is_right_pressed: false,
       is_left_pressed: false,
       rotate_is_pressed: false,
+      is_paused: false,
     }
   }
 
@@ -262,8 +268,12 @@ impl Tickable for GameCoordinator {
   }
 
   fn tick(&mut self, delta_time: f32) {
+    if self.is_paused {
+      return;
+    }
+
     if self.selected.is_none() {
-      let random = self.templates.random_one_piece();
+      let random = self.templates.random_one_piece(&mut self.random);
       self.selected = Some(TetrisInfo {
         block_type: random.block_type,
         rotation_type: random.rotation_type,
@@ -289,7 +299,10 @@ impl Spawnable for GameCoordinator {
 }
 
 impl Inputable for GameCoordinator {
-  fn input(&mut self, inputs: &Vec<Input>) {
+  fn input(&mut self, inputs: &Vec<Input>, input_state: &InputState) {
+    if input_state.just_pressed(Escape) {
+      self.is_paused = !self.is_paused;
+    }
     for input in inputs {
       match input {
         Input::Key(Left, _, Press, _) => {